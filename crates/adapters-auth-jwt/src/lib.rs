@@ -0,0 +1,108 @@
+use async_trait::async_trait;
+use base64::engine::general_purpose::STANDARD as B64;
+use base64::Engine;
+use chrono::Utc;
+use core_model::auth::IssuedToken;
+use core_ports::{PasswordHasher, TokenService};
+use jsonwebtoken::{decode, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use serde::{Deserialize, Serialize};
+
+/// 与 Nacos 默认一致：5 小时
+pub const DEFAULT_TOKEN_TTL_SECS: i64 = 18000;
+/// HS256 密钥至少 32 字节
+const MIN_SECRET_LEN: usize = 32;
+
+#[derive(Serialize, Deserialize)]
+struct Claims {
+    sub: String,
+    iat: i64,
+    exp: i64,
+}
+
+/// HS256 签名的 JWT 访问令牌
+pub struct JwtTokenService {
+    encoding: EncodingKey,
+    decoding: DecodingKey,
+    ttl: i64,
+}
+
+impl JwtTokenService {
+    pub fn new(secret: &[u8], ttl_secs: i64) -> anyhow::Result<Self> {
+        if secret.len() < MIN_SECRET_LEN {
+            return Err(anyhow::anyhow!("token secret must be at least {} bytes", MIN_SECRET_LEN));
+        }
+        Ok(Self {
+            encoding: EncodingKey::from_secret(secret),
+            decoding: DecodingKey::from_secret(secret),
+            ttl: ttl_secs.max(1),
+        })
+    }
+
+    /// 密钥为 base64 文本（同 Nacos `token.secret.key`），解码失败时按原文使用
+    pub fn from_secret_text(text: &str, ttl_secs: i64) -> anyhow::Result<Self> {
+        let text = text.trim();
+        let secret = B64.decode(text).unwrap_or_else(|_| text.as_bytes().to_vec());
+        Self::new(&secret, ttl_secs)
+    }
+
+    /// 随机密钥，仅在进程内有效，重启后已签发的令牌全部失效
+    pub fn ephemeral(ttl_secs: i64) -> Self {
+        let secret = format!("{}{}", uuid::Uuid::new_v4().simple(), uuid::Uuid::new_v4().simple());
+        Self::new(secret.as_bytes(), ttl_secs).expect("random secret is long enough")
+    }
+}
+
+impl TokenService for JwtTokenService {
+    fn issue(&self, username: &str) -> anyhow::Result<IssuedToken> {
+        let now = Utc::now().timestamp();
+        let claims = Claims { sub: username.to_string(), iat: now, exp: now + self.ttl };
+        let token = encode(&Header::new(Algorithm::HS256), &claims, &self.encoding)?;
+        Ok(IssuedToken { token, ttl: self.ttl })
+    }
+
+    fn verify(&self, token: &str) -> anyhow::Result<String> {
+        let mut validation = Validation::new(Algorithm::HS256);
+        validation.leeway = 0;
+        let data = decode::<Claims>(token, &self.decoding, &validation)?;
+        Ok(data.claims.sub)
+    }
+}
+
+/// bcrypt 密码摘要；计算在阻塞线程池中进行
+pub struct BcryptHasher {
+    cost: u32,
+    dummy: String,
+}
+
+impl Default for BcryptHasher {
+    fn default() -> Self {
+        Self::with_cost(bcrypt::DEFAULT_COST)
+    }
+}
+
+impl BcryptHasher {
+    /// 构造时按同一成本生成一次占位摘要
+    pub fn with_cost(cost: u32) -> Self {
+        let dummy = bcrypt::hash(uuid::Uuid::new_v4().to_string(), cost).expect("invalid bcrypt cost");
+        Self { cost, dummy }
+    }
+}
+
+#[async_trait]
+impl PasswordHasher for BcryptHasher {
+    async fn hash(&self, password: &str) -> anyhow::Result<String> {
+        let (password, cost) = (password.to_string(), self.cost);
+        Ok(tokio::task::spawn_blocking(move || bcrypt::hash(password, cost)).await??)
+    }
+
+    async fn verify(&self, password: &str, hash: &str) -> bool {
+        let (password, hash) = (password.to_string(), hash.to_string());
+        tokio::task::spawn_blocking(move || bcrypt::verify(password, &hash).unwrap_or(false))
+            .await
+            .unwrap_or(false)
+    }
+
+    fn dummy_hash(&self) -> &str {
+        &self.dummy
+    }
+}
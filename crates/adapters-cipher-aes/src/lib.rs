@@ -0,0 +1,80 @@
+use std::path::Path;
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use base64::engine::general_purpose::STANDARD as B64;
+use base64::Engine;
+use core_ports::ConfigCipher;
+
+const NONCE_LEN: usize = 12;
+
+/// 本地 AES-256-GCM 加密插件，对应 `cipher-aes-` 前缀。
+/// 密文格式：base64(nonce || ciphertext || tag)，调用方给出的关联数据参与 tag 计算
+pub struct LocalAesCipher {
+    key_id: String,
+    cipher: Aes256Gcm,
+}
+
+impl LocalAesCipher {
+    pub fn new(key_id: impl Into<String>, key: &[u8; 32]) -> Self {
+        Self {
+            key_id: key_id.into(),
+            cipher: Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key)),
+        }
+    }
+
+    /// 密钥文件内容为 32 字节原始密钥，或其 base64 文本；密钥 ID 取文件名（不含扩展名）
+    pub fn from_key_file(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let raw = std::fs::read(path)?;
+        let key: Vec<u8> = if raw.len() == 32 {
+            raw
+        } else {
+            let text = String::from_utf8(raw)
+                .map_err(|_| anyhow::anyhow!("key file must hold 32 raw bytes or base64 text"))?;
+            B64.decode(text.trim())?
+        };
+        let key: [u8; 32] = key
+            .try_into()
+            .map_err(|_| anyhow::anyhow!("AES-256 key must be 32 bytes"))?;
+        let key_id = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("default")
+            .to_string();
+        Ok(Self::new(key_id, &key))
+    }
+}
+
+impl ConfigCipher for LocalAesCipher {
+    fn name(&self) -> &str {
+        "aes"
+    }
+
+    fn key_ref(&self) -> String {
+        format!("local-aes:{}", self.key_id)
+    }
+
+    fn encrypt(&self, plain: &str, aad: &[u8]) -> anyhow::Result<String> {
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let sealed = self
+            .cipher
+            .encrypt(&nonce, Payload { msg: plain.as_bytes(), aad })
+            .map_err(|_| anyhow::anyhow!("encrypt failed"))?;
+        let mut out = nonce.to_vec();
+        out.extend_from_slice(&sealed);
+        Ok(B64.encode(out))
+    }
+
+    fn decrypt(&self, cipher: &str, aad: &[u8]) -> anyhow::Result<String> {
+        let raw = B64.decode(cipher.trim())?;
+        if raw.len() < NONCE_LEN {
+            return Err(anyhow::anyhow!("ciphertext too short"));
+        }
+        let (nonce, body) = raw.split_at(NONCE_LEN);
+        let plain = self
+            .cipher
+            .decrypt(Nonce::from_slice(nonce), Payload { msg: body, aad })
+            .map_err(|_| anyhow::anyhow!("decrypt failed: wrong key, corrupted ciphertext or sealed for another config"))?;
+        Ok(String::from_utf8(plain)?)
+    }
+}
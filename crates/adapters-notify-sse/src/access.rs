@@ -0,0 +1,161 @@
+use core_model::auth::{Action, Resource};
+use core_model::config::ConfigKey;
+use core_usecase::auth::Grants;
+use serde_json::Value;
+
+use crate::filter::EventFilter;
+use crate::hub::Topic;
+
+/// API 认证层放入请求扩展的订阅方身份；grants 为 None 表示未启用认证，不做权限检查
+#[derive(Debug, Clone)]
+pub struct EventPrincipal {
+    pub name: String,
+    pub grants: Option<Grants>,
+}
+
+fn patterns_or_any(patterns: &[String]) -> Vec<&str> {
+    if patterns.is_empty() {
+        vec!["*"]
+    } else {
+        patterns.iter().map(String::as_str).collect()
+    }
+}
+
+/// 事件中的配置 key；缺字段时返回 None
+fn config_key(v: &Value) -> Option<ConfigKey> {
+    Some(ConfigKey {
+        namespace: v.get("namespace")?.as_str()?.to_string(),
+        group: v.get("group")?.as_str()?.to_string(),
+        data_id: v.get("data_id")?.as_str()?.to_string(),
+    })
+}
+
+impl EventPrincipal {
+    pub fn is_admin(&self) -> bool {
+        self.grants.as_ref().is_none_or(|g| g.admin)
+    }
+
+    pub fn can_read(&self, resource: &Resource) -> bool {
+        self.grants.as_ref().is_none_or(|g| g.allows(resource, Action::Read))
+    }
+
+    /// 订阅范围须整体可读：各维度的通配模式按字面作为资源检查（同 Schema 作用范围），未指定的维度视为 `*`
+    pub(crate) fn can_subscribe(&self, topic: Topic, filter: &EventFilter) -> bool {
+        match topic {
+            Topic::Instance => patterns_or_any(&filter.services)
+                .into_iter()
+                .all(|s| self.can_read(&Resource::Service { group: "*".into(), service: s.into() })),
+            Topic::Config if !filter.configs.is_empty() => {
+                filter.configs.iter().all(|k| self.can_read(&Resource::Config(k.clone())))
+            }
+            Topic::Config => {
+                let (groups, data_ids) = (patterns_or_any(&filter.groups), patterns_or_any(&filter.data_ids));
+                patterns_or_any(&filter.namespaces).into_iter().all(|ns| {
+                    groups.iter().all(|g| {
+                        data_ids.iter().all(|d| {
+                            let key = ConfigKey { namespace: ns.into(), group: (*g).into(), data_id: (*d).into() };
+                            self.can_read(&Resource::Config(key))
+                        })
+                    })
+                })
+            }
+        }
+    }
+
+    /// 去掉无读权限的部分：配置与实例事件整条丢弃，变更集事件只保留可读的 items，全部不可读则不推送
+    pub(crate) fn visible(&self, msg: &Value) -> Option<Value> {
+        if self.grants.is_none() {
+            return Some(msg.clone());
+        }
+        let readable = |v: &Value| config_key(v).is_some_and(|k| self.can_read(&Resource::Config(k)));
+        match msg.get("topic").and_then(|t| t.as_str()) {
+            Some("instance") => {
+                let service = msg.get("service_name").and_then(|s| s.as_str())?;
+                // 旧式事件不带分组，只有对全部分组可读时才推送
+                let group = msg.pointer("/instance/group_name").and_then(|g| g.as_str()).unwrap_or("*");
+                let resource = Resource::Service { group: group.into(), service: service.into() };
+                self.can_read(&resource).then(|| msg.clone())
+            }
+            _ => match msg.get("items").and_then(|x| x.as_array()) {
+                Some(items) => {
+                    let hit: Vec<Value> = items.iter().filter(|i| readable(i)).cloned().collect();
+                    if hit.is_empty() {
+                        return None;
+                    }
+                    let mut out = msg.clone();
+                    out["items"] = Value::Array(hit);
+                    Some(out)
+                }
+                None => readable(msg).then(|| msg.clone()),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core_model::auth::Permission;
+    use serde_json::json;
+
+    use super::*;
+
+    fn reader(resources: &[&str]) -> EventPrincipal {
+        let permissions = resources
+            .iter()
+            .map(|r| Permission { role: "reader".into(), resource: (*r).into(), action: Action::Read })
+            .collect();
+        EventPrincipal { name: "alice".into(), grants: Some(Grants { admin: false, permissions }) }
+    }
+
+    fn namespaces(ns: &[&str]) -> EventFilter {
+        EventFilter { namespaces: ns.iter().map(|n| (*n).into()).collect(), ..Default::default() }
+    }
+
+    #[test]
+    fn subscribe_denied_outside_readable_namespace() {
+        let p = reader(&["dev:*:*"]);
+        assert!(p.can_subscribe(Topic::Config, &namespaces(&["dev"])));
+        assert!(!p.can_subscribe(Topic::Config, &namespaces(&["prod"])));
+        assert!(!p.can_subscribe(Topic::Config, &namespaces(&["dev", "prod"])));
+        // 未指定命名空间即订阅全部，须对全部可读
+        assert!(!p.can_subscribe(Topic::Config, &EventFilter::default()));
+    }
+
+    #[test]
+    fn config_event_in_denied_namespace_is_dropped() {
+        let p = reader(&["dev:*:*"]);
+        let event = json!({ "topic": "config", "namespace": "prod", "group": "DEFAULT_GROUP", "data_id": "app.yaml" });
+        assert_eq!(p.visible(&event), None);
+    }
+
+    #[test]
+    fn changeset_keeps_only_readable_items() {
+        let p = reader(&["dev:*:*"]);
+        let item = |ns: &str| json!({ "namespace": ns, "group": "DEFAULT_GROUP", "data_id": "app.yaml" });
+        let event = json!({ "topic": "config", "changeset_id": "c1", "items": [item("dev"), item("prod")] });
+        let out = p.visible(&event).unwrap();
+        assert_eq!(out["items"], json!([item("dev")]));
+        assert_eq!(out["changeset_id"], "c1");
+
+        let hidden = json!({ "topic": "config", "changeset_id": "c2", "items": [item("prod")] });
+        assert_eq!(p.visible(&hidden), None);
+    }
+
+    #[test]
+    fn instance_event_without_group_needs_every_group() {
+        let event = json!({ "topic": "instance", "service_name": "order", "instance": { "ip": "10.0.0.1" } });
+        assert_eq!(reader(&["public:DEFAULT_GROUP:naming/order"]).visible(&event), None);
+        assert!(reader(&["public:*:naming/order"]).visible(&event).is_some());
+
+        let grouped = json!({ "topic": "instance", "service_name": "order", "instance": { "group_name": "DEFAULT_GROUP" } });
+        assert!(reader(&["public:DEFAULT_GROUP:naming/order"]).visible(&grouped).is_some());
+    }
+
+    #[test]
+    fn everything_visible_without_auth() {
+        let p = EventPrincipal { name: "anonymous".into(), grants: None };
+        assert!(p.can_subscribe(Topic::Config, &EventFilter::default()));
+        let event = json!({ "topic": "instance", "service_name": "order" });
+        assert_eq!(p.visible(&event), Some(event));
+    }
+}
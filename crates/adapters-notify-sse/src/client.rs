@@ -0,0 +1,114 @@
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::OnceLock;
+use axum::http::HeaderMap;
+use core_model::client::ClientInfo;
+
+/// 可信反向代理：单个 IP 或 CIDR 网段
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct ProxyNet {
+    addr: IpAddr,
+    prefix: u8,
+}
+
+impl ProxyNet {
+    fn parse(raw: &str) -> Option<Self> {
+        let (addr, prefix) = match raw.split_once('/') {
+            Some((a, p)) => (a.trim().parse::<IpAddr>().ok()?, Some(p.trim().parse::<u8>().ok()?)),
+            None => (raw.trim().parse::<IpAddr>().ok()?, None),
+        };
+        let max = if addr.is_ipv4() { 32 } else { 128 };
+        let prefix = prefix.unwrap_or(max);
+        (prefix <= max).then_some(Self { addr, prefix })
+    }
+
+    fn contains(&self, ip: IpAddr) -> bool {
+        let (net, ip, bits) = match (self.addr, ip.to_canonical()) {
+            (IpAddr::V4(n), IpAddr::V4(i)) => (u32::from(n) as u128, u32::from(i) as u128, 32),
+            (IpAddr::V6(n), IpAddr::V6(i)) => (u128::from(n), u128::from(i), 128),
+            _ => return false,
+        };
+        let shift = bits - self.prefix as u32;
+        shift >= bits || net >> shift == ip >> shift
+    }
+}
+
+/// TRUSTED_PROXIES：逗号分隔的代理 IP 或网段（如 `10.0.0.0/8,127.0.0.1`），未设置时不信任任何转发头
+fn trusted_proxies() -> &'static [ProxyNet] {
+    static TRUSTED: OnceLock<Vec<ProxyNet>> = OnceLock::new();
+    TRUSTED.get_or_init(|| {
+        let raw = std::env::var("TRUSTED_PROXIES").unwrap_or_default();
+        raw.split(',')
+            .map(str::trim)
+            .filter(|p| !p.is_empty())
+            .filter_map(|p| {
+                let net = ProxyNet::parse(p);
+                if net.is_none() {
+                    tracing::warn!("ignoring invalid TRUSTED_PROXIES entry `{}`", p);
+                }
+                net
+            })
+            .collect()
+    })
+}
+
+fn is_trusted(ip: IpAddr) -> bool {
+    trusted_proxies().iter().any(|n| n.contains(ip))
+}
+
+/// 客户端 IP：对端为可信代理时，从 X-Forwarded-For 右侧起跳过可信代理取第一跳，其次 X-Real-IP；
+/// 否则直接取对端地址，转发头由客户端任意填写，不能用于灰度、审计与限流
+pub fn client_ip(headers: &HeaderMap, peer: Option<SocketAddr>) -> String {
+    let Some(peer) = peer.map(|p| p.ip().to_canonical()) else {
+        return String::new();
+    };
+    if !is_trusted(peer) {
+        return peer.to_string();
+    }
+    let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok());
+    let forwarded: Vec<IpAddr> = header("x-forwarded-for")
+        .map(|v| v.split(',').filter_map(|hop| hop.trim().parse::<IpAddr>().ok()).collect())
+        .unwrap_or_default();
+    forwarded
+        .iter()
+        .rev()
+        .find(|ip| !is_trusted(**ip))
+        .or(forwarded.first())
+        .copied()
+        .or_else(|| header("x-real-ip").and_then(|v| v.trim().parse().ok()))
+        .unwrap_or(peer)
+        .to_string()
+}
+
+/// 解析 `k1=v1,k2=v2` 形式的标签
+pub fn parse_labels(raw: &str) -> HashMap<String, String> {
+    raw.split(',')
+        .filter_map(|kv| kv.split_once('='))
+        .map(|(k, v)| (k.trim().to_string(), v.trim().to_string()))
+        .filter(|(k, _)| !k.is_empty())
+        .collect()
+}
+
+/// 标签来自 Client-Labels 头；EventSource 无法设置请求头，故也接受 labels 查询参数
+pub fn client_info(headers: &HeaderMap, peer: Option<SocketAddr>, labels: Option<&str>) -> ClientInfo {
+    let mut all = headers
+        .get("client-labels")
+        .and_then(|v| v.to_str().ok())
+        .map(parse_labels)
+        .unwrap_or_default();
+    if let Some(raw) = labels {
+        all.extend(parse_labels(raw));
+    }
+    ClientInfo { ip: client_ip(headers, peer), labels: all }
+}
+
+/// 应用名：Client-AppName 头 > app_name 查询参数
+pub fn app_name(headers: &HeaderMap, param: Option<&str>) -> Option<String> {
+    headers
+        .get("client-appname")
+        .and_then(|v| v.to_str().ok())
+        .or(param)
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(str::to_string)
+}
@@ -0,0 +1,56 @@
+use std::sync::Arc;
+use async_trait::async_trait;
+use core_model::config::{BetaRule, ConfigKey};
+use core_model::event::{ConfigChangeEvent, InstanceChangeEvent};
+use core_model::instance::ServiceName;
+use core_ports::Notifier;
+
+/// 组合通知器：同一事件依次投递给所有挂载的推送通道（SSE、WebSocket、Webhook 等）
+#[derive(Clone, Default)]
+pub struct FanoutNotifier {
+    targets: Vec<Arc<dyn Notifier>>,
+}
+
+impl FanoutNotifier {
+    pub fn new(targets: Vec<Arc<dyn Notifier>>) -> Self {
+        Self { targets }
+    }
+
+    pub fn attach(&mut self, target: Arc<dyn Notifier>) {
+        self.targets.push(target);
+    }
+}
+
+#[async_trait]
+impl Notifier for FanoutNotifier {
+    async fn notify_config_change(&self, key: &ConfigKey) {
+        for t in &self.targets {
+            t.notify_config_change(key).await;
+        }
+    }
+    async fn notify_config_beta_change(&self, key: &ConfigKey, rule: &BetaRule) {
+        for t in &self.targets {
+            t.notify_config_beta_change(key, rule).await;
+        }
+    }
+    async fn notify_config_batch(&self, changeset: &str, revision: i64, keys: &[ConfigKey]) {
+        for t in &self.targets {
+            t.notify_config_batch(changeset, revision, keys).await;
+        }
+    }
+    async fn notify_config_event(&self, event: &ConfigChangeEvent) {
+        for t in &self.targets {
+            t.notify_config_event(event).await;
+        }
+    }
+    async fn notify_instance_change(&self, service: &ServiceName) {
+        for t in &self.targets {
+            t.notify_instance_change(service).await;
+        }
+    }
+    async fn notify_instance_event(&self, event: &InstanceChangeEvent) {
+        for t in &self.targets {
+            t.notify_instance_event(event).await;
+        }
+    }
+}
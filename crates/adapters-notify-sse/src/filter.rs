@@ -0,0 +1,92 @@
+use core_model::config::ConfigKey;
+use core_model::pattern::glob_match;
+use serde::Serialize;
+use serde_json::Value;
+
+/// 订阅过滤条件：每个维度为逗号分隔的通配模式（`*` / `?`），未指定的维度不限
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct EventFilter {
+    pub namespaces: Vec<String>,
+    pub groups: Vec<String>,
+    pub data_ids: Vec<String>,
+    pub services: Vec<String>,
+    /// 按配置订阅（各字段支持通配），非空时事件须命中其中之一；WebSocket 动态订阅使用
+    pub configs: Vec<ConfigKey>,
+    /// 配置事件是否附带明文 content；默认不附带，仅认证后的订阅方可开启，且仍按读权限过滤
+    pub content: bool,
+}
+
+/// 去掉配置事件中的明文内容
+pub(crate) fn strip_content(mut msg: Value) -> Value {
+    if let Some(fields) = msg.as_object_mut() {
+        fields.remove("content");
+    }
+    msg
+}
+
+fn patterns(raw: Option<&str>) -> Vec<String> {
+    raw.map(|s| {
+        s.split(',')
+            .map(str::trim)
+            .filter(|p| !p.is_empty())
+            .map(String::from)
+            .collect()
+    })
+    .unwrap_or_default()
+}
+
+fn any_match(patterns: &[String], value: Option<&str>) -> bool {
+    patterns.is_empty() || value.is_some_and(|v| patterns.iter().any(|p| glob_match(p, v)))
+}
+
+impl EventFilter {
+    pub fn parse(namespace: Option<&str>, group: Option<&str>, data_id: Option<&str>, service: Option<&str>) -> Self {
+        Self {
+            namespaces: patterns(namespace),
+            groups: patterns(group),
+            data_ids: patterns(data_id),
+            services: patterns(service),
+            configs: Vec::new(),
+            content: false,
+        }
+    }
+
+    fn matches_key(&self, v: &Value) -> bool {
+        let field = |name: &str| v.get(name).and_then(|x| x.as_str());
+        let (ns, group, data_id) = (field("namespace"), field("group"), field("data_id"));
+        any_match(&self.namespaces, ns)
+            && any_match(&self.groups, group)
+            && any_match(&self.data_ids, data_id)
+            && (self.configs.is_empty()
+                || self.configs.iter().any(|k| {
+                    let hit = |p: &str, v: Option<&str>| v.is_some_and(|v| glob_match(p, v));
+                    hit(&k.namespace, ns) && hit(&k.group, group) && hit(&k.data_id, data_id)
+                }))
+    }
+
+    /// 返回应推送给该订阅者的事件；变更集事件只保留命中的 items，全部未命中则不推送
+    pub fn apply(&self, msg: &Value) -> Option<Value> {
+        let out = self.select(msg)?;
+        Some(if self.content { out } else { strip_content(out) })
+    }
+
+    fn select(&self, msg: &Value) -> Option<Value> {
+        match msg.get("topic").and_then(|t| t.as_str()) {
+            Some("instance") => {
+                any_match(&self.services, msg.get("service_name").and_then(|s| s.as_str())).then(|| msg.clone())
+            }
+            _ => match msg.get("items").and_then(|x| x.as_array()) {
+                Some(items) => {
+                    let hit: Vec<Value> = items.iter().filter(|i| self.matches_key(i)).cloned().collect();
+                    if hit.is_empty() {
+                        return None;
+                    }
+                    let mut out = msg.clone();
+                    out["items"] = Value::Array(hit);
+                    Some(out)
+                }
+                None => self.matches_key(msg).then(|| msg.clone()),
+            },
+        }
+    }
+}
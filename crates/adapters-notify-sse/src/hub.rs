@@ -0,0 +1,296 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use async_trait::async_trait;
+use core_model::config::{BetaRule, ConfigKey};
+use core_model::event::{ConfigChangeEvent, InstanceChangeEvent};
+use core_model::instance::ServiceName;
+use core_ports::Notifier;
+use futures::Stream;
+use serde::{Serialize, Serializer};
+use serde_json::Value;
+use tokio::sync::{broadcast, mpsc};
+use uuid::Uuid;
+
+use crate::filter::{strip_content, EventFilter};
+
+/// 每个过滤订阅者独立的队列长度；队列满时该订阅者被摘除，随后从重放日志补齐
+const SUBSCRIBER_QUEUE: usize = 256;
+/// 默认保留最近的事件条数，供断线重连与慢订阅者补发
+pub const DEFAULT_REPLAY_CAPACITY: usize = 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Topic {
+    Config,
+    Instance,
+}
+
+impl Topic {
+    pub fn parse(s: &str) -> Self {
+        if s == "instance" { Topic::Instance } else { Topic::Config }
+    }
+}
+
+/// 事件 ID，格式 `{boot}-{seq}`：boot 为进程启动时随机生成的纪元，seq 在进程内跨 topic 单调递增。
+/// 重启后纪元改变，客户端回传的旧 ID 不会与新序号混淆
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EventId {
+    pub boot: u32,
+    pub seq: u64,
+}
+
+impl fmt::Display for EventId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:08x}-{}", self.boot, self.seq)
+    }
+}
+
+impl FromStr for EventId {
+    type Err = std::num::ParseIntError;
+
+    /// 不带纪元的纯数字视为纪元 0，必然与当前进程不符
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once('-') {
+            Some((boot, seq)) => Ok(EventId { boot: u32::from_str_radix(boot, 16)?, seq: seq.parse()? }),
+            None => Ok(EventId { boot: 0, seq: s.parse()? }),
+        }
+    }
+}
+
+impl Serialize for EventId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// 带全局 ID 的事件，ID 作为 SSE 的 `id`
+#[derive(Debug, Clone)]
+pub struct HubEvent {
+    pub id: EventId,
+    pub data: Value,
+}
+
+/// 推送给订阅者的内容
+#[derive(Debug, Clone)]
+pub enum Delivery {
+    Event(HubEvent),
+    /// 缺失的事件已不在重放日志中（或来自上一次启动），客户端需全量刷新；id 为当前最新 ID
+    Resync { id: EventId },
+}
+
+impl Delivery {
+    /// 推送内容；SSE 与 WebSocket 共用
+    pub fn payload(&self) -> Value {
+        match self {
+            Delivery::Event(e) => e.data.clone(),
+            Delivery::Resync { .. } => serde_json::json!({ "topic": "resync", "reason": "events lost, reload full state" }),
+        }
+    }
+
+    pub fn id(&self) -> EventId {
+        match self {
+            Delivery::Event(e) => e.id,
+            Delivery::Resync { id } => *id,
+        }
+    }
+}
+
+struct Subscriber {
+    topic: Topic,
+    filter: EventFilter,
+    tx: mpsc::Sender<HubEvent>,
+    lagged: Arc<AtomicBool>,
+}
+
+struct HubState {
+    seq: u64,
+    log: VecDeque<(Topic, HubEvent)>,
+    capacity: usize,
+    subscribers: Vec<Subscriber>,
+}
+
+struct Attached {
+    /// None 表示游标之后的事件已丢失
+    backlog: Option<Vec<HubEvent>>,
+    head: EventId,
+    rx: mpsc::Receiver<HubEvent>,
+    lagged: Arc<AtomicBool>,
+}
+
+#[derive(Clone)]
+pub struct SseHub {
+    pub tx_config: broadcast::Sender<Value>,
+    pub tx_instance: broadcast::Sender<Value>,
+    /// 本进程的事件纪元
+    boot: u32,
+    state: Arc<Mutex<HubState>>,
+}
+
+impl SseHub {
+    pub fn new() -> Self {
+        Self::with_replay_capacity(DEFAULT_REPLAY_CAPACITY)
+    }
+
+    pub fn with_replay_capacity(capacity: usize) -> Self {
+        let (tx_config, _) = broadcast::channel::<Value>(1024);
+        let (tx_instance, _) = broadcast::channel::<Value>(1024);
+        let state = HubState { seq: 0, log: VecDeque::new(), capacity, subscribers: Vec::new() };
+        // 纪元 0 留给不带纪元的旧格式 ID
+        let boot = (Uuid::new_v4().as_u128() as u32).max(1);
+        Self { tx_config, tx_instance, boot, state: Arc::new(Mutex::new(state)) }
+    }
+
+    /// 当前最新事件 ID
+    pub fn head(&self) -> EventId {
+        self.event_id(self.state.lock().unwrap().seq)
+    }
+
+    fn event_id(&self, seq: u64) -> EventId {
+        EventId { boot: self.boot, seq }
+    }
+
+    /// 按过滤条件订阅事件流。`last_event_id` 为客户端已收到的最后 ID：
+    /// 先补发重放日志中其后的命中事件，再衔接实时事件；缺口超出日志范围或 ID 来自其他纪元（服务重启）时先推送 Resync。
+    /// 订阅者队列积压时不丢事件，而是按游标重新挂载并从日志补齐。
+    pub fn stream(&self, topic: Topic, filter: EventFilter, last_event_id: Option<EventId>) -> impl Stream<Item = Delivery> + Send + 'static {
+        let hub = self.clone();
+        async_stream::stream! {
+            let mut cursor = last_event_id;
+            loop {
+                let Attached { backlog, head, mut rx, lagged } = hub.attach(topic, &filter, cursor);
+                match backlog {
+                    Some(events) => {
+                        for e in events {
+                            yield Delivery::Event(e);
+                        }
+                    }
+                    None => yield Delivery::Resync { id: head },
+                }
+                cursor = Some(head);
+                while let Some(e) = rx.recv().await {
+                    cursor = Some(e.id);
+                    yield Delivery::Event(e);
+                }
+                if !lagged.load(Ordering::Acquire) {
+                    break;
+                }
+            }
+        }
+    }
+
+    /// 在同一把锁内计算补发内容并登记订阅者，保证补发与实时事件之间不漏不重
+    fn attach(&self, topic: Topic, filter: &EventFilter, after: Option<EventId>) -> Attached {
+        let (tx, rx) = mpsc::channel(SUBSCRIBER_QUEUE);
+        let lagged = Arc::new(AtomicBool::new(false));
+        let mut state = self.state.lock().unwrap();
+        let backlog = match after.map(|a| (a.boot, a.seq)) {
+            None => Some(Vec::new()),
+            Some((boot, _)) if boot != self.boot => None,
+            Some((_, a)) if a > state.seq => None,
+            Some((_, a)) => {
+                let oldest = state.log.front().map(|(_, e)| e.id.seq).unwrap_or(state.seq + 1);
+                if a + 1 < oldest {
+                    None
+                } else {
+                    Some(
+                        state
+                            .log
+                            .iter()
+                            .filter(|(t, e)| *t == topic && e.id.seq > a)
+                            .filter_map(|(_, e)| filter.apply(&e.data).map(|data| HubEvent { id: e.id, data }))
+                            .collect(),
+                    )
+                }
+            }
+        };
+        state.subscribers.push(Subscriber { topic, filter: filter.clone(), tx, lagged: lagged.clone() });
+        Attached { backlog, head: self.event_id(state.seq), rx, lagged }
+    }
+
+    fn publish(&self, topic: Topic, payload: Value) {
+        let mut state = self.state.lock().unwrap();
+        state.seq += 1;
+        let id = self.event_id(state.seq);
+        // 重放日志不保留明文内容，补发的配置事件只带 md5
+        state.log.push_back((topic, HubEvent { id, data: strip_content(payload.clone()) }));
+        while state.log.len() > state.capacity {
+            state.log.pop_front();
+        }
+        state.subscribers.retain(|s| {
+            if s.topic != topic {
+                return !s.tx.is_closed();
+            }
+            let Some(data) = s.filter.apply(&payload) else {
+                return !s.tx.is_closed();
+            };
+            match s.tx.try_send(HubEvent { id, data }) {
+                Ok(()) => true,
+                Err(mpsc::error::TrySendError::Full(_)) => {
+                    // 摘除后接收端读完队列即按游标重新挂载
+                    s.lagged.store(true, Ordering::Release);
+                    false
+                }
+                Err(mpsc::error::TrySendError::Closed(_)) => false,
+            }
+        });
+        drop(state);
+        let tx = match topic {
+            Topic::Config => &self.tx_config,
+            Topic::Instance => &self.tx_instance,
+        };
+        let _ = tx.send(payload);
+    }
+}
+
+#[async_trait]
+impl Notifier for SseHub {
+    async fn notify_config_change(&self, key: &ConfigKey) {
+        let payload = serde_json::json!({
+            "topic": "config",
+            "namespace": key.namespace,
+            "group": key.group,
+            "data_id": key.data_id
+        });
+        self.publish(Topic::Config, payload);
+    }
+    async fn notify_config_beta_change(&self, key: &ConfigKey, rule: &BetaRule) {
+        let payload = serde_json::json!({
+            "topic": "config",
+            "namespace": key.namespace,
+            "group": key.group,
+            "data_id": key.data_id,
+            "beta": true,
+            "beta_ips": rule.ips,
+            "beta_labels": rule.labels
+        });
+        self.publish(Topic::Config, payload);
+    }
+    async fn notify_config_batch(&self, changeset: &str, revision: i64, keys: &[ConfigKey]) {
+        let items: Vec<Value> = keys
+            .iter()
+            .map(|k| serde_json::json!({ "namespace": k.namespace, "group": k.group, "data_id": k.data_id }))
+            .collect();
+        let payload = serde_json::json!({
+            "topic": "config",
+            "changeset": changeset,
+            "revision": revision,
+            "items": items
+        });
+        self.publish(Topic::Config, payload);
+    }
+    async fn notify_config_event(&self, event: &ConfigChangeEvent) {
+        self.publish(Topic::Config, event.payload());
+    }
+    async fn notify_instance_change(&self, service: &ServiceName) {
+        let payload = serde_json::json!({
+            "topic": "instance",
+            "service_name": service.0
+        });
+        self.publish(Topic::Instance, payload);
+    }
+    async fn notify_instance_event(&self, event: &InstanceChangeEvent) {
+        self.publish(Topic::Instance, event.payload());
+    }
+}
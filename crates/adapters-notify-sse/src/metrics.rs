@@ -0,0 +1,85 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use chrono::Utc;
+use serde::Serialize;
+
+use crate::filter::EventFilter;
+use crate::hub::{Delivery, EventId};
+
+/// 单个推送连接的统计
+#[derive(Debug, Clone, Serialize)]
+pub struct ConnectionStats {
+    pub id: String,
+    /// sse / ws
+    pub transport: String,
+    /// WebSocket 连接为当前订阅的 topic，逗号分隔
+    pub topic: String,
+    pub ip: String,
+    pub app_name: Option<String>,
+    pub filter: EventFilter,
+    pub connected_at: i64,
+    pub events_sent: u64,
+    /// 因缺口过大推送 resync 的次数
+    pub resyncs: u64,
+    pub last_event_id: Option<EventId>,
+    pub last_event_at: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MetricsSnapshot {
+    pub active_connections: usize,
+    pub total_connections: u64,
+    pub total_events_sent: u64,
+    pub connections: Vec<ConnectionStats>,
+}
+
+/// 连接级统计，连接断开时移除；累计值保留
+#[derive(Default)]
+pub struct SseMetrics {
+    connections: Mutex<HashMap<String, ConnectionStats>>,
+    total_connections: AtomicU64,
+    total_events_sent: AtomicU64,
+}
+
+impl SseMetrics {
+    pub(crate) fn open(&self, stats: ConnectionStats) {
+        self.total_connections.fetch_add(1, Ordering::Relaxed);
+        self.connections.lock().unwrap().insert(stats.id.clone(), stats);
+    }
+
+    pub(crate) fn close(&self, id: &str) {
+        self.connections.lock().unwrap().remove(id);
+    }
+
+    /// WebSocket 连接调整订阅后更新
+    pub(crate) fn resubscribe(&self, id: &str, topic: String, filter: EventFilter) {
+        if let Some(c) = self.connections.lock().unwrap().get_mut(id) {
+            c.topic = topic;
+            c.filter = filter;
+        }
+    }
+
+    pub(crate) fn sent(&self, id: &str, delivery: &Delivery) {
+        self.total_events_sent.fetch_add(1, Ordering::Relaxed);
+        if let Some(c) = self.connections.lock().unwrap().get_mut(id) {
+            c.events_sent += 1;
+            if matches!(delivery, Delivery::Resync { .. }) {
+                c.resyncs += 1;
+            }
+            c.last_event_id = Some(delivery.id());
+            c.last_event_at = Some(Utc::now().timestamp());
+        }
+    }
+
+    pub fn snapshot(&self) -> MetricsSnapshot {
+        let mut connections: Vec<ConnectionStats> = self.connections.lock().unwrap().values().cloned().collect();
+        connections.sort_by_key(|c| c.connected_at);
+        MetricsSnapshot {
+            active_connections: connections.len(),
+            total_connections: self.total_connections.load(Ordering::Relaxed),
+            total_events_sent: self.total_events_sent.load(Ordering::Relaxed),
+            connections,
+        }
+    }
+}
@@ -0,0 +1,269 @@
+use std::net::SocketAddr;
+use std::sync::Arc;
+use axum::extract::{ConnectInfo, Query, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::Json;
+use axum::routing::get;
+use axum::{Extension, Router};
+use chrono::Utc;
+use core_model::auth::Resource;
+use core_model::listener::WatchTransport;
+use core_ports::{BetaConfigStore, ConfigStore, ConfigWatcherRegistry};
+use futures::Stream;
+use serde::Deserialize;
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::access::EventPrincipal;
+use crate::client::{app_name, client_info};
+use crate::filter::EventFilter;
+use crate::hub::{Delivery, EventId, SseHub, Topic};
+use crate::metrics::{ConnectionStats, SseMetrics};
+use crate::watch::{beta_event_matches, parse_watch_param, watchers_for};
+use crate::ws::ws_events;
+
+/// 校验访问令牌，返回 false 时拒绝订阅
+pub type TokenValidator = Arc<dyn Fn(&str) -> bool + Send + Sync>;
+
+/// WebSocket 订阅时比对 MD5 所需的配置读取
+#[derive(Clone)]
+pub struct ConfigSource {
+    pub store: Arc<dyn ConfigStore>,
+    pub beta: Arc<dyn BetaConfigStore>,
+}
+
+/// 推送服务（SSE 与 WebSocket）：鉴权、服务端过滤、断线续传、监听登记与连接统计
+#[derive(Clone)]
+pub struct SseService {
+    pub(crate) hub: Arc<SseHub>,
+    pub(crate) watchers: Option<Arc<dyn ConfigWatcherRegistry>>,
+    pub(crate) configs: Option<ConfigSource>,
+    pub(crate) auth_required: bool,
+    pub(crate) validator: Option<TokenValidator>,
+    pub(crate) metrics: Arc<SseMetrics>,
+}
+
+impl SseService {
+    /// 默认要求认证：只接受认证层给出的身份，或经 token_validator 校验通过的令牌
+    pub fn new(hub: Arc<SseHub>) -> Self {
+        Self { hub, watchers: None, configs: None, auth_required: true, validator: None, metrics: Arc::default() }
+    }
+
+    /// 按 SSE_AUTH_REQUIRED 决定是否鉴权（默认开启，`0` / `false` 关闭）
+    pub fn auth_from_env(self) -> Self {
+        let required = std::env::var("SSE_AUTH_REQUIRED")
+            .map(|v| !matches!(v.as_str(), "0" | "false" | "False" | "FALSE"))
+            .unwrap_or(true);
+        self.auth_required(required)
+    }
+
+    pub fn auth_required(mut self, required: bool) -> Self {
+        self.auth_required = required;
+        self
+    }
+
+    pub fn token_validator(mut self, validator: TokenValidator) -> Self {
+        self.validator = Some(validator);
+        self
+    }
+
+    /// 带 `watch` 参数的连接登记为配置监听者
+    pub fn watchers(mut self, registry: Arc<dyn ConfigWatcherRegistry>) -> Self {
+        self.watchers = Some(registry);
+        self
+    }
+
+    /// WebSocket 订阅携带 MD5 时据此立即推送差异
+    pub fn config_source(mut self, source: ConfigSource) -> Self {
+        self.configs = Some(source);
+        self
+    }
+
+    pub fn metrics(&self) -> Arc<SseMetrics> {
+        self.metrics.clone()
+    }
+
+    pub fn routes(self) -> Router {
+        Router::new()
+            .route("/nacos/v1/events/stream", get(stream_events))
+            .route("/nacos/v1/events/ws", get(ws_events))
+            .route("/nacos/v1/events/connections", get(list_connections))
+            .with_state(self)
+    }
+
+    /// 认证层给出的身份优先；没有时按 auth_required 与令牌校验决定是否放行，放行的匿名订阅方为 None，不做权限过滤
+    pub(crate) fn authorize(
+        &self,
+        headers: &HeaderMap,
+        query_token: Option<&str>,
+        principal: Option<Extension<EventPrincipal>>,
+    ) -> Result<Option<EventPrincipal>, StatusCode> {
+        if let Some(Extension(p)) = principal {
+            return Ok(Some(p));
+        }
+        if !self.auth_required {
+            return Ok(None);
+        }
+        let token = headers
+            .get("authorization")
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.strip_prefix("Bearer ").unwrap_or(v).trim())
+            .or(query_token)
+            .filter(|t| !t.is_empty());
+        let valid = match (token, &self.validator) {
+            (Some(t), Some(validate)) => validate(t),
+            _ => false,
+        };
+        if valid { Ok(None) } else { Err(StatusCode::UNAUTHORIZED) }
+    }
+}
+
+/// 转换为 SSE 帧：事件带 `id`，浏览器重连时以 `Last-Event-ID` 回传
+pub fn sse_event(delivery: Delivery) -> Event {
+    Event::default()
+        .id(delivery.id().to_string())
+        .json_data(delivery.payload())
+        .unwrap_or(Event::default().data("invalid"))
+}
+
+/// 客户端已收到的最后事件 ID：`Last-Event-ID` 头优先，其次 `last_event_id` 查询参数
+pub fn last_event_id(headers: &HeaderMap, query: Option<&str>) -> Option<EventId> {
+    headers
+        .get("last-event-id")
+        .and_then(|v| v.to_str().ok())
+        .or(query)
+        .and_then(|v| v.trim().parse().ok())
+}
+
+#[derive(Deserialize)]
+struct StreamQuery {
+    topic: Option<String>, // "config" or "instance"
+    access_token: Option<String>,
+    labels: Option<String>,
+    /// 登记监听的配置：`namespace:group:data_id[=md5]`，逗号分隔
+    watch: Option<String>,
+    app_name: Option<String>,
+    /// 以下过滤条件逗号分隔、支持 `*` / `?` 通配，在服务端生效
+    namespace: Option<String>,
+    group: Option<String>,
+    data_id: Option<String>,
+    /// 仅对 topic=instance 生效
+    service: Option<String>,
+    /// 无法设置 `Last-Event-ID` 头的客户端可用此参数续传
+    last_event_id: Option<String>,
+    /// `true` 时配置事件附带明文 content；匿名订阅方无效
+    #[serde(default)]
+    content: bool,
+}
+
+/// 连接断开时注销监听登记与连接统计
+pub(crate) struct ConnectionGuard {
+    pub(crate) id: String,
+    pub(crate) watchers: Option<Arc<dyn ConfigWatcherRegistry>>,
+    pub(crate) metrics: Arc<SseMetrics>,
+}
+
+impl Drop for ConnectionGuard {
+    fn drop(&mut self) {
+        self.metrics.close(&self.id);
+        if let Some(registry) = self.watchers.take() {
+            let id = std::mem::take(&mut self.id);
+            tokio::spawn(async move { registry.remove_watches(&id).await });
+        }
+    }
+}
+
+async fn stream_events(
+    State(svc): State<SseService>,
+    Query(q): Query<StreamQuery>,
+    peer: Option<ConnectInfo<SocketAddr>>,
+    headers: HeaderMap,
+    principal: Option<Extension<EventPrincipal>>,
+) -> Result<Sse<impl Stream<Item = Result<Event, axum::Error>>>, StatusCode> {
+    let principal = svc.authorize(&headers, q.access_token.as_deref(), principal)?;
+    let client = client_info(&headers, peer.map(|c| c.0), q.labels.as_deref());
+    let app_name = app_name(&headers, q.app_name.as_deref());
+    let topic_name = q.topic.unwrap_or_else(|| "config".into());
+    let topic = Topic::parse(&topic_name);
+    let mut filter = EventFilter::parse(q.namespace.as_deref(), q.group.as_deref(), q.data_id.as_deref(), q.service.as_deref());
+    filter.content = q.content && principal.is_some();
+    if principal.as_ref().is_some_and(|p| !p.can_subscribe(topic, &filter)) {
+        return Err(StatusCode::FORBIDDEN);
+    }
+    let last_id = last_event_id(&headers, q.last_event_id.as_deref());
+    let events = svc.hub.stream(topic, filter.clone(), last_id);
+
+    let connection_id = format!("sse:{}", Uuid::new_v4());
+    let mut watched = q.watch.as_deref().map(parse_watch_param).unwrap_or_default();
+    if let Some(p) = &principal {
+        watched.retain(|(key, _)| p.can_read(&Resource::Config(key.clone())));
+    }
+    let watchers = match &svc.watchers {
+        Some(registry) if !watched.is_empty() => {
+            let watches = watchers_for(&connection_id, &client, app_name.clone(), WatchTransport::Sse, watched);
+            registry.set_watches(&connection_id, watches).await;
+            Some(registry.clone())
+        }
+        _ => None,
+    };
+    svc.metrics.open(ConnectionStats {
+        id: connection_id.clone(),
+        topic: topic_name,
+        transport: "sse".into(),
+        ip: client.ip.clone(),
+        app_name,
+        filter,
+        connected_at: Utc::now().timestamp(),
+        events_sent: 0,
+        resyncs: 0,
+        last_event_id: last_id,
+        last_event_at: None,
+    });
+    let guard = ConnectionGuard { id: connection_id, watchers, metrics: svc.metrics.clone() };
+    let s = async_stream::stream! {
+        let guard = guard;
+        for await delivery in events {
+            // 灰度事件只推给命中规则的客户端；补发与实时事件同样按读权限过滤
+            let delivery = match delivery {
+                Delivery::Event(mut e) => {
+                    if !beta_event_matches(&e.data, &client) {
+                        continue;
+                    }
+                    if let Some(p) = &principal {
+                        match p.visible(&e.data) {
+                            Some(data) => e.data = data,
+                            None => continue,
+                        }
+                    }
+                    Delivery::Event(e)
+                }
+                resync => resync,
+            };
+            if let Some(registry) = &guard.watchers {
+                registry.touch(&guard.id).await;
+            }
+            guard.metrics.sent(&guard.id, &delivery);
+            yield Ok(sse_event(delivery));
+        }
+    };
+    Ok(Sse::new(s).keep_alive(KeepAlive::new()))
+}
+
+/// 当前 SSE / WebSocket 连接及推送统计，含各连接的 IP 与过滤条件，仅限全局管理员
+async fn list_connections(
+    State(svc): State<SseService>,
+    principal: Option<Extension<EventPrincipal>>,
+) -> Result<Json<Value>, StatusCode> {
+    match principal {
+        Some(Extension(p)) if p.is_admin() => {}
+        Some(_) => return Err(StatusCode::FORBIDDEN),
+        None => return Err(StatusCode::UNAUTHORIZED),
+    }
+    Ok(Json(serde_json::json!({
+        "code": 200,
+        "message": "success",
+        "data": svc.metrics.snapshot(),
+        "timestamp": Utc::now().timestamp(),
+    })))
+}
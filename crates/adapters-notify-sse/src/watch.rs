@@ -0,0 +1,57 @@
+use chrono::Utc;
+use core_model::client::ClientInfo;
+use core_model::config::{BetaRule, ConfigKey};
+use core_model::listener::{ConfigWatcher, WatchTransport};
+use serde_json::Value;
+
+/// SSE `watch` 参数：`namespace:group:data_id[=md5]`，逗号分隔
+pub fn parse_watch_param(raw: &str) -> Vec<(ConfigKey, Option<String>)> {
+    raw.split(',')
+        .filter_map(|item| {
+            let (key, md5) = match item.trim().split_once('=') {
+                Some((k, m)) => (k, Some(m.trim().to_string()).filter(|m| !m.is_empty())),
+                None => (item.trim(), None),
+            };
+            let mut parts = key.splitn(3, ':');
+            let (ns, group, data_id) = (parts.next()?, parts.next()?, parts.next()?);
+            if data_id.is_empty() {
+                return None;
+            }
+            let ns = if ns.is_empty() { "public" } else { ns };
+            Some((ConfigKey { namespace: ns.into(), group: group.into(), data_id: data_id.into() }, md5))
+        })
+        .collect()
+}
+
+pub fn watchers_for(
+    connection_id: &str,
+    client: &ClientInfo,
+    app_name: Option<String>,
+    transport: WatchTransport,
+    keys: Vec<(ConfigKey, Option<String>)>,
+) -> Vec<ConfigWatcher> {
+    let now = Utc::now();
+    keys.into_iter()
+        .map(|(key, md5)| ConfigWatcher {
+            connection_id: connection_id.to_string(),
+            key,
+            ip: client.ip.clone(),
+            app_name: app_name.clone(),
+            md5,
+            transport,
+            last_seen: now,
+        })
+        .collect()
+}
+
+/// 灰度事件只对命中规则的客户端有效
+pub fn beta_event_matches(msg: &Value, client: &ClientInfo) -> bool {
+    if !msg.get("beta").and_then(|b| b.as_bool()).unwrap_or(false) {
+        return true;
+    }
+    let rule = BetaRule {
+        ips: serde_json::from_value(msg["beta_ips"].clone()).unwrap_or_default(),
+        labels: serde_json::from_value(msg["beta_labels"].clone()).unwrap_or_default(),
+    };
+    rule.matches(client)
+}
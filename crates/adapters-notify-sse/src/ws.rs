@@ -0,0 +1,346 @@
+use std::net::SocketAddr;
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::{ConnectInfo, Query, State};
+use axum::Extension;
+use axum::http::HeaderMap;
+use axum::response::{IntoResponse, Response};
+use chrono::Utc;
+use core_model::client::ClientInfo;
+use core_model::config::ConfigKey;
+use core_model::listener::WatchTransport;
+use core_usecase::beta::resolve_config;
+use core_usecase::config::content_md5;
+use futures::stream::BoxStream;
+use futures::{SinkExt, StreamExt};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::access::EventPrincipal;
+use crate::client::{app_name, client_info};
+use crate::filter::EventFilter;
+use crate::hub::{Delivery, EventId, Topic};
+use crate::metrics::ConnectionStats;
+use crate::routes::{ConnectionGuard, SseService};
+use crate::watch::{beta_event_matches, watchers_for};
+
+#[derive(Deserialize)]
+pub(crate) struct WsQuery {
+    access_token: Option<String>,
+    labels: Option<String>,
+    app_name: Option<String>,
+}
+
+fn default_namespace() -> String {
+    "public".into()
+}
+
+fn default_group() -> String {
+    "DEFAULT_GROUP".into()
+}
+
+/// 订阅的配置；各字段可用通配，md5 仅对精确 key 生效
+#[derive(Deserialize)]
+struct ConfigSubscription {
+    #[serde(default = "default_namespace")]
+    namespace: String,
+    #[serde(default = "default_group")]
+    group: String,
+    data_id: String,
+    /// 客户端当前内容的 MD5；与服务端不一致时立即推送一条配置事件
+    #[serde(default)]
+    md5: Option<String>,
+}
+
+impl ConfigSubscription {
+    fn key(&self) -> ConfigKey {
+        ConfigKey { namespace: self.namespace.clone(), group: self.group.clone(), data_id: self.data_id.clone() }
+    }
+}
+
+/// 客户端消息：`{"op":"subscribe","configs":[...],"services":[...],"content":true}` / `unsubscribe` / `ping`
+#[derive(Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+enum ClientMessage {
+    Subscribe {
+        #[serde(default)]
+        configs: Vec<ConfigSubscription>,
+        #[serde(default)]
+        services: Vec<String>,
+        /// 配置事件附带明文 content；省略时沿用当前设置，匿名连接无效
+        #[serde(default)]
+        content: Option<bool>,
+    },
+    Unsubscribe {
+        #[serde(default)]
+        configs: Vec<ConfigSubscription>,
+        #[serde(default)]
+        services: Vec<String>,
+    },
+    Ping,
+}
+
+fn is_pattern(key: &ConfigKey) -> bool {
+    [&key.namespace, &key.group, &key.data_id].iter().any(|f| f.contains(['*', '?']))
+}
+
+/// 单个 WebSocket 连接的订阅状态；订阅变化时按各 topic 的游标重新挂载事件流，期间的事件由重放日志补齐
+struct Session {
+    svc: SseService,
+    /// None 为放行的匿名订阅方，不做权限过滤
+    principal: Option<EventPrincipal>,
+    id: String,
+    client: ClientInfo,
+    app_name: Option<String>,
+    configs: Vec<ConfigKey>,
+    /// 精确 key 的客户端 MD5，用于监听登记
+    md5s: Vec<(ConfigKey, Option<String>)>,
+    services: Vec<String>,
+    /// 配置事件是否附带明文内容
+    content: bool,
+    config_cursor: EventId,
+    service_cursor: EventId,
+    config_events: Option<BoxStream<'static, Delivery>>,
+    service_events: Option<BoxStream<'static, Delivery>>,
+}
+
+impl Session {
+    fn reattach(&mut self, topic: Topic) {
+        let hub = &self.svc.hub;
+        match topic {
+            Topic::Config => {
+                self.config_events = (!self.configs.is_empty()).then(|| {
+                    let filter = EventFilter { configs: self.configs.clone(), content: self.content, ..Default::default() };
+                    hub.stream(Topic::Config, filter, Some(self.config_cursor)).boxed()
+                });
+            }
+            Topic::Instance => {
+                self.service_events = (!self.services.is_empty()).then(|| {
+                    let filter = EventFilter { services: self.services.clone(), ..Default::default() };
+                    hub.stream(Topic::Instance, filter, Some(self.service_cursor)).boxed()
+                });
+            }
+        }
+        let mut topics = Vec::new();
+        if !self.configs.is_empty() {
+            topics.push("config");
+        }
+        if !self.services.is_empty() {
+            topics.push("instance");
+        }
+        let filter = EventFilter { configs: self.configs.clone(), services: self.services.clone(), ..Default::default() };
+        self.svc.metrics.resubscribe(&self.id, topics.join(","), filter);
+    }
+
+    async fn register_watches(&mut self) {
+        if let Some(registry) = &self.svc.watchers {
+            let watches = watchers_for(&self.id, &self.client, self.app_name.clone(), WatchTransport::Ws, self.md5s.clone());
+            registry.set_watches(&self.id, watches).await;
+        }
+    }
+
+    /// 请求的配置与服务须全部可读，否则整条订阅被拒绝，返回错误信息
+    fn check_subscription(&self, configs: &[ConfigSubscription], services: &[String]) -> Result<(), String> {
+        let Some(p) = &self.principal else {
+            return Ok(());
+        };
+        let filter = EventFilter { configs: configs.iter().map(ConfigSubscription::key).collect(), ..Default::default() };
+        if !configs.is_empty() && !p.can_subscribe(Topic::Config, &filter) {
+            return Err(format!("user {} has no `r` permission on some of the subscribed configs", p.name));
+        }
+        let filter = EventFilter { services: services.to_vec(), ..Default::default() };
+        if !services.is_empty() && !p.can_subscribe(Topic::Instance, &filter) {
+            return Err(format!("user {} has no `r` permission on some of the subscribed services", p.name));
+        }
+        Ok(())
+    }
+
+    /// 返回需要立即推送的配置事件（客户端 MD5 与服务端不一致）
+    async fn subscribe(&mut self, configs: Vec<ConfigSubscription>, services: Vec<String>, content: Option<bool>) -> Vec<Value> {
+        let mut diffs = Vec::new();
+        let mut configs_changed = !configs.is_empty();
+        if let Some(content) = content.map(|c| c && self.principal.is_some()) {
+            configs_changed |= content != self.content && !self.configs.is_empty();
+            self.content = content;
+        }
+        for sub in configs {
+            let key = sub.key();
+            if !is_pattern(&key) {
+                let client_md5 = sub.md5.clone().filter(|m| !m.is_empty());
+                if let (Some(_), Some(source)) = (&sub.md5, &self.svc.configs) {
+                    let server_md5 = resolve_config(&*source.store, &*source.beta, &key, &self.client)
+                        .await
+                        .map(|(item, _)| content_md5(&item.content));
+                    if server_md5 != client_md5 {
+                        diffs.push(json!({
+                            "topic": "config",
+                            "namespace": key.namespace,
+                            "group": key.group,
+                            "data_id": key.data_id,
+                            "md5": server_md5
+                        }));
+                    }
+                }
+                self.md5s.retain(|(k, _)| *k != key);
+                self.md5s.push((key.clone(), client_md5));
+            }
+            if !self.configs.contains(&key) {
+                self.configs.push(key);
+            }
+        }
+        let services_changed = !services.is_empty();
+        for s in services {
+            if !self.services.contains(&s) {
+                self.services.push(s);
+            }
+        }
+        if configs_changed {
+            self.reattach(Topic::Config);
+            self.register_watches().await;
+        }
+        if services_changed {
+            self.reattach(Topic::Instance);
+        }
+        diffs
+    }
+
+    async fn unsubscribe(&mut self, configs: Vec<ConfigSubscription>, services: Vec<String>) {
+        if !configs.is_empty() {
+            let keys: Vec<ConfigKey> = configs.iter().map(ConfigSubscription::key).collect();
+            self.configs.retain(|k| !keys.contains(k));
+            self.md5s.retain(|(k, _)| !keys.contains(k));
+            self.reattach(Topic::Config);
+            self.register_watches().await;
+        }
+        if !services.is_empty() {
+            self.services.retain(|s| !services.contains(s));
+            self.reattach(Topic::Instance);
+        }
+    }
+
+    fn ack(&self, op: &str) -> Value {
+        let configs: Vec<Value> = self
+            .configs
+            .iter()
+            .map(|k| json!({ "namespace": k.namespace, "group": k.group, "data_id": k.data_id }))
+            .collect();
+        json!({ "topic": "ack", "op": op, "configs": configs, "services": self.services })
+    }
+
+    /// 记录游标并过滤灰度事件与无读权限的事件，返回应推送的内容
+    fn deliver(&mut self, topic: Topic, delivery: Delivery) -> Option<Value> {
+        match topic {
+            Topic::Config => self.config_cursor = delivery.id(),
+            Topic::Instance => self.service_cursor = delivery.id(),
+        }
+        let payload = match &delivery {
+            Delivery::Event(e) => {
+                if !beta_event_matches(&e.data, &self.client) {
+                    return None;
+                }
+                match &self.principal {
+                    Some(p) => p.visible(&e.data)?,
+                    None => e.data.clone(),
+                }
+            }
+            Delivery::Resync { .. } => delivery.payload(),
+        };
+        self.svc.metrics.sent(&self.id, &delivery);
+        Some(payload)
+    }
+}
+
+async fn next_delivery(events: &mut Option<BoxStream<'static, Delivery>>) -> Option<Delivery> {
+    match events {
+        Some(s) => s.next().await,
+        None => std::future::pending().await,
+    }
+}
+
+pub(crate) async fn ws_events(
+    State(svc): State<SseService>,
+    Query(q): Query<WsQuery>,
+    peer: Option<ConnectInfo<SocketAddr>>,
+    headers: HeaderMap,
+    principal: Option<Extension<EventPrincipal>>,
+    ws: WebSocketUpgrade,
+) -> Response {
+    let principal = match svc.authorize(&headers, q.access_token.as_deref(), principal) {
+        Ok(p) => p,
+        Err(status) => return status.into_response(),
+    };
+    let client = client_info(&headers, peer.map(|c| c.0), q.labels.as_deref());
+    let app_name = app_name(&headers, q.app_name.as_deref());
+    ws.on_upgrade(move |socket| run(svc, socket, client, app_name, principal))
+}
+
+async fn run(svc: SseService, socket: WebSocket, client: ClientInfo, app_name: Option<String>, principal: Option<EventPrincipal>) {
+    let id = format!("ws:{}", Uuid::new_v4());
+    svc.metrics.open(ConnectionStats {
+        id: id.clone(),
+        transport: "ws".into(),
+        topic: String::new(),
+        ip: client.ip.clone(),
+        app_name: app_name.clone(),
+        filter: EventFilter::default(),
+        connected_at: Utc::now().timestamp(),
+        events_sent: 0,
+        resyncs: 0,
+        last_event_id: None,
+        last_event_at: None,
+    });
+    let _guard = ConnectionGuard { id: id.clone(), watchers: svc.watchers.clone(), metrics: svc.metrics.clone() };
+    let head = svc.hub.head();
+    let mut session = Session {
+        svc,
+        id,
+        client,
+        app_name,
+        principal,
+        configs: Vec::new(),
+        md5s: Vec::new(),
+        services: Vec::new(),
+        content: false,
+        config_cursor: head,
+        service_cursor: head,
+        config_events: None,
+        service_events: None,
+    };
+    let (mut sink, mut source) = socket.split();
+    loop {
+        let outgoing: Vec<Value> = tokio::select! {
+            msg = source.next() => match msg {
+                Some(Ok(Message::Text(text))) => match serde_json::from_str::<ClientMessage>(&text) {
+                    Ok(ClientMessage::Subscribe { configs, services, content }) => match session.check_subscription(&configs, &services) {
+                        Ok(()) => {
+                            let mut out = vec![];
+                            let diffs = session.subscribe(configs, services, content).await;
+                            out.push(session.ack("subscribe"));
+                            out.extend(diffs);
+                            out
+                        }
+                        Err(message) => vec![json!({ "topic": "error", "message": message })],
+                    },
+                    Ok(ClientMessage::Unsubscribe { configs, services }) => {
+                        session.unsubscribe(configs, services).await;
+                        vec![session.ack("unsubscribe")]
+                    }
+                    Ok(ClientMessage::Ping) => vec![json!({ "topic": "pong" })],
+                    Err(e) => vec![json!({ "topic": "error", "message": e.to_string() })],
+                },
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => continue,
+            },
+            Some(d) = next_delivery(&mut session.config_events) => session.deliver(Topic::Config, d).into_iter().collect(),
+            Some(d) = next_delivery(&mut session.service_events) => session.deliver(Topic::Instance, d).into_iter().collect(),
+        };
+        for msg in outgoing {
+            if sink.send(Message::Text(msg.to_string())).await.is_err() {
+                return;
+            }
+        }
+        if let Some(registry) = &session.svc.watchers {
+            registry.touch(&session.id).await;
+        }
+    }
+}
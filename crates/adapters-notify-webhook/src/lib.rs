@@ -0,0 +1,293 @@
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+use async_trait::async_trait;
+use chrono::Utc;
+use core_model::config::{BetaRule, ConfigKey};
+use core_model::event::{ConfigChangeEvent, InstanceChangeEvent};
+use core_model::instance::ServiceName;
+use core_model::webhook::{DeliveryState, Webhook, WebhookDelivery};
+use core_ports::{Notifier, WebhookStore};
+use hmac::{Hmac, Mac};
+use serde_json::{json, Value};
+use sha2::Sha256;
+use uuid::Uuid;
+
+/// API 层据此映射 404 / 409 / 400
+#[derive(Debug)]
+pub enum WebhookError {
+    NotFound,
+    InvalidState(DeliveryState),
+    Invalid(String),
+}
+
+impl fmt::Display for WebhookError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WebhookError::NotFound => write!(f, "webhook or delivery not found"),
+            WebhookError::InvalidState(s) => write!(f, "delivery is {:?}", s),
+            WebhookError::Invalid(msg) => write!(f, "{}", msg),
+        }
+    }
+}
+
+impl std::error::Error for WebhookError {}
+
+/// 投递策略：失败按 base_delay * 2^(n-1) 退避，封顶 max_delay，共尝试 max_attempts 次后进入死信
+#[derive(Debug, Clone)]
+pub struct DeliveryPolicy {
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+    pub timeout: Duration,
+}
+
+impl Default for DeliveryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            base_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(300),
+            timeout: Duration::from_secs(10),
+        }
+    }
+}
+
+fn env_or<T: std::str::FromStr>(name: &str, default: T) -> T {
+    std::env::var(name).ok().and_then(|v| v.parse::<T>().ok()).unwrap_or(default)
+}
+
+impl DeliveryPolicy {
+    /// WEBHOOK_MAX_ATTEMPTS / WEBHOOK_RETRY_BASE_MS / WEBHOOK_RETRY_MAX_SECS / WEBHOOK_TIMEOUT_SECS
+    pub fn from_env() -> Self {
+        let d = Self::default();
+        Self {
+            max_attempts: env_or("WEBHOOK_MAX_ATTEMPTS", d.max_attempts).max(1),
+            base_delay: Duration::from_millis(env_or("WEBHOOK_RETRY_BASE_MS", d.base_delay.as_millis() as u64)),
+            max_delay: Duration::from_secs(env_or("WEBHOOK_RETRY_MAX_SECS", d.max_delay.as_secs())),
+            timeout: Duration::from_secs(env_or("WEBHOOK_TIMEOUT_SECS", d.timeout.as_secs())),
+        }
+    }
+
+    fn backoff(&self, attempts: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempts.saturating_sub(1));
+        self.base_delay.saturating_mul(factor).min(self.max_delay)
+    }
+}
+
+/// 签名：`hex(HMAC-SHA256(secret, "{timestamp}.{body}"))`，放在 `X-Rustacos-Signature: sha256=...`
+pub fn sign(secret: &str, timestamp: i64, body: &str) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("hmac accepts any key length");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body.as_bytes());
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// 出站 Webhook 通知器：按订阅过滤后异步投递，不阻塞发布流程
+#[derive(Clone)]
+pub struct WebhookNotifier {
+    store: Arc<dyn WebhookStore>,
+    client: reqwest::Client,
+    policy: DeliveryPolicy,
+}
+
+impl WebhookNotifier {
+    pub fn new(store: Arc<dyn WebhookStore>, policy: DeliveryPolicy) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(policy.timeout)
+            .build()
+            .expect("failed to build webhook http client");
+        Self { store, client, policy }
+    }
+
+    /// 重新投递死信，重置尝试次数
+    pub async fn redeliver(&self, delivery_id: &str) -> anyhow::Result<WebhookDelivery> {
+        let mut d = self.store.get_delivery(delivery_id).await.ok_or(WebhookError::NotFound)?;
+        if d.state != DeliveryState::DeadLetter {
+            return Err(WebhookError::InvalidState(d.state).into());
+        }
+        d.state = DeliveryState::Pending;
+        d.attempts = 0;
+        d.next_attempt_at = Some(Utc::now());
+        d.updated_at = Utc::now();
+        self.store.put_delivery(d.clone()).await?;
+        self.spawn(d.clone());
+        Ok(d)
+    }
+
+    /// 发送一条 ping 事件，用于验证地址与签名
+    pub async fn ping(&self, webhook_id: &str) -> anyhow::Result<WebhookDelivery> {
+        let hook = self.store.get_webhook(webhook_id).await.ok_or(WebhookError::NotFound)?;
+        let payload = json!({ "topic": "ping", "webhook_id": hook.id, "name": hook.name });
+        let d = self.enqueue(&hook, "ping", payload).await?;
+        Ok(d)
+    }
+
+    async fn enqueue(&self, hook: &Webhook, topic: &str, mut payload: Value) -> anyhow::Result<WebhookDelivery> {
+        let now = Utc::now();
+        payload["timestamp"] = json!(now.timestamp());
+        let d = WebhookDelivery {
+            id: Uuid::new_v4().to_string(),
+            webhook_id: hook.id.clone(),
+            topic: topic.to_string(),
+            payload,
+            state: DeliveryState::Pending,
+            attempts: 0,
+            last_status: None,
+            last_error: None,
+            next_attempt_at: Some(now),
+            created_at: now,
+            updated_at: now,
+        };
+        self.store.put_delivery(d.clone()).await?;
+        self.spawn(d.clone());
+        Ok(d)
+    }
+
+    async fn dispatch(&self, topic: &str, payload: Value, matches: impl Fn(&Webhook) -> bool) {
+        let hooks = match self.store.list_webhooks().await {
+            Ok(h) => h,
+            Err(e) => {
+                tracing::warn!("list webhooks failed: {}", e);
+                return;
+            }
+        };
+        for hook in hooks.iter().filter(|h| matches(h)) {
+            let mut payload = payload.clone();
+            if !hook.include_content {
+                if let Some(fields) = payload.as_object_mut() {
+                    fields.remove("content");
+                }
+            }
+            if let Err(e) = self.enqueue(hook, topic, payload).await {
+                tracing::warn!("enqueue webhook {} failed: {}", hook.id, e);
+            }
+        }
+    }
+
+    fn spawn(&self, delivery: WebhookDelivery) {
+        let this = self.clone();
+        tokio::spawn(async move { this.run(delivery).await });
+    }
+
+    async fn run(&self, mut d: WebhookDelivery) {
+        loop {
+            let Some(hook) = self.store.get_webhook(&d.webhook_id).await else {
+                d.state = DeliveryState::DeadLetter;
+                d.last_error = Some("webhook deleted".into());
+                d.next_attempt_at = None;
+                d.updated_at = Utc::now();
+                let _ = self.store.put_delivery(d).await;
+                return;
+            };
+            d.attempts += 1;
+            match self.send(&hook, &d).await {
+                Ok(status) if (200..300).contains(&status) => {
+                    d.state = DeliveryState::Delivered;
+                    d.last_status = Some(status);
+                    d.last_error = None;
+                }
+                Ok(status) => {
+                    d.last_status = Some(status);
+                    d.last_error = Some(format!("HTTP {}", status));
+                }
+                Err(e) => {
+                    d.last_status = None;
+                    d.last_error = Some(e.to_string());
+                }
+            }
+            let delay = self.policy.backoff(d.attempts);
+            if d.state == DeliveryState::Delivered {
+                d.next_attempt_at = None;
+            } else if d.attempts >= self.policy.max_attempts {
+                d.state = DeliveryState::DeadLetter;
+                d.next_attempt_at = None;
+                tracing::warn!("webhook delivery {} to {} dead-lettered: {:?}", d.id, hook.url, d.last_error);
+            } else {
+                d.next_attempt_at = chrono::Duration::from_std(delay).ok().map(|dl| Utc::now() + dl);
+            }
+            d.updated_at = Utc::now();
+            let _ = self.store.put_delivery(d.clone()).await;
+            if d.state != DeliveryState::Pending {
+                return;
+            }
+            tokio::time::sleep(delay).await;
+        }
+    }
+
+    async fn send(&self, hook: &Webhook, d: &WebhookDelivery) -> anyhow::Result<u16> {
+        let body = d.payload.to_string();
+        let timestamp = Utc::now().timestamp();
+        let resp = self
+            .client
+            .post(&hook.url)
+            .header("Content-Type", "application/json")
+            .header("User-Agent", "rustacos-webhook")
+            .header("X-Rustacos-Event", &d.topic)
+            .header("X-Rustacos-Delivery", &d.id)
+            .header("X-Rustacos-Timestamp", timestamp.to_string())
+            .header("X-Rustacos-Signature", format!("sha256={}", sign(&hook.secret, timestamp, &body)))
+            .body(body)
+            .send()
+            .await?;
+        Ok(resp.status().as_u16())
+    }
+}
+
+fn key_json(key: &ConfigKey) -> Value {
+    json!({ "namespace": key.namespace, "group": key.group, "data_id": key.data_id })
+}
+
+#[async_trait]
+impl Notifier for WebhookNotifier {
+    async fn notify_config_change(&self, key: &ConfigKey) {
+        let mut payload = key_json(key);
+        payload["topic"] = json!("config");
+        self.dispatch("config", payload, |h| h.matches_config(&key.namespace, &key.group)).await;
+    }
+    async fn notify_config_beta_change(&self, key: &ConfigKey, rule: &BetaRule) {
+        let mut payload = key_json(key);
+        payload["topic"] = json!("config");
+        payload["beta"] = json!(true);
+        payload["beta_ips"] = json!(rule.ips);
+        payload["beta_labels"] = json!(rule.labels);
+        self.dispatch("config", payload, |h| h.matches_config(&key.namespace, &key.group)).await;
+    }
+    /// 变更集按 Webhook 的过滤条件裁剪 items，每个命中的 Webhook 只收到一次
+    async fn notify_config_batch(&self, changeset: &str, revision: i64, keys: &[ConfigKey]) {
+        let hooks = match self.store.list_webhooks().await {
+            Ok(h) => h,
+            Err(e) => {
+                tracing::warn!("list webhooks failed: {}", e);
+                return;
+            }
+        };
+        for hook in &hooks {
+            let items: Vec<Value> = keys
+                .iter()
+                .filter(|k| hook.matches_config(&k.namespace, &k.group))
+                .map(key_json)
+                .collect();
+            if items.is_empty() {
+                continue;
+            }
+            let payload = json!({ "topic": "config", "changeset": changeset, "revision": revision, "items": items });
+            if let Err(e) = self.enqueue(hook, "config", payload).await {
+                tracing::warn!("enqueue webhook {} failed: {}", hook.id, e);
+            }
+        }
+    }
+    async fn notify_config_event(&self, event: &ConfigChangeEvent) {
+        let key = &event.key;
+        self.dispatch("config", event.payload(), |h| h.matches_config(&key.namespace, &key.group)).await;
+    }
+    async fn notify_instance_change(&self, service: &ServiceName) {
+        let payload = json!({ "topic": "instance", "service_name": service.0 });
+        self.dispatch("instance", payload, |h| h.matches_service(&service.0)).await;
+    }
+    async fn notify_instance_event(&self, event: &InstanceChangeEvent) {
+        let service = &event.instance.service.0;
+        self.dispatch("instance", event.payload(), |h| h.matches_service(service)).await;
+    }
+}
@@ -0,0 +1,153 @@
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use adapters_notify_webhook::{sign, DeliveryPolicy, WebhookNotifier};
+use adapters_storage_memory::MemStores;
+use axum::extract::State;
+use axum::http::{HeaderMap, StatusCode};
+use axum::routing::post;
+use axum::Router;
+use chrono::Utc;
+use core_model::webhook::{DeliveryState, Webhook, WebhookDelivery};
+use core_ports::WebhookStore;
+
+/// 本地接收端：记录每次请求的头与正文，`ok` 为 false 时返回 500
+#[derive(Clone, Default)]
+struct Receiver {
+    ok: Arc<AtomicBool>,
+    hits: Arc<AtomicU32>,
+    requests: Arc<Mutex<Vec<(HeaderMap, String)>>>,
+}
+
+async fn receive(State(r): State<Receiver>, headers: HeaderMap, body: String) -> StatusCode {
+    r.hits.fetch_add(1, Ordering::SeqCst);
+    r.requests.lock().unwrap().push((headers, body));
+    if r.ok.load(Ordering::SeqCst) {
+        StatusCode::OK
+    } else {
+        StatusCode::INTERNAL_SERVER_ERROR
+    }
+}
+
+async fn serve(ok: bool) -> (Receiver, String) {
+    let receiver = Receiver::default();
+    receiver.ok.store(ok, Ordering::SeqCst);
+    let app = Router::new().route("/hook", post(receive)).with_state(receiver.clone());
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    (receiver, format!("http://{}/hook", addr))
+}
+
+fn policy(max_attempts: u32) -> DeliveryPolicy {
+    DeliveryPolicy {
+        max_attempts,
+        base_delay: Duration::from_millis(10),
+        max_delay: Duration::from_millis(40),
+        timeout: Duration::from_secs(5),
+    }
+}
+
+async fn register(store: &MemStores, url: String) -> Webhook {
+    let now = Utc::now();
+    let hook = Webhook {
+        id: "hook-1".into(),
+        name: "test".into(),
+        url,
+        secret: "s3cret".into(),
+        topics: Vec::new(),
+        namespaces: Vec::new(),
+        groups: Vec::new(),
+        services: Vec::new(),
+        include_content: false,
+        enabled: true,
+        created_at: now,
+        updated_at: now,
+    };
+    store.put_webhook(hook.clone()).await.unwrap();
+    hook
+}
+
+/// 等待投递进入终态（已投递或死信）
+async fn settled(store: &MemStores, id: &str) -> WebhookDelivery {
+    for _ in 0..200 {
+        if let Some(d) = store.get_delivery(id).await {
+            if d.state != DeliveryState::Pending {
+                return d;
+            }
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    panic!("delivery {} did not settle", id);
+}
+
+#[tokio::test]
+async fn signs_body_with_timestamp() {
+    let (receiver, url) = serve(true).await;
+    let store = MemStores::default();
+    let hook = register(&store, url).await;
+    let notifier = WebhookNotifier::new(Arc::new(store.clone()), policy(3));
+
+    let d = notifier.ping(&hook.id).await.unwrap();
+    let d = settled(&store, &d.id).await;
+    assert_eq!(d.state, DeliveryState::Delivered);
+    assert_eq!(d.attempts, 1);
+    assert_eq!(d.last_status, Some(200));
+
+    let requests = receiver.requests.lock().unwrap();
+    let (headers, body) = &requests[0];
+    let header = |name: &str| headers.get(name).unwrap().to_str().unwrap().to_string();
+    let timestamp: i64 = header("x-rustacos-timestamp").parse().unwrap();
+    assert_eq!(header("x-rustacos-signature"), format!("sha256={}", sign(&hook.secret, timestamp, body)));
+    assert_eq!(header("x-rustacos-event"), "ping");
+    assert_eq!(header("x-rustacos-delivery"), d.id);
+    let payload: serde_json::Value = serde_json::from_str(body).unwrap();
+    assert_eq!(payload["webhook_id"], hook.id);
+}
+
+#[tokio::test]
+async fn retries_with_backoff_then_dead_letters() {
+    let (receiver, url) = serve(false).await;
+    let store = MemStores::default();
+    let hook = register(&store, url).await;
+    let notifier = WebhookNotifier::new(Arc::new(store.clone()), policy(3));
+
+    let started = std::time::Instant::now();
+    let d = notifier.ping(&hook.id).await.unwrap();
+    let d = settled(&store, &d.id).await;
+    assert_eq!(d.state, DeliveryState::DeadLetter);
+    assert_eq!(d.attempts, 3);
+    assert_eq!(d.last_status, Some(500));
+    assert_eq!(d.last_error.as_deref(), Some("HTTP 500"));
+    assert!(d.next_attempt_at.is_none());
+    assert_eq!(receiver.hits.load(Ordering::SeqCst), 3);
+    // 两次重试之间退避 10ms、20ms
+    assert!(started.elapsed() >= Duration::from_millis(30));
+    // 死信之后不再投递
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert_eq!(receiver.hits.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn redeliver_resets_dead_letter() {
+    let (receiver, url) = serve(false).await;
+    let store = MemStores::default();
+    let hook = register(&store, url).await;
+    let notifier = WebhookNotifier::new(Arc::new(store.clone()), policy(2));
+
+    let d = notifier.ping(&hook.id).await.unwrap();
+    assert_eq!(settled(&store, &d.id).await.state, DeliveryState::DeadLetter);
+
+    receiver.ok.store(true, Ordering::SeqCst);
+    let retried = notifier.redeliver(&d.id).await.unwrap();
+    assert_eq!(retried.state, DeliveryState::Pending);
+    assert_eq!(retried.attempts, 0);
+    let done = settled(&store, &d.id).await;
+    assert_eq!(done.state, DeliveryState::Delivered);
+    assert_eq!(done.attempts, 1);
+    assert_eq!(receiver.hits.load(Ordering::SeqCst), 3);
+
+    // 只有死信可以重投
+    assert!(notifier.redeliver(&d.id).await.is_err());
+    assert!(notifier.redeliver("missing").await.is_err());
+}
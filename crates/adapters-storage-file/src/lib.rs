@@ -0,0 +1,273 @@
+use std::collections::BTreeMap;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use async_trait::async_trait;
+use core_model::audit::{AuditEntry, AuditFilter, AuditRecord};
+use core_model::auth::{AccessKey, Action, Permission, RoleBinding, User};
+use core_model::schedule::{ScheduleState, ScheduledChange};
+use core_ports::{AccessKeyStore, AuditStore, PermissionStore, RoleStore, ScheduledChangeStore, StoreError, StoreResult, UserStore};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// 以 JSON 文件保存的小型表：启动时整体读入，每次修改整体写回（先写临时文件再改名，避免写一半）
+struct JsonTable<T> {
+    path: PathBuf,
+    rows: Mutex<BTreeMap<String, T>>,
+}
+
+impl<T: Serialize + DeserializeOwned + Clone> JsonTable<T> {
+    fn open(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let rows = match std::fs::read(&path) {
+            Ok(raw) if !raw.is_empty() => serde_json::from_slice(&raw)
+                .map_err(|e| anyhow::anyhow!("failed to parse {}: {}", path.display(), e))?,
+            Ok(_) => BTreeMap::new(),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(e) => return Err(e.into()),
+        };
+        Ok(Self { path, rows: Mutex::new(rows) })
+    }
+
+    fn get(&self, key: &str) -> Option<T> {
+        self.rows.lock().unwrap().get(key).cloned()
+    }
+
+    fn list(&self) -> Vec<T> {
+        self.rows.lock().unwrap().values().cloned().collect()
+    }
+
+    /// 修改并落盘；写文件失败时回滚内存中的改动
+    fn update<R>(&self, f: impl FnOnce(&mut BTreeMap<String, T>) -> R) -> StoreResult<R> {
+        let mut rows = self.rows.lock().unwrap();
+        let before = rows.clone();
+        let out = f(&mut rows);
+        if let Err(e) = self.flush(&rows) {
+            *rows = before;
+            return Err(e);
+        }
+        Ok(out)
+    }
+
+    fn flush(&self, rows: &BTreeMap<String, T>) -> StoreResult<()> {
+        if let Some(dir) = self.path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir)?;
+        }
+        let tmp = self.path.with_extension("tmp");
+        std::fs::write(&tmp, serde_json::to_vec_pretty(rows).map_err(|e| StoreError::Backend(e.to_string()))?)?;
+        std::fs::rename(&tmp, &self.path)?;
+        Ok(())
+    }
+}
+
+/// 文件持久化的用户表，重启后保留用户与密码摘要
+pub struct FileUserStore {
+    users: JsonTable<User>,
+}
+
+impl FileUserStore {
+    pub fn open(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        Ok(Self { users: JsonTable::open(path)? })
+    }
+}
+
+#[async_trait]
+impl UserStore for FileUserStore {
+    async fn get_user(&self, username: &str) -> Option<User> {
+        self.users.get(username)
+    }
+    async fn list_users(&self) -> StoreResult<Vec<User>> {
+        Ok(self.users.list())
+    }
+    async fn put_user(&self, user: User) -> StoreResult<()> {
+        self.users.update(|rows| {
+            rows.insert(user.username.clone(), user);
+        })
+    }
+    async fn delete_user(&self, username: &str) -> StoreResult<bool> {
+        self.users.update(|rows| rows.remove(username).is_some())
+    }
+}
+
+/// 文件持久化的角色绑定，行键为 `角色+用户名`
+pub struct FileRoleStore {
+    roles: JsonTable<RoleBinding>,
+}
+
+impl FileRoleStore {
+    pub fn open(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        Ok(Self { roles: JsonTable::open(path)? })
+    }
+}
+
+#[async_trait]
+impl RoleStore for FileRoleStore {
+    async fn list_roles(&self, username: Option<&str>) -> StoreResult<Vec<RoleBinding>> {
+        let mut v = self.roles.list();
+        v.retain(|b| username.is_none_or(|u| b.username == u));
+        Ok(v)
+    }
+    async fn add_role(&self, binding: RoleBinding) -> StoreResult<bool> {
+        let k = format!("{}+{}", binding.role, binding.username);
+        self.roles.update(|rows| rows.insert(k, binding).is_none())
+    }
+    async fn delete_role(&self, role: &str, username: Option<&str>) -> StoreResult<usize> {
+        self.roles.update(|rows| {
+            let before = rows.len();
+            rows.retain(|_, b| !(b.role == role && username.is_none_or(|u| b.username == u)));
+            before - rows.len()
+        })
+    }
+}
+
+/// 文件持久化的角色权限，行键为 `角色+资源+动作`
+pub struct FilePermissionStore {
+    permissions: JsonTable<Permission>,
+}
+
+impl FilePermissionStore {
+    pub fn open(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        Ok(Self { permissions: JsonTable::open(path)? })
+    }
+}
+
+fn permission_key(role: &str, resource: &str, action: Action) -> String {
+    format!("{}+{}+{}", role, resource, action.as_str())
+}
+
+#[async_trait]
+impl PermissionStore for FilePermissionStore {
+    async fn list_permissions(&self, role: Option<&str>) -> StoreResult<Vec<Permission>> {
+        let mut v = self.permissions.list();
+        v.retain(|p| role.is_none_or(|r| p.role == r));
+        Ok(v)
+    }
+    async fn add_permission(&self, permission: Permission) -> StoreResult<bool> {
+        let k = permission_key(&permission.role, &permission.resource, permission.action);
+        self.permissions.update(|rows| rows.insert(k, permission).is_none())
+    }
+    async fn delete_permission(&self, role: &str, resource: &str, action: Action) -> StoreResult<bool> {
+        self.permissions.update(|rows| rows.remove(&permission_key(role, resource, action)).is_some())
+    }
+}
+
+/// 文件持久化的 AK/SK 凭据
+pub struct FileAccessKeyStore {
+    keys: JsonTable<AccessKey>,
+}
+
+impl FileAccessKeyStore {
+    pub fn open(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        Ok(Self { keys: JsonTable::open(path)? })
+    }
+}
+
+#[async_trait]
+impl AccessKeyStore for FileAccessKeyStore {
+    async fn get_access_key(&self, access_key: &str) -> Option<AccessKey> {
+        self.keys.get(access_key)
+    }
+    async fn list_access_keys(&self) -> StoreResult<Vec<AccessKey>> {
+        Ok(self.keys.list())
+    }
+    async fn put_access_key(&self, key: AccessKey) -> StoreResult<()> {
+        self.keys.update(|rows| {
+            rows.insert(key.access_key.clone(), key);
+        })
+    }
+    async fn delete_access_key(&self, access_key: &str) -> StoreResult<bool> {
+        self.keys.update(|rows| rows.remove(access_key).is_some())
+    }
+}
+
+/// 文件持久化的定时发布任务，重启后继续执行未完成的发布与回退
+pub struct FileScheduledChangeStore {
+    changes: JsonTable<ScheduledChange>,
+}
+
+impl FileScheduledChangeStore {
+    pub fn open(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        Ok(Self { changes: JsonTable::open(path)? })
+    }
+}
+
+#[async_trait]
+impl ScheduledChangeStore for FileScheduledChangeStore {
+    async fn put_scheduled(&self, change: ScheduledChange) -> StoreResult<()> {
+        self.changes.update(|rows| {
+            rows.insert(change.id.clone(), change);
+        })
+    }
+    async fn get_scheduled(&self, id: &str) -> Option<ScheduledChange> {
+        self.changes.get(id)
+    }
+    async fn list_scheduled(
+        &self,
+        namespace: Option<&str>,
+        state: Option<ScheduleState>,
+    ) -> StoreResult<Vec<ScheduledChange>> {
+        let mut v = self.changes.list();
+        v.retain(|c| namespace.is_none_or(|ns| c.key.namespace == ns) && state.is_none_or(|s| c.state == s));
+        v.sort_by_key(|c| c.publish_at);
+        Ok(v)
+    }
+}
+
+/// 审计日志：JSON Lines 文件，只追加不改写，每条写入后落盘；启动时整体读入供查询。
+/// 文件句柄常驻，写入与落盘在阻塞线程池中进行，不占用异步运行时
+pub struct FileAuditStore {
+    /// 追加时持有，保证写入顺序与哈希链一致
+    file: Arc<Mutex<File>>,
+    entries: Arc<Mutex<Vec<AuditEntry>>>,
+}
+
+impl FileAuditStore {
+    pub fn open(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let raw = match std::fs::read_to_string(&path) {
+            Ok(raw) => raw,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e.into()),
+        };
+        let entries = raw
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| {
+                serde_json::from_str(line).map_err(|e| anyhow::anyhow!("failed to parse {} line {}: {}", path.display(), i + 1, e))
+            })
+            .collect::<anyhow::Result<Vec<AuditEntry>>>()?;
+        if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir)?;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        Ok(Self { file: Arc::new(Mutex::new(file)), entries: Arc::new(Mutex::new(entries)) })
+    }
+}
+
+#[async_trait]
+impl AuditStore for FileAuditStore {
+    async fn append_audit(&self, record: AuditRecord) -> StoreResult<AuditEntry> {
+        let (file, entries) = (self.file.clone(), self.entries.clone());
+        tokio::task::spawn_blocking(move || {
+            let mut file = file.lock().unwrap();
+            // 查询只与这两处短暂的加锁竞争，不等待落盘
+            let entry = AuditEntry::chain(entries.lock().unwrap().last(), record);
+            let mut line = serde_json::to_vec(&entry).map_err(|e| StoreError::Backend(e.to_string()))?;
+            line.push(b'\n');
+            file.write_all(&line)?;
+            file.sync_data()?;
+            entries.lock().unwrap().push(entry.clone());
+            Ok(entry)
+        })
+        .await
+        .map_err(|e| StoreError::Backend(e.to_string()))?
+    }
+    async fn query_audit(&self, filter: &AuditFilter, offset: usize, limit: usize) -> StoreResult<(usize, Vec<AuditEntry>)> {
+        Ok(filter.page(&self.entries.lock().unwrap(), offset, limit))
+    }
+    async fn audit_chain(&self) -> StoreResult<Vec<AuditEntry>> {
+        Ok(self.entries.lock().unwrap().clone())
+    }
+}
@@ -0,0 +1,73 @@
+use dashmap::DashMap;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// 配置内容的三元组倒排索引（按小写字符切分），用于子串检索的候选过滤。
+/// 命中候选后仍需逐条校验，大小写敏感的查询同样适用。
+#[derive(Clone, Default)]
+pub struct ContentIndex {
+    postings: Arc<DashMap<String, HashSet<String>>>,
+}
+
+fn trigrams(text: &str) -> HashSet<String> {
+    let chars: Vec<char> = text.to_lowercase().chars().collect();
+    chars.windows(3).map(|w| w.iter().collect()).collect()
+}
+
+impl ContentIndex {
+    pub fn update(&self, key: &str, old: Option<&str>, new: &str) {
+        let new_grams = trigrams(new);
+        if let Some(old) = old {
+            for g in trigrams(old).difference(&new_grams) {
+                self.remove_posting(g, key);
+            }
+        }
+        for g in new_grams {
+            self.postings.entry(g).or_default().insert(key.to_string());
+        }
+    }
+
+    pub fn remove(&self, key: &str, content: &str) {
+        for g in trigrams(content) {
+            self.remove_posting(&g, key);
+        }
+    }
+
+    fn remove_posting(&self, gram: &str, key: &str) {
+        let empty = match self.postings.get_mut(gram) {
+            Some(mut set) => {
+                set.remove(key);
+                set.is_empty()
+            }
+            None => false,
+        };
+        if empty {
+            self.postings.remove_if(gram, |_, set| set.is_empty());
+        }
+    }
+
+    /// 返回可能包含 pattern 的配置键；pattern 不足三个字符时无法使用索引，返回 None
+    pub fn candidates(&self, pattern: &str) -> Option<HashSet<String>> {
+        let grams = trigrams(pattern);
+        if grams.is_empty() {
+            return None;
+        }
+        let mut sets: Vec<HashSet<String>> = Vec::with_capacity(grams.len());
+        for g in &grams {
+            match self.postings.get(g) {
+                Some(set) => sets.push(set.clone()),
+                None => return Some(HashSet::new()),
+            }
+        }
+        sets.sort_by_key(|s| s.len());
+        let mut iter = sets.into_iter();
+        let mut acc = iter.next().unwrap_or_default();
+        for s in iter {
+            acc.retain(|k| s.contains(k));
+            if acc.is_empty() {
+                break;
+            }
+        }
+        Some(acc)
+    }
+}
@@ -0,0 +1,45 @@
+use core_model::config::ConfigKey;
+use core_model::template::RefTarget;
+use dashmap::DashMap;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// 模板依赖图：正向记录配置引用的目标，反向记录目标被哪些配置引用
+#[derive(Clone, Default)]
+pub struct DependencyGraph {
+    refs: Arc<DashMap<ConfigKey, HashSet<String>>>,
+    dependents: Arc<DashMap<String, HashSet<ConfigKey>>>,
+}
+
+impl DependencyGraph {
+    pub fn set(&self, key: &ConfigKey, targets: &[RefTarget]) {
+        let new_ids: HashSet<String> = targets.iter().map(RefTarget::id).collect();
+        let old_ids = if new_ids.is_empty() {
+            self.refs.remove(key).map(|(_, v)| v).unwrap_or_default()
+        } else {
+            self.refs.insert(key.clone(), new_ids.clone()).unwrap_or_default()
+        };
+        for id in old_ids.difference(&new_ids) {
+            let empty = match self.dependents.get_mut(id) {
+                Some(mut m) => {
+                    m.remove(key);
+                    m.is_empty()
+                }
+                None => false,
+            };
+            if empty {
+                self.dependents.remove_if(id, |_, m| m.is_empty());
+            }
+        }
+        for id in new_ids {
+            self.dependents.entry(id).or_default().insert(key.clone());
+        }
+    }
+
+    pub fn dependents(&self, id: &str) -> Vec<ConfigKey> {
+        self.dependents
+            .get(id)
+            .map(|m| m.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+}
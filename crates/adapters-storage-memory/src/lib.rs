@@ -1,54 +1,125 @@
 use async_trait::async_trait;
-use chrono::Utc;
-use core_model::config::{ConfigHistoryItem, ConfigItem, ConfigKey};
+use chrono::{DateTime, Utc};
+use core_model::audit::{AuditEntry, AuditFilter, AuditRecord};
+use core_model::auth::{AccessKey, Action, Permission, RoleBinding, User};
+use core_model::changeset::{Changeset, ConfigBatch};
+use core_model::config::{BetaConfigItem, ConfigFilter, ConfigHistoryItem, ConfigItem, ConfigKey};
 use core_model::instance::{Instance, InstanceId, ServiceName};
+use core_model::listener::ConfigWatcher;
 use core_model::namespace::Namespace;
-use core_ports::{ConfigHistoryStore, ConfigStore, InstanceStore, NamespaceStore};
+use core_model::review::{ChangeRequest, ChangeRequestState};
+use core_model::schedule::{ScheduleState, ScheduledChange};
+use core_model::schema::ConfigSchema;
+use core_model::search::{ContentMatcher, ContentQuery, ContentSearchHit};
+use core_model::template::RefTarget;
+use core_model::webhook::{DeliveryState, Webhook, WebhookDelivery};
+use core_ports::{BetaConfigStore, ChangeRequestStore, ChangesetStore, ConfigBatchWriter, ConfigDependencyStore, ConfigHistoryStore, ConfigStore, ConfigWatcherRegistry, InstanceStore, NamespaceStore, ScheduledChangeStore, SchemaStore, UserStore, WebhookStore, RoleStore, PermissionStore, AccessKeyStore, NonceStore, AuditStore, StoreError, StoreResult};
 use dashmap::DashMap;
-use std::sync::Arc;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::{Arc, Mutex, RwLock, RwLockReadGuard};
+
+mod content_index;
+mod dependency;
+pub use content_index::ContentIndex;
+pub use dependency::DependencyGraph;
 
 #[derive(Clone, Default)]
 pub struct MemStores {
     pub configs: Arc<DashMap<String, ConfigItem>>,
     pub histories: Arc<DashMap<String, Vec<ConfigHistoryItem>>>,
+    pub betas: Arc<DashMap<String, BetaConfigItem>>,
     pub instances: Arc<DashMap<String, Instance>>,
     pub namespaces: Arc<DashMap<String, Namespace>>,
+    pub schemas: Arc<DashMap<String, ConfigSchema>>,
+    pub content_index: ContentIndex,
+    pub dependencies: DependencyGraph,
+    pub changesets: Arc<DashMap<String, Changeset>>,
+    pub change_requests: Arc<DashMap<String, ChangeRequest>>,
+    pub scheduled: Arc<DashMap<String, ScheduledChange>>,
+    pub webhooks: Arc<DashMap<String, Webhook>>,
+    pub webhook_deliveries: Arc<DashMap<String, WebhookDelivery>>,
+    pub users: Arc<DashMap<String, User>>,
+    /// `角色+用户名` → 绑定
+    pub roles: Arc<DashMap<String, RoleBinding>>,
+    /// `角色+资源+动作` → 权限
+    pub permissions: Arc<DashMap<String, Permission>>,
+    pub access_keys: Arc<DashMap<String, AccessKey>>,
+    /// `access_key+nonce` → 过期时间
+    pub nonces: Arc<DashMap<String, DateTime<Utc>>>,
+    /// 审计日志，按序号升序
+    pub audit: Arc<Mutex<Vec<AuditEntry>>>,
+    /// 连接 ID → 该连接监听的配置
+    pub watchers: Arc<DashMap<String, Vec<ConfigWatcher>>>,
+    /// 单条读写持读锁，批量提交持写锁，保证变更集对外整体可见
+    batch_lock: Arc<RwLock<()>>,
+    /// 最近分配的变更集 revision
+    revision: Arc<AtomicI64>,
 }
 
 fn key_of(k: &ConfigKey) -> String {
     format!("{}+{}+{}", k.namespace, k.group, k.data_id)
 }
 
+fn permission_key(role: &str, resource: &str, action: Action) -> String {
+    format!("{}+{}+{}", role, resource, action.as_str())
+}
+
+impl MemStores {
+    fn shared(&self) -> RwLockReadGuard<'_, ()> {
+        self.batch_lock.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn put_item(&self, item: ConfigItem) {
+        let k = key_of(&item.key);
+        let content = item.content.clone();
+        let old = self.configs.insert(k.clone(), item);
+        self.content_index.update(&k, old.as_ref().map(|o| o.content.as_str()), &content);
+    }
+
+    fn delete_item(&self, key: &ConfigKey) -> bool {
+        let k = key_of(key);
+        match self.configs.remove(&k) {
+            Some((_, old)) => {
+                self.content_index.remove(&k, &old.content);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn append_history(&self, item: ConfigHistoryItem) {
+        self.histories.entry(key_of(&item.key)).or_default().push(item);
+    }
+}
+
 #[async_trait]
 impl ConfigStore for MemStores {
     async fn get(&self, key: &ConfigKey) -> Option<ConfigItem> {
+        let _g = self.shared();
         self.configs.get(&key_of(key)).map(|v| v.clone())
     }
-    async fn put(&self, item: ConfigItem) -> anyhow::Result<()> {
-        self.configs.insert(key_of(&item.key), item);
+    async fn put(&self, item: ConfigItem) -> StoreResult<()> {
+        let _g = self.shared();
+        self.put_item(item);
         Ok(())
     }
-    async fn delete(&self, key: &ConfigKey) -> anyhow::Result<bool> {
-        Ok(self.configs.remove(&key_of(key)).is_some())
+    async fn delete(&self, key: &ConfigKey) -> StoreResult<bool> {
+        let _g = self.shared();
+        Ok(self.delete_item(key))
     }
     async fn list(
         &self,
         namespace: &str,
         page: u32,
         size: u32,
-        filter: Option<&str>,
-    ) -> anyhow::Result<(usize, Vec<ConfigItem>)> {
+        filter: &ConfigFilter,
+    ) -> StoreResult<(usize, Vec<ConfigItem>)> {
+        let _g = self.shared();
         let mut v: Vec<ConfigItem> = self
             .configs
             .iter()
             .filter(|e| e.value().key.namespace == namespace)
-            .filter(|e| {
-                if let Some(f) = filter {
-                    e.value().key.data_id.contains(f)
-                } else {
-                    true
-                }
-            })
+            .filter(|e| filter.matches(e.value()))
             .map(|e| e.value().clone())
             .collect();
         v.sort_by(|a, b| b.updated_at.cmp(&a.updated_at));
@@ -57,18 +128,49 @@ impl ConfigStore for MemStores {
         let end = (start + size as usize).min(total);
         Ok((total, v[start..end].to_vec()))
     }
+    async fn search_content(
+        &self,
+        namespace: &str,
+        query: &ContentQuery,
+        limit: usize,
+    ) -> StoreResult<Vec<ContentSearchHit>> {
+        let matcher = ContentMatcher::new(query).map_err(|e| StoreError::Invalid(e.to_string()))?;
+        let _g = self.shared();
+        // 子串查询先走索引收窄候选，正则查询全量扫描
+        let candidates = if query.regex { None } else { self.content_index.candidates(&query.pattern) };
+        let check = |item: &ConfigItem| -> Option<ContentSearchHit> {
+            if item.key.namespace != namespace {
+                return None;
+            }
+            let matches = matcher.find_lines(&item.content);
+            if matches.is_empty() {
+                return None;
+            }
+            Some(ContentSearchHit { key: item.key.clone(), updated_at: item.updated_at, matches })
+        };
+        let mut hits: Vec<ContentSearchHit> = match candidates {
+            Some(keys) => keys
+                .iter()
+                .filter_map(|k| self.configs.get(k).and_then(|e| check(e.value())))
+                .collect(),
+            None => self.configs.iter().filter_map(|e| check(e.value())).collect(),
+        };
+        hits.sort_by_key(|h| std::cmp::Reverse(h.updated_at));
+        hits.truncate(limit);
+        Ok(hits)
+    }
 }
 
 #[async_trait]
 impl ConfigHistoryStore for MemStores {
-    async fn append(&self, item: ConfigHistoryItem) -> anyhow::Result<()> {
-        let key = key_of(&item.key);
-        let mut entry = self.histories.entry(key).or_default();
+    async fn append(&self, item: ConfigHistoryItem) -> StoreResult<()> {
+        let _g = self.shared();
         // 就地 push，避免不必要拷贝
-        entry.value_mut().push(item);
+        self.append_history(item);
         Ok(())
     }
-    async fn list(&self, key: &ConfigKey) -> anyhow::Result<Vec<ConfigHistoryItem>> {
+    async fn list(&self, key: &ConfigKey) -> StoreResult<Vec<ConfigHistoryItem>> {
+        let _g = self.shared();
         Ok(self
             .histories
             .get(&key_of(key))
@@ -77,16 +179,371 @@ impl ConfigHistoryStore for MemStores {
     }
 }
 
+#[async_trait]
+impl ConfigBatchWriter for MemStores {
+    async fn commit(&self, batch: ConfigBatch) -> StoreResult<()> {
+        let _g = self.batch_lock.write().unwrap_or_else(|e| e.into_inner());
+        let stale: Vec<ConfigKey> = batch
+            .expected
+            .iter()
+            .filter(|(key, expected)| {
+                let current = self.configs.get(&key_of(key));
+                match (current.as_deref(), expected) {
+                    (None, None) => false,
+                    (Some(c), Some(e)) => c.version_ts != e.version_ts || c.content != e.content,
+                    _ => true,
+                }
+            })
+            .map(|(key, _)| key.clone())
+            .collect();
+        if !stale.is_empty() {
+            return Err(StoreError::Stale(stale));
+        }
+        for key in &batch.deletes {
+            self.delete_item(key);
+        }
+        for item in batch.puts {
+            self.put_item(item);
+        }
+        for h in batch.history {
+            self.append_history(h);
+        }
+        Ok(())
+    }
+    async fn next_revision(&self) -> i64 {
+        let now = Utc::now().timestamp();
+        let prev = self
+            .revision
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |last| Some((last + 1).max(now)))
+            .unwrap_or_else(|last| last);
+        (prev + 1).max(now)
+    }
+}
+
+#[async_trait]
+impl ChangesetStore for MemStores {
+    async fn put_changeset(&self, cs: Changeset) -> StoreResult<()> {
+        self.changesets.insert(cs.id.clone(), cs);
+        Ok(())
+    }
+    async fn get_changeset(&self, id: &str) -> Option<Changeset> {
+        self.changesets.get(id).map(|v| v.clone())
+    }
+    async fn list_changesets(&self, limit: usize) -> StoreResult<Vec<Changeset>> {
+        let mut v: Vec<Changeset> = self.changesets.iter().map(|e| e.value().clone()).collect();
+        v.sort_by(|a, b| b.revision.cmp(&a.revision).then(b.created_at.cmp(&a.created_at)));
+        v.truncate(limit);
+        Ok(v)
+    }
+}
+
+#[async_trait]
+impl ChangeRequestStore for MemStores {
+    async fn put_change_request(&self, cr: ChangeRequest) -> StoreResult<()> {
+        self.change_requests.insert(cr.id.clone(), cr);
+        Ok(())
+    }
+    async fn get_change_request(&self, id: &str) -> Option<ChangeRequest> {
+        self.change_requests.get(id).map(|v| v.clone())
+    }
+    async fn list_change_requests(
+        &self,
+        namespace: Option<&str>,
+        state: Option<ChangeRequestState>,
+    ) -> StoreResult<Vec<ChangeRequest>> {
+        let mut v: Vec<ChangeRequest> = self
+            .change_requests
+            .iter()
+            .filter(|e| namespace.map(|ns| e.value().key.namespace == ns).unwrap_or(true))
+            .filter(|e| state.map(|s| e.value().state == s).unwrap_or(true))
+            .map(|e| e.value().clone())
+            .collect();
+        v.sort_by_key(|c| std::cmp::Reverse(c.created_at));
+        Ok(v)
+    }
+}
+
+#[async_trait]
+impl ConfigWatcherRegistry for MemStores {
+    async fn set_watches(&self, connection_id: &str, watches: Vec<ConfigWatcher>) {
+        if watches.is_empty() {
+            self.watchers.remove(connection_id);
+        } else {
+            self.watchers.insert(connection_id.to_string(), watches);
+        }
+    }
+    async fn remove_watches(&self, connection_id: &str) {
+        self.watchers.remove(connection_id);
+    }
+    async fn touch(&self, connection_id: &str) {
+        if let Some(mut ws) = self.watchers.get_mut(connection_id) {
+            let now = Utc::now();
+            for w in ws.iter_mut() {
+                w.last_seen = now;
+            }
+        }
+    }
+    async fn watchers_of(&self, key: &ConfigKey) -> Vec<ConfigWatcher> {
+        self.watchers
+            .iter()
+            .flat_map(|e| e.value().iter().filter(|w| &w.key == key).cloned().collect::<Vec<_>>())
+            .collect()
+    }
+    async fn watches_by_ip(&self, ip: &str) -> Vec<ConfigWatcher> {
+        self.watchers
+            .iter()
+            .flat_map(|e| e.value().iter().filter(|w| w.ip == ip).cloned().collect::<Vec<_>>())
+            .collect()
+    }
+}
+
+#[async_trait]
+impl ScheduledChangeStore for MemStores {
+    async fn put_scheduled(&self, change: ScheduledChange) -> StoreResult<()> {
+        self.scheduled.insert(change.id.clone(), change);
+        Ok(())
+    }
+    async fn get_scheduled(&self, id: &str) -> Option<ScheduledChange> {
+        self.scheduled.get(id).map(|v| v.clone())
+    }
+    async fn list_scheduled(
+        &self,
+        namespace: Option<&str>,
+        state: Option<ScheduleState>,
+    ) -> StoreResult<Vec<ScheduledChange>> {
+        let mut v: Vec<ScheduledChange> = self
+            .scheduled
+            .iter()
+            .filter(|e| namespace.map(|ns| e.value().key.namespace == ns).unwrap_or(true))
+            .filter(|e| state.map(|s| e.value().state == s).unwrap_or(true))
+            .map(|e| e.value().clone())
+            .collect();
+        v.sort_by_key(|c| c.publish_at);
+        Ok(v)
+    }
+}
+
+#[async_trait]
+impl UserStore for MemStores {
+    async fn get_user(&self, username: &str) -> Option<User> {
+        self.users.get(username).map(|v| v.clone())
+    }
+    async fn list_users(&self) -> StoreResult<Vec<User>> {
+        let mut v: Vec<User> = self.users.iter().map(|e| e.value().clone()).collect();
+        v.sort_by(|a, b| a.username.cmp(&b.username));
+        Ok(v)
+    }
+    async fn put_user(&self, user: User) -> StoreResult<()> {
+        self.users.insert(user.username.clone(), user);
+        Ok(())
+    }
+    async fn delete_user(&self, username: &str) -> StoreResult<bool> {
+        Ok(self.users.remove(username).is_some())
+    }
+}
+
+#[async_trait]
+impl RoleStore for MemStores {
+    async fn list_roles(&self, username: Option<&str>) -> StoreResult<Vec<RoleBinding>> {
+        let mut v: Vec<RoleBinding> = self
+            .roles
+            .iter()
+            .map(|e| e.value().clone())
+            .filter(|b| username.is_none_or(|u| b.username == u))
+            .collect();
+        v.sort_by(|a, b| (&a.role, &a.username).cmp(&(&b.role, &b.username)));
+        Ok(v)
+    }
+    async fn add_role(&self, binding: RoleBinding) -> StoreResult<bool> {
+        let k = format!("{}+{}", binding.role, binding.username);
+        Ok(self.roles.insert(k, binding).is_none())
+    }
+    async fn delete_role(&self, role: &str, username: Option<&str>) -> StoreResult<usize> {
+        let before = self.roles.len();
+        self.roles.retain(|_, b| !(b.role == role && username.is_none_or(|u| b.username == u)));
+        Ok(before - self.roles.len())
+    }
+}
+
+#[async_trait]
+impl PermissionStore for MemStores {
+    async fn list_permissions(&self, role: Option<&str>) -> StoreResult<Vec<Permission>> {
+        let mut v: Vec<Permission> = self
+            .permissions
+            .iter()
+            .map(|e| e.value().clone())
+            .filter(|p| role.is_none_or(|r| p.role == r))
+            .collect();
+        v.sort_by(|a, b| (&a.role, &a.resource).cmp(&(&b.role, &b.resource)));
+        Ok(v)
+    }
+    async fn add_permission(&self, permission: Permission) -> StoreResult<bool> {
+        let k = permission_key(&permission.role, &permission.resource, permission.action);
+        Ok(self.permissions.insert(k, permission).is_none())
+    }
+    async fn delete_permission(&self, role: &str, resource: &str, action: Action) -> StoreResult<bool> {
+        Ok(self.permissions.remove(&permission_key(role, resource, action)).is_some())
+    }
+}
+
+#[async_trait]
+impl AccessKeyStore for MemStores {
+    async fn get_access_key(&self, access_key: &str) -> Option<AccessKey> {
+        self.access_keys.get(access_key).map(|v| v.clone())
+    }
+    async fn list_access_keys(&self) -> StoreResult<Vec<AccessKey>> {
+        let mut v: Vec<AccessKey> = self.access_keys.iter().map(|e| e.value().clone()).collect();
+        v.sort_by(|a, b| a.access_key.cmp(&b.access_key));
+        Ok(v)
+    }
+    async fn put_access_key(&self, key: AccessKey) -> StoreResult<()> {
+        self.access_keys.insert(key.access_key.clone(), key);
+        Ok(())
+    }
+    async fn delete_access_key(&self, access_key: &str) -> StoreResult<bool> {
+        Ok(self.access_keys.remove(access_key).is_some())
+    }
+}
+
+/// 超过该条数时顺带清理过期 nonce
+const NONCE_PRUNE_THRESHOLD: usize = 4096;
+
+#[async_trait]
+impl NonceStore for MemStores {
+    async fn remember_nonce(&self, scope: &str, nonce: &str, expires_at: DateTime<Utc>) -> bool {
+        let now = Utc::now();
+        if self.nonces.len() > NONCE_PRUNE_THRESHOLD {
+            self.nonces.retain(|_, exp| *exp > now);
+        }
+        match self.nonces.entry(format!("{}+{}", scope, nonce)) {
+            dashmap::mapref::entry::Entry::Occupied(mut e) => {
+                if *e.get() > now {
+                    return false;
+                }
+                e.insert(expires_at);
+                true
+            }
+            dashmap::mapref::entry::Entry::Vacant(e) => {
+                e.insert(expires_at);
+                true
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl AuditStore for MemStores {
+    async fn append_audit(&self, record: AuditRecord) -> StoreResult<AuditEntry> {
+        let mut log = self.audit.lock().unwrap();
+        let entry = AuditEntry::chain(log.last(), record);
+        log.push(entry.clone());
+        Ok(entry)
+    }
+    async fn query_audit(&self, filter: &AuditFilter, offset: usize, limit: usize) -> StoreResult<(usize, Vec<AuditEntry>)> {
+        Ok(filter.page(&self.audit.lock().unwrap(), offset, limit))
+    }
+    async fn audit_chain(&self) -> StoreResult<Vec<AuditEntry>> {
+        Ok(self.audit.lock().unwrap().clone())
+    }
+}
+
+/// 已投递记录最多保留的条数与时长；待投递与死信不清理
+const DELIVERED_KEEP: usize = 1000;
+const DELIVERED_TTL_HOURS: i64 = 24;
+
+impl MemStores {
+    /// 清理过期或超出条数的已投递记录，最旧的先清理
+    fn prune_delivered(&self) {
+        let cutoff = Utc::now() - chrono::Duration::hours(DELIVERED_TTL_HOURS);
+        self.webhook_deliveries
+            .retain(|_, d| d.state != DeliveryState::Delivered || d.updated_at > cutoff);
+        let mut delivered: Vec<(DateTime<Utc>, String)> = self
+            .webhook_deliveries
+            .iter()
+            .filter(|e| e.value().state == DeliveryState::Delivered)
+            .map(|e| (e.value().updated_at, e.key().clone()))
+            .collect();
+        if delivered.len() <= DELIVERED_KEEP {
+            return;
+        }
+        delivered.sort();
+        for (_, id) in &delivered[..delivered.len() - DELIVERED_KEEP] {
+            self.webhook_deliveries.remove(id);
+        }
+    }
+}
+
+#[async_trait]
+impl WebhookStore for MemStores {
+    async fn put_webhook(&self, hook: Webhook) -> StoreResult<()> {
+        self.webhooks.insert(hook.id.clone(), hook);
+        Ok(())
+    }
+    async fn get_webhook(&self, id: &str) -> Option<Webhook> {
+        self.webhooks.get(id).map(|v| v.clone())
+    }
+    async fn list_webhooks(&self) -> StoreResult<Vec<Webhook>> {
+        let mut v: Vec<Webhook> = self.webhooks.iter().map(|e| e.value().clone()).collect();
+        v.sort_by_key(|h| h.created_at);
+        Ok(v)
+    }
+    async fn delete_webhook(&self, id: &str) -> StoreResult<bool> {
+        Ok(self.webhooks.remove(id).is_some())
+    }
+    async fn put_delivery(&self, delivery: WebhookDelivery) -> StoreResult<()> {
+        let delivered = delivery.state == DeliveryState::Delivered;
+        self.webhook_deliveries.insert(delivery.id.clone(), delivery);
+        if delivered {
+            self.prune_delivered();
+        }
+        Ok(())
+    }
+    async fn get_delivery(&self, id: &str) -> Option<WebhookDelivery> {
+        self.webhook_deliveries.get(id).map(|v| v.clone())
+    }
+    async fn list_deliveries(
+        &self,
+        webhook_id: Option<&str>,
+        state: Option<DeliveryState>,
+        limit: usize,
+    ) -> StoreResult<Vec<WebhookDelivery>> {
+        let mut v: Vec<WebhookDelivery> = self
+            .webhook_deliveries
+            .iter()
+            .filter(|e| webhook_id.map(|id| e.value().webhook_id == id).unwrap_or(true))
+            .filter(|e| state.map(|s| e.value().state == s).unwrap_or(true))
+            .map(|e| e.value().clone())
+            .collect();
+        v.sort_by_key(|d| std::cmp::Reverse(d.created_at));
+        v.truncate(limit);
+        Ok(v)
+    }
+}
+
+#[async_trait]
+impl BetaConfigStore for MemStores {
+    async fn get_beta(&self, key: &ConfigKey) -> Option<BetaConfigItem> {
+        self.betas.get(&key_of(key)).map(|v| v.clone())
+    }
+    async fn put_beta(&self, item: BetaConfigItem) -> StoreResult<()> {
+        self.betas.insert(key_of(&item.key), item);
+        Ok(())
+    }
+    async fn delete_beta(&self, key: &ConfigKey) -> StoreResult<bool> {
+        Ok(self.betas.remove(&key_of(key)).is_some())
+    }
+}
+
 #[async_trait]
 impl InstanceStore for MemStores {
-    async fn register(&self, ins: Instance) -> anyhow::Result<()> {
+    async fn register(&self, ins: Instance) -> StoreResult<()> {
         self.instances.insert(ins.id.0.clone(), ins);
         Ok(())
     }
-    async fn deregister(&self, _service: &ServiceName, id: &InstanceId) -> anyhow::Result<bool> {
+    async fn deregister(&self, _service: &ServiceName, id: &InstanceId) -> StoreResult<bool> {
         Ok(self.instances.remove(&id.0).is_some())
     }
-    async fn beat(&self, _service: &ServiceName, id: &InstanceId) -> anyhow::Result<bool> {
+    async fn beat(&self, _service: &ServiceName, id: &InstanceId) -> StoreResult<bool> {
         if let Some(mut i) = self.instances.get_mut(&id.0) {
             i.last_beat_at = Utc::now();
             i.healthy = true;
@@ -95,7 +552,7 @@ impl InstanceStore for MemStores {
             Ok(false)
         }
     }
-    async fn list(&self, service: Option<&ServiceName>) -> anyhow::Result<Vec<Instance>> {
+    async fn list(&self, service: Option<&ServiceName>) -> StoreResult<Vec<Instance>> {
         Ok(self
             .instances
             .iter()
@@ -109,23 +566,82 @@ impl InstanceStore for MemStores {
             .map(|e| e.value().clone())
             .collect())
     }
+    async fn expire(&self, before: DateTime<Utc>) -> StoreResult<Vec<Instance>> {
+        // 先收集需要更新的键，避免持锁过久；标记时再检查一次，期间收到心跳的实例保持健康
+        let keys: Vec<String> = self
+            .instances
+            .iter()
+            .filter(|e| e.value().healthy && e.value().last_beat_at < before)
+            .map(|e| e.key().clone())
+            .collect();
+        let mut expired = Vec::new();
+        for k in keys {
+            if let Some(mut v) = self.instances.get_mut(&k) {
+                if v.healthy && v.last_beat_at < before {
+                    v.healthy = false;
+                    expired.push(v.clone());
+                }
+            }
+        }
+        Ok(expired)
+    }
 }
 
 #[async_trait]
 impl NamespaceStore for MemStores {
-    async fn create(&self, ns: Namespace) -> anyhow::Result<()> {
-        self.namespaces.insert(ns.id.clone(), ns);
-        Ok(())
+    async fn get(&self, id: &str) -> Option<Namespace> {
+        self.namespaces.get(id).map(|v| v.clone())
     }
-    async fn update(&self, ns: Namespace) -> anyhow::Result<bool> {
+    async fn create(&self, ns: Namespace) -> StoreResult<()> {
+        match self.namespaces.entry(ns.id.clone()) {
+            dashmap::mapref::entry::Entry::Occupied(_) => Err(StoreError::Conflict(format!("namespace {} already exists", ns.id))),
+            dashmap::mapref::entry::Entry::Vacant(v) => {
+                v.insert(ns);
+                Ok(())
+            }
+        }
+    }
+    async fn update(&self, ns: Namespace) -> StoreResult<bool> {
         Ok(self.namespaces.insert(ns.id.clone(), ns).is_some())
     }
-    async fn delete(&self, id: &str) -> anyhow::Result<bool> {
+    async fn delete(&self, id: &str) -> StoreResult<bool> {
         Ok(self.namespaces.remove(id).is_some())
     }
-    async fn list(&self) -> anyhow::Result<Vec<Namespace>> {
+    async fn list(&self) -> StoreResult<Vec<Namespace>> {
         Ok(self.namespaces.iter().map(|e| e.value().clone()).collect())
     }
 }
 
+#[async_trait]
+impl ConfigDependencyStore for MemStores {
+    async fn set_refs(&self, key: &ConfigKey, targets: Vec<RefTarget>) {
+        self.dependencies.set(key, &targets);
+    }
+    async fn dependents(&self, target: &RefTarget) -> Vec<ConfigKey> {
+        self.dependencies.dependents(&target.id())
+    }
+}
 
+#[async_trait]
+impl SchemaStore for MemStores {
+    async fn put(&self, schema: ConfigSchema) -> StoreResult<()> {
+        self.schemas.insert(schema.id.clone(), schema);
+        Ok(())
+    }
+    async fn get(&self, id: &str) -> Option<ConfigSchema> {
+        self.schemas.get(id).map(|v| v.clone())
+    }
+    async fn delete(&self, id: &str) -> StoreResult<bool> {
+        Ok(self.schemas.remove(id).is_some())
+    }
+    async fn list(&self, namespace: Option<&str>) -> StoreResult<Vec<ConfigSchema>> {
+        let mut v: Vec<ConfigSchema> = self
+            .schemas
+            .iter()
+            .filter(|e| namespace.map(|ns| e.value().namespace == ns).unwrap_or(true))
+            .map(|e| e.value().clone())
+            .collect();
+        v.sort_by(|a, b| a.id.cmp(&b.id));
+        Ok(v)
+    }
+}
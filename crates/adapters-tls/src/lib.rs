@@ -0,0 +1,175 @@
+use std::fs::File;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, SystemTime};
+use axum::extract::ConnectInfo;
+use axum::Router;
+use core_model::auth::ClientCertificate;
+use hyper::body::Incoming;
+use hyper::Request;
+use hyper_util::rt::{TokioExecutor, TokioIo};
+use hyper_util::server::conn::auto::Builder;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use rustls::server::WebPkiClientVerifier;
+use rustls::{RootCertStore, ServerConfig};
+use tokio::net::TcpListener;
+use tokio_rustls::TlsAcceptor;
+use tower::ServiceExt;
+
+/// 证书文件的默认检查间隔，秒
+pub const DEFAULT_RELOAD_INTERVAL_SECS: u64 = 10;
+/// 握手超时，避免半开连接一直占着任务
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// 监听端的 TLS 设置；client_ca_file 设置后要求客户端出示该 CA 签发的证书（mTLS）
+#[derive(Debug, Clone)]
+pub struct TlsSettings {
+    pub cert_file: PathBuf,
+    pub key_file: PathBuf,
+    pub client_ca_file: Option<PathBuf>,
+    pub reload_interval: Duration,
+}
+
+impl TlsSettings {
+    /// TLS_CERT_FILE 与 TLS_KEY_FILE 同时设置时启用；TLS_CLIENT_CA_FILE 开启 mTLS；
+    /// TLS_RELOAD_INTERVAL_SECS 为证书文件的检查间隔
+    pub fn from_env() -> anyhow::Result<Option<Self>> {
+        let var = |name: &str| std::env::var(name).ok().filter(|v| !v.trim().is_empty());
+        let (cert_file, key_file) = match (var("TLS_CERT_FILE"), var("TLS_KEY_FILE")) {
+            (Some(cert), Some(key)) => (cert, key),
+            (None, None) => return Ok(None),
+            _ => anyhow::bail!("TLS_CERT_FILE and TLS_KEY_FILE must be set together"),
+        };
+        let secs = var("TLS_RELOAD_INTERVAL_SECS").and_then(|v| v.parse().ok()).unwrap_or(DEFAULT_RELOAD_INTERVAL_SECS);
+        Ok(Some(Self {
+            cert_file: cert_file.into(),
+            key_file: key_file.into(),
+            client_ca_file: var("TLS_CLIENT_CA_FILE").map(Into::into),
+            reload_interval: Duration::from_secs(secs.max(1)),
+        }))
+    }
+
+    /// 读取证书链、私钥与客户端 CA，构建 rustls 配置（同时支持 h2 与 http/1.1）
+    pub fn load(&self) -> anyhow::Result<ServerConfig> {
+        let provider = Arc::new(rustls::crypto::ring::default_provider());
+        let builder = ServerConfig::builder_with_provider(provider.clone()).with_safe_default_protocol_versions()?;
+        let builder = match &self.client_ca_file {
+            Some(path) => {
+                let mut roots = RootCertStore::empty();
+                for cert in read_certs(path)? {
+                    roots.add(cert).map_err(|e| anyhow::anyhow!("invalid CA certificate in {}: {}", path.display(), e))?;
+                }
+                let verifier = WebPkiClientVerifier::builder_with_provider(Arc::new(roots), provider).build()?;
+                builder.with_client_cert_verifier(verifier)
+            }
+            None => builder.with_no_client_auth(),
+        };
+        let mut config = builder
+            .with_single_cert(read_certs(&self.cert_file)?, read_key(&self.key_file)?)
+            .map_err(|e| anyhow::anyhow!("certificate {} does not match key: {}", self.cert_file.display(), e))?;
+        config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+        Ok(config)
+    }
+
+    fn files(&self) -> impl Iterator<Item = &Path> {
+        [Some(&self.cert_file), Some(&self.key_file), self.client_ca_file.as_ref()].into_iter().flatten().map(PathBuf::as_path)
+    }
+}
+
+fn open(path: &Path) -> anyhow::Result<BufReader<File>> {
+    File::open(path).map(BufReader::new).map_err(|e| anyhow::anyhow!("failed to open {}: {}", path.display(), e))
+}
+
+fn read_certs(path: &Path) -> anyhow::Result<Vec<CertificateDer<'static>>> {
+    let certs = rustls_pemfile::certs(&mut open(path)?)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| anyhow::anyhow!("failed to parse {}: {}", path.display(), e))?;
+    if certs.is_empty() {
+        anyhow::bail!("no certificate found in {}", path.display());
+    }
+    Ok(certs)
+}
+
+fn read_key(path: &Path) -> anyhow::Result<PrivateKeyDer<'static>> {
+    rustls_pemfile::private_key(&mut open(path)?)
+        .map_err(|e| anyhow::anyhow!("failed to parse {}: {}", path.display(), e))?
+        .ok_or_else(|| anyhow::anyhow!("no private key found in {}", path.display()))
+}
+
+/// 取证书主题与 CN；解析失败时视为未出示证书
+fn client_certificate(der: &CertificateDer<'_>) -> Option<ClientCertificate> {
+    let (_, cert) = x509_parser::parse_x509_certificate(der.as_ref()).ok()?;
+    let subject = cert.subject();
+    let common_name = subject.iter_common_name().next().and_then(|cn| cn.as_str().ok()).map(str::to_string);
+    Some(ClientCertificate { subject: subject.to_string(), common_name })
+}
+
+/// 文件的修改时间与长度，任一变化即重新加载
+fn fingerprint(settings: &TlsSettings) -> Vec<Option<(SystemTime, u64)>> {
+    settings
+        .files()
+        .map(|p| std::fs::metadata(p).ok().and_then(|m| Some((m.modified().ok()?, m.len()))))
+        .collect()
+}
+
+/// 定期检查证书、私钥与 CA 文件，变化后重建配置；新配置只作用于之后建立的连接。
+/// 加载失败（如证书与私钥尚未都写完）时保留旧配置，等下一次变化再试
+async fn watch(settings: TlsSettings, current: Arc<RwLock<Arc<ServerConfig>>>) {
+    let mut last = fingerprint(&settings);
+    let mut tick = tokio::time::interval(settings.reload_interval);
+    tick.tick().await;
+    loop {
+        tick.tick().await;
+        let now = fingerprint(&settings);
+        if now == last {
+            continue;
+        }
+        last = now;
+        match settings.load() {
+            Ok(config) => {
+                *current.write().unwrap() = Arc::new(config);
+                tracing::info!("reloaded TLS certificate from {}", settings.cert_file.display());
+            }
+            Err(e) => tracing::warn!("failed to reload TLS certificate, keeping the previous one: {}", e),
+        }
+    }
+}
+
+/// 以 TLS 提供服务。每个请求带上 `ConnectInfo<SocketAddr>`；mTLS 下另带 `ClientCertificate`，
+/// 供认证层作为调用方身份
+pub async fn serve(listener: TcpListener, app: Router, settings: TlsSettings) -> anyhow::Result<()> {
+    let current = Arc::new(RwLock::new(Arc::new(settings.load()?)));
+    tokio::spawn(watch(settings, current.clone()));
+    loop {
+        let (stream, peer) = match listener.accept().await {
+            Ok(conn) => conn,
+            Err(e) => {
+                tracing::warn!("failed to accept connection: {}", e);
+                tokio::time::sleep(Duration::from_millis(100)).await;
+                continue;
+            }
+        };
+        let acceptor = TlsAcceptor::from(current.read().unwrap().clone());
+        let app = app.clone();
+        tokio::spawn(async move {
+            let tls = match tokio::time::timeout(HANDSHAKE_TIMEOUT, acceptor.accept(stream)).await {
+                Ok(Ok(tls)) => tls,
+                Ok(Err(e)) => return tracing::debug!("TLS handshake with {} failed: {}", peer, e),
+                Err(_) => return tracing::debug!("TLS handshake with {} timed out", peer),
+            };
+            let cert = tls.get_ref().1.peer_certificates().and_then(|c| c.first()).and_then(client_certificate);
+            let service = hyper::service::service_fn(move |mut req: Request<Incoming>| {
+                req.extensions_mut().insert(ConnectInfo(peer));
+                if let Some(cert) = &cert {
+                    req.extensions_mut().insert(cert.clone());
+                }
+                app.clone().oneshot(req)
+            });
+            // 保留协议升级，WebSocket 事件通道同样可走 TLS
+            if let Err(e) = Builder::new(TokioExecutor::new()).serve_connection_with_upgrades(TokioIo::new(tls), service).await {
+                tracing::debug!("connection from {} closed: {}", peer, e);
+            }
+        });
+    }
+}
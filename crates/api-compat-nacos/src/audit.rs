@@ -0,0 +1,166 @@
+use std::fmt::Display;
+use axum::extract::{Query, State};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use core_model::audit::{AuditAction, AuditEntry, AuditFilter, AuditRecord};
+use core_model::auth::{Action, Resource};
+use core_model::config::ConfigKey;
+use core_model::instance::Instance;
+use core_ports::ConfigStore;
+use core_usecase::audit::VerifyAuditChain;
+use core_usecase::config::content_md5;
+
+use super::{admin_only, ok, service_resource, ApiError, ApiResult, AppCtx, Caller};
+
+pub(crate) fn config_resource(key: &ConfigKey) -> String {
+    Resource::Config(key.clone()).path()
+}
+
+pub(crate) fn namespace_resource(id: &str) -> String {
+    format!("namespace/{}", id)
+}
+
+pub(crate) fn user_resource(username: &str) -> String {
+    format!("user/{}", username)
+}
+
+/// 指定用户时为单个绑定，否则为整个角色
+pub(crate) fn role_resource(role: &str, username: Option<&str>) -> String {
+    match username {
+        Some(u) => format!("role/{}/{}", role, u),
+        None => format!("role/{}", role),
+    }
+}
+
+pub(crate) fn permission_resource(role: &str, resource: &str, action: Action) -> String {
+    format!("permission/{}/{}/{}", role, action.as_str(), resource)
+}
+
+pub(crate) fn access_key_resource(ak: &str) -> String {
+    format!("access_key/{}", ak)
+}
+
+pub(crate) fn webhook_resource(id: &str) -> String {
+    format!("webhook/{}", id)
+}
+
+pub(crate) fn instance_resource(i: &Instance) -> String {
+    format!("{}/{}:{}", service_resource(&i.group, &i.service.0).path(), i.ip, i.port)
+}
+
+/// 命名空间、实例以 JSON 的 MD5 作为内容摘要
+pub(crate) fn entity_hash<T: Serialize>(v: &T) -> String {
+    content_md5(&serde_json::to_string(v).unwrap_or_default())
+}
+
+/// 存储内容（加密配置为密文）的 MD5，与配置事件中的 md5 一致
+pub(crate) async fn config_hash(ctx: &AppCtx, key: &ConfigKey) -> Option<String> {
+    ConfigStore::get(&*ctx.stores, key).await.map(|c| content_md5(&c.content))
+}
+
+/// 追加一条审计记录；写入失败只记日志，不影响已完成的操作
+pub(crate) async fn record<T, E: Display>(
+    ctx: &AppCtx,
+    caller: &Caller,
+    action: AuditAction,
+    resource: String,
+    (before_hash, after_hash): (Option<String>, Option<String>),
+    result: &Result<T, E>,
+) {
+    let record = AuditRecord {
+        at: Utc::now(),
+        operator: caller.name.clone(),
+        source_ip: caller.ip.clone(),
+        user_agent: caller.user_agent.clone(),
+        action,
+        resource,
+        before_hash,
+        after_hash,
+        success: result.is_ok(),
+        message: result.as_ref().err().map(|e| e.to_string()),
+    };
+    if let Err(e) = ctx.audit.append_audit(record).await {
+        tracing::error!("failed to append audit record: {}", e);
+    }
+}
+
+/// 配置写操作的审计，变更后的摘要取自当前存储
+pub(crate) async fn record_config<T, E: Display>(
+    ctx: &AppCtx,
+    caller: &Caller,
+    action: AuditAction,
+    key: &ConfigKey,
+    before: Option<String>,
+    result: &Result<T, E>,
+) {
+    let after = config_hash(ctx, key).await;
+    record(ctx, caller, action, config_resource(key), (before, after), result).await;
+}
+
+#[derive(Deserialize)]
+pub(crate) struct AuditQuery {
+    operator: Option<String>,
+    action: Option<String>,
+    /// 资源路径，支持 `*` / `?` 通配
+    resource: Option<String>,
+    success: Option<bool>,
+    /// Unix 秒，左闭右开
+    since: Option<i64>,
+    until: Option<i64>,
+    page: Option<usize>,
+    size: Option<usize>,
+}
+
+#[derive(Serialize)]
+pub(crate) struct AuditPageDto {
+    total_count: usize,
+    page_number: usize,
+    page_size: usize,
+    items: Vec<AuditEntry>,
+}
+
+/// 按序号倒序分页查询，仅限全局管理员
+pub(crate) async fn list_audit(
+    State(ctx): State<AppCtx>,
+    caller: Caller,
+    Query(q): Query<AuditQuery>,
+) -> ApiResult<AuditPageDto> {
+    admin_only(&caller)?;
+    let non_empty = |v: Option<String>| v.map(|s| s.trim().to_string()).filter(|s| !s.is_empty());
+    let action = non_empty(q.action).map(|a| a.parse::<AuditAction>()).transpose().map_err(ApiError::Validation)?;
+    let time = |secs: Option<i64>| secs.and_then(|s| DateTime::<Utc>::from_timestamp(s, 0));
+    let filter = AuditFilter {
+        operator: non_empty(q.operator),
+        action,
+        resource: non_empty(q.resource),
+        success: q.success,
+        since: time(q.since),
+        until: time(q.until),
+    };
+    let page = q.page.unwrap_or(1).max(1);
+    let size = q.size.unwrap_or(20).clamp(1, 500);
+    let (total, items) = ctx.audit.query_audit(&filter, page.saturating_sub(1).saturating_mul(size), size).await?;
+    ok(AuditPageDto { total_count: total, page_number: page, page_size: size, items })
+}
+
+#[derive(Serialize)]
+pub(crate) struct ChainReportDto {
+    total: usize,
+    intact: bool,
+    broken_at: Option<u64>,
+    reason: Option<String>,
+    head_hash: Option<String>,
+}
+
+/// 重算整条哈希链，报告第一处断点
+pub(crate) async fn verify_audit(State(ctx): State<AppCtx>, caller: Caller) -> ApiResult<ChainReportDto> {
+    admin_only(&caller)?;
+    let r = (VerifyAuditChain { store: &*ctx.audit }).exec().await?;
+    ok(ChainReportDto {
+        total: r.total,
+        intact: r.broken_at.is_none(),
+        broken_at: r.broken_at,
+        reason: r.reason,
+        head_hash: r.head_hash,
+    })
+}
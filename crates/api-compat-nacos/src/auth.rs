@@ -0,0 +1,590 @@
+use std::net::SocketAddr;
+use std::sync::Arc;
+use axum::{
+    async_trait,
+    body::{to_bytes, Body},
+    extract::{ConnectInfo, Form, FromRequestParts, Path, Query, Request, State},
+    http::{request::Parts, HeaderMap},
+    middleware::Next,
+    response::{IntoResponse, Json, Response},
+    Router,
+};
+use adapters_notify_sse::EventPrincipal;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use core_model::audit::AuditAction;
+use core_model::auth::{
+    AccessKey, Action, ClientCertificate, Permission, Resource, RoleBinding, User, HEADER_ACCESS_KEY, HEADER_NONCE,
+    HEADER_SIGNATURE, HEADER_TIMESTAMP,
+};
+use core_ports::{AccessKeyStore, NonceStore, PasswordHasher, PermissionStore, RoleStore, TokenService, UserStore};
+use core_usecase::auth::{
+    AuthError, BindRole, ChangePassword, CreateAccessKey, CreateUser, DeleteAccessKey, DeleteRole, DeleteUser,
+    GrantPermission, Grants, LoadGrants, Login, RevokeAccessKey, RotateAccessKey, SignedRequest, VerifySignature,
+    VerifyToken,
+};
+use uuid::Uuid;
+
+use super::{audit, client, limits, ok, ApiError, ApiResult, AppCtx};
+
+/// 认证与授权所需的端口；enabled 为 false 时所有 API 免令牌访问且不做权限检查
+#[derive(Clone)]
+pub struct AuthCtx {
+    pub enabled: bool,
+    pub users: Arc<dyn UserStore>,
+    pub roles: Arc<dyn RoleStore>,
+    pub permissions: Arc<dyn PermissionStore>,
+    pub hasher: Arc<dyn PasswordHasher>,
+    pub tokens: Arc<dyn TokenService>,
+    pub keys: Arc<dyn AccessKeyStore>,
+    pub nonces: Arc<dyn NonceStore>,
+    /// 签名时间戳允许的偏差，秒
+    pub signature_window_secs: i64,
+    /// 轮换 SK 时旧密钥的默认宽限期，秒
+    pub rotation_grace_secs: i64,
+}
+
+/// 令牌或签名校验通过后放入请求扩展的当前用户（AK 请求为 access_key，客户端证书为 `cert:CN`）
+#[derive(Debug, Clone)]
+pub struct Principal(pub String);
+
+/// 处理请求的调用方。启用认证时为令牌中的用户并带上其权限；
+/// 关闭认证时取 X-Nacos-User 头（缺省 admin），不做权限检查
+pub(crate) struct Caller {
+    pub name: String,
+    /// 来源 IP 与 User-Agent，写入审计日志
+    pub ip: String,
+    pub user_agent: String,
+    grants: Option<Grants>,
+}
+
+impl Caller {
+    pub fn can(&self, resource: &Resource, action: Action) -> bool {
+        self.grants.as_ref().is_none_or(|g| g.allows(resource, action))
+    }
+
+    /// 未启用认证时为 None，表示不做权限检查
+    pub fn grants(&self) -> Option<&Grants> {
+        self.grants.as_ref()
+    }
+
+    pub fn is_admin(&self) -> bool {
+        self.grants.as_ref().is_none_or(|g| g.admin)
+    }
+
+    /// 记录到历史、变更集与事件中的操作人
+    pub fn actor(&self) -> Option<String> {
+        Some(self.name.clone())
+    }
+}
+
+#[async_trait]
+impl FromRequestParts<AppCtx> for Caller {
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, ctx: &AppCtx) -> Result<Self, ApiError> {
+        let peer = parts.extensions.get::<ConnectInfo<SocketAddr>>().map(|c| c.0);
+        let ip = client::client_ip(&parts.headers, peer);
+        let user_agent = header_str(&parts.headers, "user-agent").to_string();
+        let Some(Principal(name)) = parts.extensions.get::<Principal>().cloned() else {
+            return Ok(Caller { name: client::actor(&parts.headers), ip, user_agent, grants: None });
+        };
+        let uc = LoadGrants { roles: &*ctx.auth.roles, permissions: &*ctx.auth.permissions };
+        let grants = uc.exec(&name).await?;
+        Ok(Caller { name, ip, user_agent, grants: Some(grants) })
+    }
+}
+
+/// 缺少任一资源的权限时返回 403，写法同 check_approval
+pub(crate) fn authorize(caller: &Caller, resources: &[Resource], action: Action) -> Result<(), ApiError> {
+    match resources.iter().find(|r| !caller.can(r, action)) {
+        Some(missing) => Err(ApiError::Forbidden(format!(
+            "user {} has no `{}` permission on {}",
+            caller.name,
+            action.as_str(),
+            missing.path()
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// 用户、角色、权限、命名空间与 Webhook 的管理仅限全局管理员
+pub(crate) fn admin_only(caller: &Caller) -> Result<(), ApiError> {
+    if !caller.is_admin() {
+        return Err(ApiError::Forbidden(format!("user {} is not a global admin", caller.name)));
+    }
+    Ok(())
+}
+
+// -------------------- Authentication --------------------
+
+/// 依次取 `Authorization: Bearer`、`accessToken`（Nacos 客户端）与 `access_token` 查询参数
+pub(crate) fn request_token(headers: &HeaderMap, query: Option<&str>) -> Option<String> {
+    let bearer = headers
+        .get("authorization")
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.strip_prefix("Bearer ").unwrap_or(v).trim().to_string());
+    // 跳过空值，与推送服务读取的参数保持一致
+    let from_query = || {
+        query?.split('&').find_map(|pair| {
+            let (k, v) = pair.split_once('=')?;
+            matches!(k, "accessToken" | "access_token")
+                .then(|| urlencoding::decode(v).map(|s| s.into_owned()).unwrap_or_default())
+                .filter(|t| !t.is_empty())
+        })
+    };
+    bearer.or_else(from_query).filter(|t| !t.is_empty())
+}
+
+/// 签名请求的请求体上限，验签需要完整读入
+const SIGNED_BODY_LIMIT: usize = 16 * 1024 * 1024;
+
+fn header_str<'h>(headers: &'h HeaderMap, name: &str) -> &'h str {
+    headers.get(name).and_then(|v| v.to_str().ok()).unwrap_or_default()
+}
+
+/// 校验 AK/SK 签名；需要读入请求体计算摘要，验签后原样放回
+async fn verify_signed(auth: &AuthCtx, req: Request) -> Result<(Request, String), ApiError> {
+    let (parts, body) = req.into_parts();
+    let body = to_bytes(body, SIGNED_BODY_LIMIT)
+        .await
+        .map_err(|_| ApiError::PayloadTooLarge("signed request body too large".into()))?;
+    let headers = &parts.headers;
+    let Ok(timestamp) = header_str(headers, HEADER_TIMESTAMP).parse::<i64>() else {
+        return Err(ApiError::Unauthorized(format!("{HEADER_TIMESTAMP} must be unix seconds")));
+    };
+    let signed = SignedRequest {
+        access_key: header_str(headers, HEADER_ACCESS_KEY),
+        signature: header_str(headers, HEADER_SIGNATURE),
+        method: parts.method.as_str(),
+        path: parts.uri.path(),
+        query: parts.uri.query().unwrap_or_default(),
+        timestamp,
+        nonce: header_str(headers, HEADER_NONCE),
+        body: &body,
+    };
+    let uc = VerifySignature {
+        keys: &*auth.keys,
+        nonces: &*auth.nonces,
+        window_secs: auth.signature_window_secs,
+    };
+    let access_key = uc.exec(&signed).await?;
+    Ok((Request::from_parts(parts, Body::from(body)), access_key))
+}
+
+/// 挂在除登录与健康检查外的全部 API 路由上；带 AK 头的请求走签名校验，否则校验令牌；
+/// 两者都没有但 mTLS 连接出示了有效客户端证书时，以证书身份访问
+pub(crate) async fn require_token(State(ctx): State<AppCtx>, req: Request, next: Next) -> Response {
+    if !ctx.auth.enabled {
+        return next.run(req).await;
+    }
+    match authenticate(&ctx.auth, req).await {
+        Ok((req, Some(_))) => next.run(req).await,
+        Ok((_, None)) => ApiError::Unauthorized("access token required".into()).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// 依次尝试 AK 签名、令牌与客户端证书，通过后放入 Principal；未携带任何凭据时返回 None
+async fn authenticate(auth: &AuthCtx, mut req: Request) -> Result<(Request, Option<String>), ApiError> {
+    let name = if req.headers().contains_key(HEADER_ACCESS_KEY) {
+        let (signed, access_key) = verify_signed(auth, req).await?;
+        req = signed;
+        Some(access_key)
+    } else if let Some(token) = request_token(req.headers(), req.uri().query()) {
+        let uc = VerifyToken { users: &*auth.users, tokens: &*auth.tokens };
+        Some(uc.exec(&token).await?)
+    } else {
+        req.extensions().get::<ClientCertificate>().map(ClientCertificate::principal)
+    };
+    if let Some(name) = &name {
+        req.extensions_mut().insert(Principal(name.clone()));
+    }
+    Ok((req, name))
+}
+
+/// 事件流路由的认证，凭据与 API 相同；通过后把订阅方身份与权限交给推送服务。
+/// 开启认证时未携带凭据的请求返回 401，推送服务不会收到没有身份的订阅
+async fn authenticate_events(State(auth): State<AuthCtx>, mut req: Request, next: Next) -> Response {
+    if !auth.enabled {
+        let name = client::actor(req.headers());
+        req.extensions_mut().insert(EventPrincipal { name, grants: None });
+        return next.run(req).await;
+    }
+    let (mut req, name) = match authenticate(&auth, req).await {
+        Ok((req, Some(name))) => (req, name),
+        Ok((_, None)) => return ApiError::Unauthorized("access token required".into()).into_response(),
+        Err(e) => return e.into_response(),
+    };
+    let uc = LoadGrants { roles: &*auth.roles, permissions: &*auth.permissions };
+    match uc.exec(&name).await {
+        Ok(grants) => {
+            req.extensions_mut().insert(EventPrincipal { name, grants: Some(grants) });
+            next.run(req).await
+        }
+        Err(e) => ApiError::from(e).into_response(),
+    }
+}
+
+/// 为事件流路由挂上认证，由 app-bootstrap 在限流之内挂载
+pub fn event_auth<S: Clone + Send + Sync + 'static>(router: Router<S>, auth: AuthCtx) -> Router<S> {
+    router.route_layer(axum::middleware::from_fn_with_state(auth, authenticate_events))
+}
+
+#[derive(Deserialize, Default)]
+pub(crate) struct LoginParams {
+    username: Option<String>,
+    password: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct LoginResponse {
+    access_token: String,
+    token_ttl: i64,
+    username: String,
+    global_admin: bool,
+}
+
+/// Nacos 兼容登录：参数可放在查询串或表单中，成功直接返回 `accessToken` / `tokenTtl`
+pub(crate) async fn login(
+    State(ctx): State<AppCtx>,
+    peer: Option<ConnectInfo<SocketAddr>>,
+    headers: HeaderMap,
+    Query(q): Query<LoginParams>,
+    form: Option<Form<LoginParams>>,
+) -> Result<Json<LoginResponse>, ApiError> {
+    let form = form.map(|f| f.0).unwrap_or_default();
+    let username = form.username.or(q.username).unwrap_or_default();
+    let password = form.password.or(q.password).unwrap_or_default();
+    ctx.limiter.check_login(&limits::peer_addr(&headers, peer.map(|c| c.0)), &username)?;
+    let uc = Login { users: &*ctx.auth.users, hasher: &*ctx.auth.hasher, tokens: &*ctx.auth.tokens };
+    let t = uc.exec(&username, &password).await?;
+    let grants = LoadGrants { roles: &*ctx.auth.roles, permissions: &*ctx.auth.permissions };
+    let g = grants.exec(&username).await?;
+    Ok(Json(LoginResponse { access_token: t.token, token_ttl: t.ttl, username, global_admin: g.admin }))
+}
+
+// -------------------- Users --------------------
+#[derive(Serialize)]
+pub(crate) struct UserDto {
+    username: String,
+    create_time: i64,
+    update_time: i64,
+}
+
+fn to_user_dto(u: User) -> UserDto {
+    UserDto { username: u.username, create_time: u.created_at.timestamp(), update_time: u.updated_at.timestamp() }
+}
+
+#[derive(Deserialize)]
+pub(crate) struct CreateUserRequest {
+    username: String,
+    password: String,
+}
+
+pub(crate) async fn create_user(State(ctx): State<AppCtx>, caller: Caller, Json(req): Json<CreateUserRequest>) -> ApiResult<UserDto> {
+    admin_only(&caller)?;
+    let uc = CreateUser { users: &*ctx.auth.users, hasher: &*ctx.auth.hasher };
+    let res = uc.exec(&req.username, &req.password).await;
+    let after = res.as_ref().ok().map(audit::entity_hash);
+    audit::record(&ctx, &caller, AuditAction::UserCreate, audit::user_resource(req.username.trim()), (None, after), &res).await;
+    ok(to_user_dto(res?))
+}
+
+pub(crate) async fn list_users(State(ctx): State<AppCtx>, caller: Caller) -> ApiResult<Vec<UserDto>> {
+    admin_only(&caller)?;
+    let items = ctx.auth.users.list_users().await?;
+    ok(items.into_iter().map(to_user_dto).collect())
+}
+
+#[derive(Deserialize)]
+pub(crate) struct ChangePasswordRequest {
+    #[serde(alias = "newPassword")]
+    password: String,
+}
+
+/// 修改密码，本人或全局管理员可改；已签发的令牌在过期前仍然有效
+pub(crate) async fn change_password(
+    State(ctx): State<AppCtx>,
+    caller: Caller,
+    Path(username): Path<String>,
+    Json(req): Json<ChangePasswordRequest>,
+) -> ApiResult<UserDto> {
+    if caller.name != username {
+        admin_only(&caller)?;
+    }
+    let before = ctx.auth.users.get_user(&username).await.map(|u| audit::entity_hash(&u));
+    let uc = ChangePassword { users: &*ctx.auth.users, hasher: &*ctx.auth.hasher };
+    let res = uc.exec(&username, &req.password).await;
+    let after = res.as_ref().map(audit::entity_hash).ok().or(before.clone());
+    audit::record(&ctx, &caller, AuditAction::UserUpdate, audit::user_resource(&username), (before, after), &res).await;
+    ok(to_user_dto(res?))
+}
+
+/// 删除用户，其令牌与角色绑定随即失效；不允许删除最后一个用户或最后一个全局管理员
+pub(crate) async fn delete_user(State(ctx): State<AppCtx>, caller: Caller, Path(username): Path<String>) -> ApiResult<bool> {
+    admin_only(&caller)?;
+    let before = ctx.auth.users.get_user(&username).await.map(|u| audit::entity_hash(&u));
+    let uc = DeleteUser { users: &*ctx.auth.users, roles: &*ctx.auth.roles };
+    let res = uc.exec(&username).await;
+    let after = if res.is_ok() { None } else { before.clone() };
+    audit::record(&ctx, &caller, AuditAction::UserDelete, audit::user_resource(&username), (before, after), &res).await;
+    res?;
+    ok(true)
+}
+
+// -------------------- Roles & permissions --------------------
+// 参数与 Nacos 相同，可放在查询串或表单中
+#[derive(Deserialize, Default)]
+pub(crate) struct RoleParams {
+    role: Option<String>,
+    username: Option<String>,
+}
+
+fn merge_role_params(q: RoleParams, form: Option<Form<RoleParams>>) -> RoleParams {
+    let form = form.map(|f| f.0).unwrap_or_default();
+    RoleParams {
+        role: form.role.or(q.role).filter(|r| !r.is_empty()),
+        username: form.username.or(q.username).filter(|u| !u.is_empty()),
+    }
+}
+
+pub(crate) async fn bind_role(
+    State(ctx): State<AppCtx>,
+    caller: Caller,
+    Query(q): Query<RoleParams>,
+    form: Option<Form<RoleParams>>,
+) -> ApiResult<RoleBinding> {
+    admin_only(&caller)?;
+    let p = merge_role_params(q, form);
+    let (Some(role), Some(username)) = (p.role, p.username) else {
+        return Err(ApiError::Validation("role and username are required".into()));
+    };
+    let uc = BindRole { users: &*ctx.auth.users, keys: &*ctx.auth.keys, roles: &*ctx.auth.roles };
+    let res = uc.exec(&role, &username).await;
+    let after = res.as_ref().ok().map(audit::entity_hash);
+    audit::record(&ctx, &caller, AuditAction::RoleBind, audit::role_resource(&role, Some(&username)), (None, after), &res).await;
+    ok(res?)
+}
+
+/// 按用户过滤时只返回该用户的绑定
+pub(crate) async fn list_roles(State(ctx): State<AppCtx>, caller: Caller, Query(q): Query<RoleParams>) -> ApiResult<Vec<RoleBinding>> {
+    admin_only(&caller)?;
+    let username = q.username.filter(|u| !u.is_empty());
+    let mut items = ctx.auth.roles.list_roles(username.as_deref()).await?;
+    if let Some(role) = q.role.filter(|r| !r.is_empty()) {
+        items.retain(|b| b.role == role);
+    }
+    ok(items)
+}
+
+/// 指定 username 时解除该用户的绑定，否则删除整个角色及其权限
+pub(crate) async fn delete_role(
+    State(ctx): State<AppCtx>,
+    caller: Caller,
+    Query(q): Query<RoleParams>,
+    form: Option<Form<RoleParams>>,
+) -> ApiResult<usize> {
+    admin_only(&caller)?;
+    let p = merge_role_params(q, form);
+    let Some(role) = p.role else {
+        return Err(ApiError::Validation("role is required".into()));
+    };
+    let uc = DeleteRole { roles: &*ctx.auth.roles, permissions: &*ctx.auth.permissions };
+    let res = uc.exec(&role, p.username.as_deref()).await;
+    let resource = audit::role_resource(&role, p.username.as_deref());
+    audit::record(&ctx, &caller, AuditAction::RoleDelete, resource, (None, None), &res).await;
+    ok(res?)
+}
+
+#[derive(Deserialize, Default)]
+pub(crate) struct PermissionParams {
+    role: Option<String>,
+    resource: Option<String>,
+    /// r / w / rw
+    action: Option<String>,
+}
+
+type PermissionKey = (String, String, Action);
+
+fn permission_key(q: PermissionParams, form: Option<Form<PermissionParams>>) -> Result<PermissionKey, AuthError> {
+    let form = form.map(|f| f.0).unwrap_or_default();
+    let non_empty = |v: Option<String>| v.filter(|s| !s.is_empty());
+    let (Some(role), Some(resource), Some(action)) =
+        (non_empty(form.role.or(q.role)), non_empty(form.resource.or(q.resource)), non_empty(form.action.or(q.action)))
+    else {
+        return Err(AuthError::Invalid("role, resource and action are required".into()));
+    };
+    let action = action.parse::<Action>().map_err(AuthError::Invalid)?;
+    Ok((role, resource, action))
+}
+
+pub(crate) async fn add_permission(
+    State(ctx): State<AppCtx>,
+    caller: Caller,
+    Query(q): Query<PermissionParams>,
+    form: Option<Form<PermissionParams>>,
+) -> ApiResult<Permission> {
+    admin_only(&caller)?;
+    let (role, resource, action) = permission_key(q, form)?;
+    let uc = GrantPermission { roles: &*ctx.auth.roles, permissions: &*ctx.auth.permissions };
+    let res = uc.exec(&role, &resource, action).await;
+    let after = res.as_ref().ok().map(audit::entity_hash);
+    let target = audit::permission_resource(&role, &resource, action);
+    audit::record(&ctx, &caller, AuditAction::PermissionGrant, target, (None, after), &res).await;
+    ok(res?)
+}
+
+pub(crate) async fn list_permissions(State(ctx): State<AppCtx>, caller: Caller, Query(q): Query<PermissionParams>) -> ApiResult<Vec<Permission>> {
+    admin_only(&caller)?;
+    let role = q.role.filter(|r| !r.is_empty());
+    ok(ctx.auth.permissions.list_permissions(role.as_deref()).await?)
+}
+
+pub(crate) async fn delete_permission(
+    State(ctx): State<AppCtx>,
+    caller: Caller,
+    Query(q): Query<PermissionParams>,
+    form: Option<Form<PermissionParams>>,
+) -> ApiResult<bool> {
+    admin_only(&caller)?;
+    let (role, resource, action) = permission_key(q, form)?;
+    let res = ctx.auth.permissions.delete_permission(&role, &resource, action).await;
+    let target = audit::permission_resource(&role, &resource, action);
+    audit::record(&ctx, &caller, AuditAction::PermissionRevoke, target, (None, None), &res).await;
+    ok(res?)
+}
+
+// -------------------- Access keys --------------------
+/// SK 只在创建与轮换时返回一次，其余接口不回显
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AccessKeyDto {
+    access_key: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    secret_key: Option<String>,
+    desc: String,
+    roles: Vec<String>,
+    revoked: bool,
+    /// 旧 SK 的失效时间，轮换宽限期内才有
+    #[serde(skip_serializing_if = "Option::is_none")]
+    previous_expires_at: Option<i64>,
+    create_time: i64,
+    update_time: i64,
+}
+
+async fn to_key_dto(ctx: &AppCtx, k: AccessKey, reveal: bool) -> Result<AccessKeyDto, ApiError> {
+    let roles = ctx.auth.roles.list_roles(Some(&k.access_key)).await?.into_iter().map(|b| b.role).collect();
+    let previous_expires_at = k.previous_expires_at.filter(|t| *t > Utc::now()).map(|t| t.timestamp());
+    Ok(AccessKeyDto {
+        secret_key: reveal.then_some(k.secret_key),
+        desc: k.desc,
+        roles,
+        revoked: k.revoked_at.is_some(),
+        previous_expires_at,
+        create_time: k.created_at.timestamp(),
+        update_time: k.updated_at.timestamp(),
+        access_key: k.access_key,
+    })
+}
+
+async fn key_response(ctx: &AppCtx, result: anyhow::Result<AccessKey>, reveal: bool) -> ApiResult<AccessKeyDto> {
+    ok(to_key_dto(ctx, result?, reveal).await?)
+}
+
+async fn key_hash(ctx: &AppCtx, ak: &str) -> Option<String> {
+    ctx.auth.keys.get_access_key(ak).await.map(|k| audit::entity_hash(&k))
+}
+
+fn generate_access_key() -> String {
+    format!("AK{}", Uuid::new_v4().simple().to_string()[..20].to_uppercase())
+}
+
+fn generate_secret_key() -> String {
+    format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple())
+}
+
+#[derive(Deserialize, Default)]
+pub(crate) struct CreateAccessKeyRequest {
+    #[serde(default)]
+    desc: String,
+    #[serde(default)]
+    roles: Vec<String>,
+}
+
+pub(crate) async fn create_access_key(
+    State(ctx): State<AppCtx>,
+    caller: Caller,
+    req: Option<Json<CreateAccessKeyRequest>>,
+) -> ApiResult<AccessKeyDto> {
+    admin_only(&caller)?;
+    let req = req.map(|r| r.0).unwrap_or_default();
+    let uc = CreateAccessKey { keys: &*ctx.auth.keys, roles: &*ctx.auth.roles };
+    let ak = generate_access_key();
+    let result = uc.exec(ak.clone(), generate_secret_key(), req.desc, &req.roles).await;
+    let after = result.as_ref().ok().map(audit::entity_hash);
+    audit::record(&ctx, &caller, AuditAction::AccessKeyCreate, audit::access_key_resource(&ak), (None, after), &result).await;
+    key_response(&ctx, result, true).await
+}
+
+pub(crate) async fn list_access_keys(State(ctx): State<AppCtx>, caller: Caller) -> ApiResult<Vec<AccessKeyDto>> {
+    admin_only(&caller)?;
+    let items = ctx.auth.keys.list_access_keys().await?;
+    let mut out = Vec::with_capacity(items.len());
+    for k in items {
+        out.push(to_key_dto(&ctx, k, false).await?);
+    }
+    ok(out)
+}
+
+pub(crate) async fn get_access_key(State(ctx): State<AppCtx>, caller: Caller, Path(ak): Path<String>) -> ApiResult<AccessKeyDto> {
+    admin_only(&caller)?;
+    let result = ctx.auth.keys.get_access_key(&ak).await.ok_or_else(|| AuthError::AccessKeyNotFound.into());
+    key_response(&ctx, result, false).await
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RotateParams {
+    /// 旧 SK 继续有效的秒数，缺省取 AUTH_KEY_ROTATION_GRACE_SECS，0 表示立即失效
+    grace_secs: Option<i64>,
+}
+
+/// 生成新 SK 并返回；宽限期内新旧 SK 都能通过验签，便于客户端滚动更新
+pub(crate) async fn rotate_access_key(
+    State(ctx): State<AppCtx>,
+    caller: Caller,
+    Path(ak): Path<String>,
+    Query(q): Query<RotateParams>,
+) -> ApiResult<AccessKeyDto> {
+    admin_only(&caller)?;
+    let grace = q.grace_secs.unwrap_or(ctx.auth.rotation_grace_secs).max(0);
+    let before = key_hash(&ctx, &ak).await;
+    let uc = RotateAccessKey { keys: &*ctx.auth.keys };
+    let result = uc.exec(&ak, generate_secret_key(), grace).await;
+    let after = result.as_ref().map(audit::entity_hash).ok().or(before.clone());
+    audit::record(&ctx, &caller, AuditAction::AccessKeyRotate, audit::access_key_resource(&ak), (before, after), &result).await;
+    key_response(&ctx, result, true).await
+}
+
+pub(crate) async fn revoke_access_key(State(ctx): State<AppCtx>, caller: Caller, Path(ak): Path<String>) -> ApiResult<AccessKeyDto> {
+    admin_only(&caller)?;
+    let before = key_hash(&ctx, &ak).await;
+    let result = RevokeAccessKey { keys: &*ctx.auth.keys }.exec(&ak).await;
+    let after = result.as_ref().map(audit::entity_hash).ok().or(before.clone());
+    audit::record(&ctx, &caller, AuditAction::AccessKeyRevoke, audit::access_key_resource(&ak), (before, after), &result).await;
+    key_response(&ctx, result, false).await
+}
+
+/// 删除 AK 并解除其角色绑定
+pub(crate) async fn delete_access_key(State(ctx): State<AppCtx>, caller: Caller, Path(ak): Path<String>) -> ApiResult<bool> {
+    admin_only(&caller)?;
+    let before = key_hash(&ctx, &ak).await;
+    let uc = DeleteAccessKey { keys: &*ctx.auth.keys, roles: &*ctx.auth.roles };
+    let res = uc.exec(&ak).await;
+    let after = if res.is_ok() { None } else { before.clone() };
+    audit::record(&ctx, &caller, AuditAction::AccessKeyDelete, audit::access_key_resource(&ak), (before, after), &res).await;
+    res?;
+    ok(true)
+}
@@ -0,0 +1,149 @@
+use std::collections::HashMap;
+use axum::{extract::{Query, State}, response::Json};
+use serde::{Deserialize, Serialize};
+use core_model::audit::AuditAction;
+use core_model::auth::{Action, Resource};
+use core_model::config::{BetaRule, ConfigKey};
+use core_ports::BetaConfigStore;
+use core_usecase::beta::{PromoteBeta, PublishBeta, StopBeta};
+use core_usecase::cipher;
+use core_usecase::config::PublishConfig;
+
+use super::audit;
+use super::auth::{authorize, Caller};
+use super::limits::check_content_size;
+use super::{check_approval, ok, ApiError, ApiResult, AppCtx};
+
+// -------------------- Beta (gray) APIs --------------------
+#[derive(Serialize)]
+pub(crate) struct BetaConfigDto {
+    data_id: String,
+    group: String,
+    namespace: String,
+    content: String,
+    format: Option<String>,
+    beta_ips: Vec<String>,
+    beta_labels: HashMap<String, String>,
+    update_time: i64,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct PublishBetaRequest {
+    data_id: String,
+    group: String,
+    content: String,
+    namespace: Option<String>,
+    #[serde(default)]
+    format: Option<String>,
+    #[serde(default)]
+    beta_ips: Vec<String>,
+    #[serde(default)]
+    beta_labels: HashMap<String, String>,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct BetaKeyQuery {
+    data_id: String,
+    group: Option<String>,
+    namespace: Option<String>,
+}
+
+impl BetaKeyQuery {
+    fn into_key(self) -> ConfigKey {
+        ConfigKey {
+            namespace: self.namespace.unwrap_or_else(|| "public".into()),
+            group: self.group.unwrap_or_else(|| "DEFAULT_GROUP".into()),
+            data_id: self.data_id,
+        }
+    }
+}
+
+pub(crate) async fn publish_beta(
+    State(ctx): State<AppCtx>,
+    caller: Caller,
+    Json(req): Json<PublishBetaRequest>,
+) -> ApiResult<bool> {
+    let key = ConfigKey {
+        namespace: req.namespace.unwrap_or_else(|| "public".into()),
+        group: req.group,
+        data_id: req.data_id,
+    };
+    authorize(&caller, &[Resource::Config(key.clone())], Action::Write)?;
+    check_content_size(&ctx, &req.content)?;
+    check_approval(&ctx, &[&key.namespace]).await?;
+    let rule = BetaRule { ips: req.beta_ips, labels: req.beta_labels };
+    let uc = PublishBeta {
+        beta: &*ctx.stores,
+        notifier: Some(&*ctx.notifier),
+        schemas: Some(&*ctx.stores),
+        cipher: ctx.cipher.as_deref(),
+    };
+    uc.exec(key, req.content, req.format, rule, caller.actor()).await?;
+    ok(true)
+}
+
+pub(crate) async fn get_beta(
+    State(ctx): State<AppCtx>,
+    caller: Caller,
+    Query(q): Query<BetaKeyQuery>,
+) -> ApiResult<Option<BetaConfigDto>> {
+    let key = q.into_key();
+    authorize(&caller, &[Resource::Config(key.clone())], Action::Read)?;
+    let Some(b) = ctx.stores.get_beta(&key).await else {
+        return ok(None);
+    };
+    let content = cipher::open(ctx.cipher.as_deref(), &key, &b.content, b.data_key.as_deref())?;
+    ok(Some(BetaConfigDto {
+        data_id: b.key.data_id,
+        group: b.key.group,
+        namespace: b.key.namespace,
+        content,
+        format: b.format,
+        beta_ips: b.rule.ips,
+        beta_labels: b.rule.labels,
+        update_time: b.updated_at.timestamp(),
+    }))
+}
+
+pub(crate) async fn promote_beta(
+    State(ctx): State<AppCtx>,
+    caller: Caller,
+    Json(q): Json<BetaKeyQuery>,
+) -> ApiResult<bool> {
+    let key = q.into_key();
+    authorize(&caller, &[Resource::Config(key.clone())], Action::Write)?;
+    check_approval(&ctx, &[&key.namespace]).await?;
+    let uc = PromoteBeta {
+        publish: PublishConfig {
+            store: &*ctx.stores,
+            history: &*ctx.stores,
+            notifier: Some(&*ctx.notifier),
+            schemas: Some(&*ctx.stores),
+            cipher: ctx.cipher.as_deref(),
+            deps: Some(&*ctx.stores),
+            betas: Some(&*ctx.stores),
+        },
+        beta: &*ctx.stores,
+    };
+    let before = audit::config_hash(&ctx, &key).await;
+    let res = uc.exec(key.clone(), caller.actor()).await;
+    // 灰度不存在时未发生写入，不记审计
+    if !matches!(res, Ok(false)) {
+        audit::record_config(&ctx, &caller, AuditAction::ConfigPublish, &key, before, &res).await;
+    }
+    if !res? {
+        return Err(ApiError::NotFound("beta not found".into()));
+    }
+    ok(true)
+}
+
+pub(crate) async fn stop_beta(
+    State(ctx): State<AppCtx>,
+    caller: Caller,
+    Query(q): Query<BetaKeyQuery>,
+) -> ApiResult<bool> {
+    let key = q.into_key();
+    authorize(&caller, &[Resource::Config(key.clone())], Action::Write)?;
+    let uc = StopBeta { beta: &*ctx.stores, notifier: Some(&*ctx.notifier) };
+    ok(uc.exec(&key).await?)
+}
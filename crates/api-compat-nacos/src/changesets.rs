@@ -0,0 +1,247 @@
+use axum::{extract::{Path, Query, State}, response::Json};
+use serde::{Deserialize, Serialize};
+use core_model::audit::AuditAction;
+use core_model::auth::{Action, Resource};
+use core_model::changeset::Changeset;
+use core_model::config::{ConfigItem, ConfigKey};
+use core_ports::ChangesetStore;
+use core_usecase::changeset::{ApplyChangeset, ChangeOp};
+use core_usecase::config::content_md5;
+use uuid::Uuid;
+
+use super::audit;
+use super::auth::{authorize, Caller};
+use super::limits::check_content_size;
+use super::{check_approval, meta_of, ok, ApiError, ApiResult, AppCtx};
+
+// -------------------- Changesets --------------------
+#[derive(Deserialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub(crate) enum ChangeOpRequest {
+    Put {
+        namespace: Option<String>,
+        group: String,
+        data_id: String,
+        content: String,
+        #[serde(default, alias = "type")]
+        format: Option<String>,
+        #[serde(default)]
+        config_tags: Option<String>,
+        #[serde(default)]
+        desc: Option<String>,
+        #[serde(default)]
+        app_name: Option<String>,
+    },
+    Delete {
+        namespace: Option<String>,
+        group: String,
+        data_id: String,
+    },
+}
+
+#[derive(Deserialize)]
+pub(crate) struct ChangesetRequest {
+    #[serde(default)]
+    description: Option<String>,
+    ops: Vec<ChangeOpRequest>,
+}
+
+#[derive(Serialize)]
+struct ChangesetEntryDto {
+    data_id: String,
+    group: String,
+    namespace: String,
+    /// create / update / delete
+    action: &'static str,
+}
+
+#[derive(Serialize)]
+pub(crate) struct ChangesetDto {
+    id: String,
+    revision: i64,
+    description: Option<String>,
+    actor: Option<String>,
+    create_time: i64,
+    rollback_of: Option<String>,
+    rolled_back_by: Option<String>,
+    entries: Vec<ChangesetEntryDto>,
+}
+
+fn to_changeset_dto(cs: Changeset) -> ChangesetDto {
+    ChangesetDto {
+        id: cs.id,
+        revision: cs.revision,
+        description: cs.description,
+        actor: cs.actor,
+        create_time: cs.created_at.timestamp(),
+        rollback_of: cs.rollback_of,
+        rolled_back_by: cs.rolled_back_by,
+        entries: cs
+            .entries
+            .into_iter()
+            .map(|e| ChangesetEntryDto {
+                action: match (&e.before, &e.after) {
+                    (None, _) => "create",
+                    (Some(_), Some(_)) => "update",
+                    (Some(_), None) => "delete",
+                },
+                data_id: e.key.data_id,
+                group: e.key.group,
+                namespace: e.key.namespace,
+            })
+            .collect(),
+    }
+}
+
+fn key_of(namespace: Option<String>, group: String, data_id: String) -> ConfigKey {
+    ConfigKey { namespace: namespace.unwrap_or_else(|| "public".into()), group, data_id }
+}
+
+fn resources_of(cs: &Changeset) -> Vec<Resource> {
+    cs.entries.iter().map(|e| Resource::Config(e.key.clone())).collect()
+}
+
+fn apply_uc(ctx: &AppCtx) -> ApplyChangeset<'_> {
+    ApplyChangeset {
+        store: &*ctx.stores,
+        writer: &*ctx.stores,
+        changesets: &*ctx.stores,
+        notifier: Some(&*ctx.notifier),
+        schemas: Some(&*ctx.stores),
+        cipher: ctx.cipher.as_deref(),
+        deps: Some(&*ctx.stores),
+    }
+}
+
+pub(crate) async fn apply_changeset(
+    State(ctx): State<AppCtx>,
+    caller: Caller,
+    Json(req): Json<ChangesetRequest>,
+) -> ApiResult<ChangesetDto> {
+    let ops = req
+        .ops
+        .into_iter()
+        .map(|op| match op {
+            ChangeOpRequest::Put { namespace, group, data_id, content, format, config_tags, desc, app_name } => ChangeOp::Put {
+                key: key_of(namespace, group, data_id),
+                content,
+                format,
+                meta: meta_of(config_tags, desc, app_name),
+            },
+            ChangeOpRequest::Delete { namespace, group, data_id } => ChangeOp::Delete {
+                key: key_of(namespace, group, data_id),
+            },
+        })
+        .collect::<Vec<_>>();
+    let keys: Vec<&ConfigKey> = ops
+        .iter()
+        .map(|op| match op {
+            ChangeOp::Put { key, .. } | ChangeOp::Delete { key } => key,
+        })
+        .collect();
+    let resources: Vec<Resource> = keys.iter().map(|k| Resource::Config((*k).clone())).collect();
+    authorize(&caller, &resources, Action::Write)?;
+    for op in &ops {
+        if let ChangeOp::Put { content, .. } = op {
+            check_content_size(&ctx, content)?;
+        }
+    }
+    let namespaces: Vec<&str> = keys.iter().map(|k| k.namespace.as_str()).collect();
+    check_approval(&ctx, &namespaces).await?;
+    let keys: Vec<ConfigKey> = keys.into_iter().cloned().collect();
+    let id = Uuid::new_v4().to_string();
+    let res = apply_uc(&ctx).exec(id, ops, req.description, caller.actor()).await;
+    audit_changeset(&ctx, &caller, None, &keys, &res).await;
+    ok(to_changeset_dto(res?))
+}
+
+#[derive(Deserialize)]
+pub(crate) struct ListChangesetQuery {
+    limit: Option<usize>,
+}
+
+/// 只列出调用方能读取全部条目的变更集
+pub(crate) async fn list_changesets(
+    State(ctx): State<AppCtx>,
+    caller: Caller,
+    Query(q): Query<ListChangesetQuery>,
+) -> ApiResult<Vec<ChangesetDto>> {
+    let limit = q.limit.unwrap_or(50).min(500);
+    let items = ctx.stores.list_changesets(limit).await?;
+    ok(items
+        .into_iter()
+        .filter(|cs| resources_of(cs).iter().all(|r| caller.can(r, Action::Read)))
+        .map(to_changeset_dto)
+        .collect())
+}
+
+pub(crate) async fn get_changeset(
+    State(ctx): State<AppCtx>,
+    caller: Caller,
+    Path(id): Path<String>,
+) -> ApiResult<ChangesetDto> {
+    let Some(cs) = ctx.stores.get_changeset(&id).await else {
+        return Err(ApiError::NotFound("changeset not found".into()));
+    };
+    authorize(&caller, &resources_of(&cs), Action::Read)?;
+    ok(to_changeset_dto(cs))
+}
+
+#[derive(Deserialize)]
+pub(crate) struct RollbackChangesetQuery {
+    /// 为 true 时忽略变更集之后的修改，强制恢复
+    #[serde(default)]
+    force: bool,
+}
+
+pub(crate) async fn rollback_changeset(
+    State(ctx): State<AppCtx>,
+    caller: Caller,
+    Path(id): Path<String>,
+    Query(q): Query<RollbackChangesetQuery>,
+) -> ApiResult<ChangesetDto> {
+    let mut keys = Vec::new();
+    if let Some(cs) = ctx.stores.get_changeset(&id).await {
+        authorize(&caller, &resources_of(&cs), Action::Write)?;
+        let namespaces: Vec<&str> = cs.entries.iter().map(|e| e.key.namespace.as_str()).collect();
+        check_approval(&ctx, &namespaces).await?;
+        keys = cs.entries.into_iter().map(|e| e.key).collect();
+    }
+    let new_id = Uuid::new_v4().to_string();
+    let res = match apply_uc(&ctx).rollback(&id, new_id, q.force, caller.actor()).await {
+        Ok(Some(cs)) => Ok(cs),
+        Ok(None) => return Err(ApiError::NotFound("changeset not found".into())),
+        Err(e) => Err(e),
+    };
+    audit_changeset(&ctx, &caller, Some(AuditAction::ConfigRollback), &keys, &res).await;
+    ok(to_changeset_dto(res?))
+}
+
+/// 变更集逐条审计，前后摘要取自变更集快照；失败时整体未生效，按涉及的配置各记一条失败。
+/// action 为 None 时按条目区分发布与删除
+async fn audit_changeset(
+    ctx: &AppCtx,
+    caller: &Caller,
+    action: Option<AuditAction>,
+    keys: &[ConfigKey],
+    res: &anyhow::Result<Changeset>,
+) {
+    let hash = |item: &Option<ConfigItem>| item.as_ref().map(|c| content_md5(&c.content));
+    match res {
+        Ok(cs) => {
+            for e in &cs.entries {
+                let op = action.unwrap_or(if e.after.is_some() { AuditAction::ConfigPublish } else { AuditAction::ConfigDelete });
+                let outcome: Result<(), String> = Ok(());
+                audit::record(ctx, caller, op, audit::config_resource(&e.key), (hash(&e.before), hash(&e.after)), &outcome).await;
+            }
+        }
+        Err(e) => {
+            let outcome: Result<(), String> = Err(e.to_string());
+            for key in keys {
+                let current = audit::config_hash(ctx, key).await;
+                let op = action.unwrap_or(AuditAction::ConfigPublish);
+                audit::record(ctx, caller, op, audit::config_resource(key), (current.clone(), current), &outcome).await;
+            }
+        }
+    }
+}
@@ -0,0 +1,14 @@
+use axum::http::HeaderMap;
+
+pub(crate) use adapters_notify_sse::client::{app_name, client_info, client_ip};
+
+/// 操作人：X-Nacos-User 头，缺省为 admin
+pub(crate) fn actor(headers: &HeaderMap) -> String {
+    headers
+        .get("x-nacos-user")
+        .and_then(|v| v.to_str().ok())
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .unwrap_or("admin")
+        .to_string()
+}
@@ -0,0 +1,203 @@
+use std::fmt;
+use std::time::Duration;
+use axum::{
+    http::{header, StatusCode},
+    response::{IntoResponse, Json, Response},
+};
+use chrono::Utc;
+use adapters_notify_webhook::WebhookError;
+use core_ports::StoreError;
+use core_usecase::auth::AuthError;
+use core_usecase::changeset::{ChangesetConflict, ChangesetError};
+use core_usecase::review::ReviewError;
+use core_usecase::schedule::ScheduleError;
+use core_usecase::schema::SchemaValidationError;
+use core_usecase::template::RenderError;
+
+use super::ApiResponse;
+
+/// 接口失败的分类：决定 HTTP 状态码与响应体中的 `error` 码，`message` 仅供展示
+#[derive(Debug)]
+pub enum ApiError {
+    /// 参数、内容、Schema 或模板校验不通过
+    Validation(String),
+    NotFound(String),
+    /// 重名、状态不符或内容已被他人修改
+    Conflict(String),
+    /// 令牌缺失、过期或签名无效
+    Unauthorized(String),
+    /// 凭据错误、权限不足或命名空间要求审批
+    Forbidden(String),
+    /// 超出限流预算，retry_after 为建议等待的时长
+    QuotaExceeded { message: String, retry_after: Option<Duration> },
+    /// 配置内容或实例元数据超出大小上限
+    PayloadTooLarge(String),
+    Internal(String),
+}
+
+impl ApiError {
+    pub fn status(&self) -> StatusCode {
+        match self {
+            ApiError::Validation(_) => StatusCode::BAD_REQUEST,
+            ApiError::NotFound(_) => StatusCode::NOT_FOUND,
+            ApiError::Conflict(_) => StatusCode::CONFLICT,
+            ApiError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+            ApiError::Forbidden(_) => StatusCode::FORBIDDEN,
+            ApiError::QuotaExceeded { .. } => StatusCode::TOO_MANY_REQUESTS,
+            ApiError::PayloadTooLarge(_) => StatusCode::PAYLOAD_TOO_LARGE,
+            ApiError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    /// 稳定的错误码，客户端与前端据此分支，不随 message 文案变化
+    pub fn code(&self) -> &'static str {
+        match self {
+            ApiError::Validation(_) => "validation_failed",
+            ApiError::NotFound(_) => "not_found",
+            ApiError::Conflict(_) => "conflict",
+            ApiError::Unauthorized(_) => "unauthorized",
+            ApiError::Forbidden(_) => "forbidden",
+            ApiError::QuotaExceeded { .. } => "quota_exceeded",
+            ApiError::PayloadTooLarge(_) => "payload_too_large",
+            ApiError::Internal(_) => "internal",
+        }
+    }
+
+    fn message(&self) -> &str {
+        match self {
+            ApiError::Validation(m)
+            | ApiError::NotFound(m)
+            | ApiError::Conflict(m)
+            | ApiError::Unauthorized(m)
+            | ApiError::Forbidden(m)
+            | ApiError::PayloadTooLarge(m)
+            | ApiError::Internal(m) => m,
+            ApiError::QuotaExceeded { message, .. } => message,
+        }
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message())
+    }
+}
+
+impl std::error::Error for ApiError {}
+
+/// 响应体沿用 ApiResponse，code 与 HTTP 状态码一致；限流时另带 Retry-After（秒）
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let status = self.status();
+        if status.is_server_error() {
+            tracing::error!("request failed: {}", self);
+        }
+        let retry_after = match &self {
+            ApiError::QuotaExceeded { retry_after: Some(wait), .. } => Some(wait.as_secs_f64().ceil().max(1.0) as u64),
+            _ => None,
+        };
+        let body: ApiResponse<()> = ApiResponse {
+            code: status.as_u16() as i32,
+            error: Some(self.code()),
+            message: self.to_string(),
+            data: None,
+            timestamp: Utc::now().timestamp(),
+        };
+        let mut response = (status, Json(body)).into_response();
+        if let Some(secs) = retry_after {
+            response.headers_mut().insert(header::RETRY_AFTER, secs.into());
+        }
+        response
+    }
+}
+
+impl From<StoreError> for ApiError {
+    fn from(e: StoreError) -> Self {
+        match e {
+            StoreError::NotFound(m) => ApiError::NotFound(m),
+            StoreError::Conflict(m) => ApiError::Conflict(m),
+            StoreError::Stale(_) => ApiError::Conflict(e.to_string()),
+            StoreError::Invalid(m) => ApiError::Validation(m),
+            StoreError::Backend(_) => ApiError::Internal(e.to_string()),
+        }
+    }
+}
+
+impl From<AuthError> for ApiError {
+    fn from(e: AuthError) -> Self {
+        let kind = match &e {
+            // 同 Nacos，凭据错误按 403 返回
+            AuthError::InvalidCredentials | AuthError::Forbidden(_) => ApiError::Forbidden,
+            AuthError::Unauthorized(_) => ApiError::Unauthorized,
+            AuthError::UserNotFound | AuthError::AccessKeyNotFound => ApiError::NotFound,
+            AuthError::UserExists => ApiError::Conflict,
+            AuthError::Invalid(_) => ApiError::Validation,
+        };
+        kind(e.to_string())
+    }
+}
+
+impl From<WebhookError> for ApiError {
+    fn from(e: WebhookError) -> Self {
+        let kind = match &e {
+            WebhookError::NotFound => ApiError::NotFound,
+            WebhookError::InvalidState(_) => ApiError::Conflict,
+            WebhookError::Invalid(_) => ApiError::Validation,
+        };
+        kind(e.to_string())
+    }
+}
+
+impl From<ReviewError> for ApiError {
+    fn from(e: ReviewError) -> Self {
+        let kind = match &e {
+            ReviewError::NotFound => ApiError::NotFound,
+            ReviewError::Forbidden(_) => ApiError::Forbidden,
+            ReviewError::InvalidState(_) | ReviewError::Stale => ApiError::Conflict,
+        };
+        kind(e.to_string())
+    }
+}
+
+impl From<ScheduleError> for ApiError {
+    fn from(e: ScheduleError) -> Self {
+        let kind = match &e {
+            ScheduleError::NotFound => ApiError::NotFound,
+            ScheduleError::InvalidState(_) => ApiError::Conflict,
+            ScheduleError::Invalid(_) => ApiError::Validation,
+        };
+        kind(e.to_string())
+    }
+}
+
+fn downcast<E>(e: anyhow::Error) -> Result<ApiError, anyhow::Error>
+where
+    E: Into<ApiError> + fmt::Display + fmt::Debug + Send + Sync + 'static,
+{
+    e.downcast::<E>().map(Into::into)
+}
+
+/// 用例层返回 anyhow，按其中携带的领域错误归类；无法识别的一律视为内部错误
+impl From<anyhow::Error> for ApiError {
+    fn from(e: anyhow::Error) -> Self {
+        let e = match downcast::<StoreError>(e)
+            .or_else(downcast::<AuthError>)
+            .or_else(downcast::<ReviewError>)
+            .or_else(downcast::<ScheduleError>)
+            .or_else(downcast::<WebhookError>)
+        {
+            Ok(api) => return api,
+            Err(e) => e,
+        };
+        if let Some(RenderError::Forbidden(_)) = e.downcast_ref::<RenderError>() {
+            return ApiError::Forbidden(e.to_string());
+        }
+        if e.is::<ChangesetConflict>() {
+            ApiError::Conflict(e.to_string())
+        } else if e.is::<ChangesetError>() || e.is::<SchemaValidationError>() || e.is::<RenderError>() {
+            ApiError::Validation(e.to_string())
+        } else {
+            ApiError::Internal(e.to_string())
+        }
+    }
+}
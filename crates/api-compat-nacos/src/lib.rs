@@ -1,47 +1,104 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::net::SocketAddr;
 use std::sync::Arc;
-use axum::{routing::{get, post, delete, put}, Router, response::{Json, sse::{Sse, Event, KeepAlive}}, extract::{State, Query, Path}};
+use axum::{routing::{get, post, delete, put}, Router, response::Json, extract::{ConnectInfo, State, Query, Path}};
 use axum::http::HeaderMap;
-use axum::http::StatusCode;
 use serde::{Serialize, Deserialize};
 use chrono::Utc;
 use adapters_storage_memory::MemStores;
 use adapters_notify_sse::SseHub;
-use core_model::config::{ConfigItem as DomainConfigItem, ConfigKey};
+use adapters_notify_webhook::WebhookNotifier;
+use core_model::audit::AuditAction;
+use core_model::auth::{Action, Resource};
+use core_model::config::{ConfigFilter, ConfigItem as DomainConfigItem, ConfigKey, ConfigMeta};
+use core_model::event::{InstanceChangeEvent, InstanceOp};
 use core_model::instance::{Instance as DomainInstance, InstanceId, ServiceName};
 use core_model::namespace::Namespace as DomainNamespace;
-use core_ports::{ConfigStore, InstanceStore, NamespaceStore, Notifier};
-use core_usecase::config::PublishConfig;
+use core_model::search::{ContentQuery, LineMatch};
+use core_model::template::RefTarget;
+use core_ports::{AuditStore, ConfigCipher, ConfigDependencyStore, ConfigStore, InstanceStore, NamespaceStore, Notifier, ScheduledChangeStore};
+use core_usecase::beta::resolve_config;
+use core_usecase::cipher;
+use core_usecase::config::{content_md5, deleted_event, PublishConfig};
+use core_usecase::template::{notify_dependents, RenderConfig};
 use uuid::Uuid;
-use futures::Stream;
-use async_stream::stream;
-use tokio::sync::broadcast;
+
+pub use auth::{event_auth, AuthCtx, Principal};
+pub use error::ApiError;
+pub use limits::{rate_limited, Budget, Limits, RateLimiter};
+use auth::{admin_only, authorize, Caller};
+use limits::{check_content_size, check_metadata_size};
+
+mod audit;
+mod auth;
+mod beta;
+mod changesets;
+mod client;
+mod error;
+mod limits;
+mod listeners;
+mod reviews;
+mod schedules;
+mod schemas;
+mod transfer;
+mod webhooks;
 
 #[derive(Serialize)]
 struct ApiResponse<T> {
     code: i32,
+    /// 失败时的稳定错误码，见 ApiError::code
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<&'static str>,
     message: String,
     data: Option<T>,
     timestamp: i64,
 }
 
-fn ok<T: Serialize>(data: T) -> Json<ApiResponse<T>> {
-    Json(ApiResponse {
+/// 成功返回 HTTP 200 与数据，失败由 ApiError 决定状态码
+type ApiResult<T> = Result<Json<ApiResponse<T>>, ApiError>;
+
+fn ok<T: Serialize>(data: T) -> ApiResult<T> {
+    Ok(Json(ApiResponse {
         code: 200,
+        error: None,
         message: "success".to_string(),
         data: Some(data),
         timestamp: Utc::now().timestamp(),
-    })
+    }))
+}
+
+// 开启审批的命名空间只能经变更请求发布，直接写入返回 403
+async fn check_approval(ctx: &AppCtx, namespaces: &[&str]) -> Result<(), ApiError> {
+    for &ns in namespaces {
+        if NamespaceStore::get(&*ctx.stores, ns).await.is_some_and(|n| n.require_approval) {
+            return Err(ApiError::Forbidden(format!("namespace {} requires approval, submit a change request instead", ns)));
+        }
+    }
+    Ok(())
 }
 
-async fn health() -> Json<ApiResponse<&'static str>> {
+async fn health() -> ApiResult<&'static str> {
     ok("UP")
 }
 
 #[derive(Clone)]
 pub struct AppCtx {
     pub stores: Arc<MemStores>,
-    pub notifier: Arc<SseHub>,
+    /// 组合通知器，用例通过它向所有推送通道发事件
+    pub notifier: Arc<dyn Notifier>,
+    /// 长轮询直接订阅事件中心
+    pub events: Arc<SseHub>,
+    /// cipher-* 配置的加密插件；未配置时拒绝发布此类配置
+    pub cipher: Option<Arc<dyn ConfigCipher>>,
+    /// 出站 Webhook 的测试投递与死信重投
+    pub webhooks: Arc<WebhookNotifier>,
+    pub auth: AuthCtx,
+    /// 写操作审计日志
+    pub audit: Arc<dyn AuditStore>,
+    /// 定时发布任务，可与调度器共用文件存储
+    pub schedules: Arc<dyn ScheduledChangeStore>,
+    /// 限流与内容大小上限
+    pub limiter: Arc<RateLimiter>,
 }
 
 #[derive(Deserialize)]
@@ -50,8 +107,27 @@ struct PublishConfigRequest {
     group: String,
     content: String,
     namespace: Option<String>,
-    #[serde(default)]
+    #[serde(default, alias = "type", alias = "config_type")]
     format: Option<String>,
+    #[serde(default)]
+    config_tags: Option<String>,
+    #[serde(default)]
+    desc: Option<String>,
+    #[serde(default)]
+    app_name: Option<String>,
+}
+
+// Nacos 风格的逗号分隔标签
+fn parse_tags(raw: &str) -> Vec<String> {
+    raw.split(',').map(|t| t.trim().to_string()).filter(|t| !t.is_empty()).collect()
+}
+
+fn meta_of(config_tags: Option<String>, desc: Option<String>, app_name: Option<String>) -> ConfigMeta {
+    ConfigMeta {
+        tags: config_tags.as_deref().map(parse_tags),
+        desc,
+        app_name,
+    }
 }
 
 #[derive(Deserialize)]
@@ -59,6 +135,10 @@ struct GetConfigQuery {
     data_id: String,
     group: Option<String>,
     namespace: Option<String>,
+    labels: Option<String>,
+    /// 为 true 时解析内容中的模板引用
+    #[serde(default)]
+    render: bool,
 }
 
 #[derive(Deserialize)]
@@ -67,6 +147,10 @@ struct ListConfigQuery {
     page: Option<u32>,
     size: Option<u32>,
     search: Option<String>,
+    group: Option<String>,
+    app_name: Option<String>,
+    tags: Option<String>,
+    format: Option<String>,
 }
 
 #[derive(Serialize)]
@@ -85,49 +169,97 @@ struct ConfigItemDto {
     content: String,
     namespace: String,
     update_time: i64,
+    beta: bool,
+    format: Option<String>,
+    config_tags: String,
+    desc: Option<String>,
+    app_name: Option<String>,
+    /// 非空时 content 为密文
+    data_key: Option<String>,
+    /// 存储内容的 MD5，长轮询监听时回传
+    md5: String,
 }
 
 fn to_config_dto(c: DomainConfigItem) -> ConfigItemDto {
     ConfigItemDto {
+        md5: content_md5(&c.content),
         data_id: c.key.data_id,
         group: c.key.group,
         content: c.content,
         namespace: c.key.namespace,
         update_time: c.updated_at.timestamp(),
+        beta: false,
+        format: c.format,
+        config_tags: c.tags.join(","),
+        desc: c.desc,
+        app_name: c.app_name,
+        data_key: c.data_key,
     }
 }
 
 async fn publish_config(
     State(ctx): State<AppCtx>,
+    caller: Caller,
     Json(req): Json<PublishConfigRequest>,
-) -> Json<ApiResponse<bool>> {
+) -> ApiResult<bool> {
     let key = ConfigKey {
         namespace: req.namespace.unwrap_or_else(|| "public".into()),
         group: req.group,
         data_id: req.data_id,
     };
+    authorize(&caller, &[Resource::Config(key.clone())], Action::Write)?;
+    check_content_size(&ctx, &req.content)?;
+    check_approval(&ctx, &[&key.namespace]).await?;
     let uc = PublishConfig {
         store: &*ctx.stores,
         history: &*ctx.stores,
         notifier: Some(&*ctx.notifier),
+        schemas: Some(&*ctx.stores),
+        cipher: ctx.cipher.as_deref(),
+        deps: Some(&*ctx.stores),
+        betas: Some(&*ctx.stores),
     };
-    match uc.exec(key, req.content, req.format, Some("admin".into())).await {
-        Ok(_) => ok(true),
-        Err(e) => Json(ApiResponse { code: 500, message: e.to_string(), data: None, timestamp: Utc::now().timestamp() }),
-    }
+    let meta = meta_of(req.config_tags, req.desc, req.app_name);
+    let before = audit::config_hash(&ctx, &key).await;
+    let res = uc.exec(key.clone(), req.content, req.format, meta, caller.actor()).await;
+    audit::record_config(&ctx, &caller, AuditAction::ConfigPublish, &key, before, &res).await;
+    res?;
+    ok(true)
 }
 
 async fn get_config(
     State(ctx): State<AppCtx>,
+    caller: Caller,
     Query(q): Query<GetConfigQuery>,
-) -> Json<ApiResponse<Option<ConfigItemDto>>> {
+    peer: Option<ConnectInfo<SocketAddr>>,
+    headers: HeaderMap,
+) -> ApiResult<ConfigItemDto> {
     let key = ConfigKey {
         namespace: q.namespace.unwrap_or_else(|| "public".into()),
         group: q.group.unwrap_or_else(|| "DEFAULT_GROUP".into()),
         data_id: q.data_id,
     };
-    let item = ctx.stores.get(&key).await.map(to_config_dto);
-    ok(item)
+    authorize(&caller, &[Resource::Config(key.clone())], Action::Read)?;
+    // 命中灰度规则的客户端拿到灰度版本
+    let client = client::client_info(&headers, peer.map(|c| c.0), q.labels.as_deref());
+    // 同 Nacos，配置不存在返回 404
+    let Some((mut item, beta)) = resolve_config(&*ctx.stores, &*ctx.stores, &key, &client).await else {
+        return Err(ApiError::NotFound("config data not exist".into()));
+    };
+    let md5 = content_md5(&item.content);
+    // 读取单条配置时解密，列表、历史与导出保持密文
+    item.content = cipher::open(ctx.cipher.as_deref(), &key, &item.content, item.data_key.as_deref())?;
+    item.data_key = None;
+    if q.render {
+        let uc = RenderConfig {
+            store: &*ctx.stores,
+            namespaces: &*ctx.stores,
+            cipher: ctx.cipher.as_deref(),
+            grants: caller.grants(),
+        };
+        item.content = uc.exec(&item).await?;
+    }
+    ok(ConfigItemDto { beta, md5, ..to_config_dto(item) })
 }
 
 #[derive(Deserialize)]
@@ -139,38 +271,105 @@ struct DeleteConfigQuery {
 
 async fn delete_config(
     State(ctx): State<AppCtx>,
+    caller: Caller,
     Query(q): Query<DeleteConfigQuery>,
-) -> Json<ApiResponse<bool>> {
+) -> ApiResult<bool> {
     let key = ConfigKey {
         namespace: q.namespace.unwrap_or_else(|| "public".into()),
         group: q.group.unwrap_or_else(|| "DEFAULT_GROUP".into()),
         data_id: q.data_id,
     };
-    match ConfigStore::delete(&*ctx.stores, &key).await {
-        Ok(v) => ok(v),
-        Err(e) => Json(ApiResponse { code: 500, message: e.to_string(), data: None, timestamp: Utc::now().timestamp() }),
+    authorize(&caller, &[Resource::Config(key.clone())], Action::Write)?;
+    check_approval(&ctx, &[&key.namespace]).await?;
+    let before = audit::config_hash(&ctx, &key).await;
+    let res = ConfigStore::delete(&*ctx.stores, &key).await;
+    audit::record_config(&ctx, &caller, AuditAction::ConfigDelete, &key, before, &res).await;
+    let deleted = res?;
+    ctx.stores.set_refs(&key, vec![]).await;
+    if deleted {
+        ctx.notifier.notify_config_event(&deleted_event(key.clone(), caller.actor())).await;
     }
+    // 引用方渲染结果随之失效
+    notify_dependents(&*ctx.stores, &*ctx.notifier, RefTarget::Config(key)).await;
+    ok(deleted)
 }
 
 async fn list_configs(
     State(ctx): State<AppCtx>,
+    caller: Caller,
     Query(q): Query<ListConfigQuery>,
-) -> Json<ApiResponse<PagedConfigResponse>> {
+) -> ApiResult<PagedConfigResponse> {
     let page = q.page.unwrap_or(1);
     let size = q.size.unwrap_or(10);
-    match ConfigStore::list(&*ctx.stores, &q.namespace, page, size, q.search.as_deref()).await {
-        Ok((total, items)) => {
-            let pages = if size == 0 { 0 } else { (total as u32 + size - 1) / size };
-            ok(PagedConfigResponse {
-                total_count: total,
-                page_number: page,
-                page_size: size,
-                pages,
-                data: items.into_iter().map(to_config_dto).collect(),
-            })
-        }
-        Err(e) => Json(ApiResponse { code: 500, message: e.to_string(), data: None, timestamp: Utc::now().timestamp() }),
+    let non_empty = |v: Option<String>| v.filter(|s| !s.trim().is_empty());
+    let filter = ConfigFilter {
+        data_id: non_empty(q.search),
+        group: non_empty(q.group),
+        app_name: non_empty(q.app_name),
+        tags: q.tags.as_deref().map(parse_tags).unwrap_or_default(),
+        format: non_empty(q.format),
+    };
+    // 非管理员只能看到有读权限的配置，先全量过滤再分页
+    let listed = if caller.is_admin() {
+        ConfigStore::list(&*ctx.stores, &q.namespace, page, size, &filter).await
+    } else {
+        ConfigStore::list(&*ctx.stores, &q.namespace, 1, u32::MAX, &filter).await.map(|(_, mut items)| {
+            items.retain(|c| caller.can(&Resource::Config(c.key.clone()), Action::Read));
+            let total = items.len();
+            let skip = (page.max(1) as usize - 1).saturating_mul(size as usize);
+            (total, items.into_iter().skip(skip).take(size as usize).collect())
+        })
+    };
+    let (total, items) = listed?;
+    let pages = if size == 0 { 0 } else { (total as u32 + size - 1) / size };
+    ok(PagedConfigResponse {
+        total_count: total,
+        page_number: page,
+        page_size: size,
+        pages,
+        data: items.into_iter().map(to_config_dto).collect(),
+    })
+}
+
+#[derive(Deserialize)]
+struct SearchConfigQuery {
+    namespace: String,
+    q: String,
+    #[serde(default)]
+    regex: bool,
+    #[serde(default)]
+    ignore_case: bool,
+    limit: Option<usize>,
+}
+
+#[derive(Serialize)]
+struct SearchHitDto {
+    data_id: String,
+    group: String,
+    namespace: String,
+    update_time: i64,
+    matches: Vec<LineMatch>,
+}
+
+async fn search_configs(
+    State(ctx): State<AppCtx>,
+    caller: Caller,
+    Query(q): Query<SearchConfigQuery>,
+) -> ApiResult<Vec<SearchHitDto>> {
+    if q.q.is_empty() {
+        return Err(ApiError::Validation("q is required".into()));
     }
+    let query = ContentQuery { pattern: q.q, regex: q.regex, ignore_case: q.ignore_case };
+    let limit = q.limit.unwrap_or(50).min(500);
+    // 正则不合法时存储层返回 StoreError::Invalid，即 400
+    let hits = ConfigStore::search_content(&*ctx.stores, &q.namespace, &query, limit).await?;
+    ok(hits.into_iter().filter(|h| caller.can(&Resource::Config(h.key.clone()), Action::Read)).map(|h| SearchHitDto {
+        data_id: h.key.data_id,
+        group: h.key.group,
+        namespace: h.key.namespace,
+        update_time: h.updated_at.timestamp(),
+        matches: h.matches,
+    }).collect())
 }
 
 #[derive(Deserialize)]
@@ -186,26 +385,26 @@ struct HistoryItemDto {
     deleted: bool,
     content: String,
     updated_at: i64,
+    data_key: Option<String>,
 }
 
 async fn list_history(
     State(ctx): State<AppCtx>,
+    caller: Caller,
     Query(q): Query<HistoryQuery>,
-) -> Json<ApiResponse<Vec<HistoryItemDto>>> {
+) -> ApiResult<Vec<HistoryItemDto>> {
     let key = ConfigKey { namespace: q.namespace, group: q.group, data_id: q.data_id };
-    match core_ports::ConfigHistoryStore::list(&*ctx.stores, &key).await {
-        Ok(mut items) => {
-            items.sort_by(|a, b| b.version_ts.cmp(&a.version_ts));
-            let data = items.into_iter().map(|h| HistoryItemDto {
-                version: h.version_ts,
-                deleted: h.deleted,
-                content: h.content,
-                updated_at: h.updated_at.timestamp(),
-            }).collect();
-            ok(data)
-        }
-        Err(e) => Json(ApiResponse { code: 500, message: e.to_string(), data: None, timestamp: Utc::now().timestamp() }),
-    }
+    authorize(&caller, &[Resource::Config(key.clone())], Action::Read)?;
+    let mut items = core_ports::ConfigHistoryStore::list(&*ctx.stores, &key).await?;
+    items.sort_by(|a, b| b.version_ts.cmp(&a.version_ts));
+    let data = items.into_iter().map(|h| HistoryItemDto {
+        version: h.version_ts,
+        deleted: h.deleted,
+        content: h.content,
+        updated_at: h.updated_at.timestamp(),
+        data_key: h.data_key,
+    }).collect();
+    ok(data)
 }
 
 #[derive(Deserialize)]
@@ -218,73 +417,31 @@ struct RollbackRequest {
 
 async fn rollback_config(
     State(ctx): State<AppCtx>,
+    caller: Caller,
     Json(body): Json<RollbackRequest>,
-) -> Json<ApiResponse<bool>> {
+) -> ApiResult<bool> {
     let key = ConfigKey { namespace: body.namespace, group: body.group, data_id: body.data_id };
-    match core_ports::ConfigHistoryStore::list(&*ctx.stores, &key).await {
-        Ok(items) => {
-            if let Some(hist) = items.into_iter().find(|h| h.version_ts == body.version) {
-                let uc = PublishConfig {
-                    store: &*ctx.stores,
-                    history: &*ctx.stores,
-                    notifier: Some(&*ctx.notifier),
-                };
-                match uc.exec(key, hist.content, hist.format, Some("admin".into())).await {
-                    Ok(_) => ok(true),
-                    Err(e) => Json(ApiResponse { code: 500, message: e.to_string(), data: None, timestamp: Utc::now().timestamp() }),
-                }
-            } else {
-                Json(ApiResponse { code: 404, message: "version not found".into(), data: None, timestamp: Utc::now().timestamp() })
-            }
-        }
-        Err(e) => Json(ApiResponse { code: 500, message: e.to_string(), data: None, timestamp: Utc::now().timestamp() }),
-    }
-}
-
-#[derive(Deserialize)]
-struct ExportQuery {
-    namespace: String,
-}
-
-async fn export_configs(
-    State(ctx): State<AppCtx>,
-    Query(q): Query<ExportQuery>,
-) -> Json<ApiResponse<Vec<ConfigItemDto>>> {
-    // 直接遍历内存存储
-    let items: Vec<ConfigItemDto> = ctx.stores
-        .configs
-        .iter()
-        .filter(|e| e.value().key.namespace == q.namespace)
-        .map(|e| to_config_dto(e.value().clone()))
-        .collect();
-    ok(items)
-}
-
-#[derive(Deserialize)]
-struct ImportItem {
-    data_id: String,
-    group: String,
-    namespace: String,
-    content: String,
-    #[serde(default)]
-    format: Option<String>,
-}
-
-async fn import_configs(
-    State(ctx): State<AppCtx>,
-    Json(items): Json<Vec<ImportItem>>,
-) -> Json<ApiResponse<bool>> {
-    for it in items {
-        let uc = PublishConfig {
-            store: &*ctx.stores,
-            history: &*ctx.stores,
-            notifier: Some(&*ctx.notifier),
-        };
-        let key = ConfigKey { namespace: it.namespace, group: it.group, data_id: it.data_id };
-        if let Err(e) = uc.exec(key, it.content, it.format, Some("admin".into())).await {
-            return Json(ApiResponse { code: 500, message: e.to_string(), data: None, timestamp: Utc::now().timestamp() });
-        }
-    }
+    authorize(&caller, &[Resource::Config(key.clone())], Action::Write)?;
+    check_approval(&ctx, &[&key.namespace]).await?;
+    let items = core_ports::ConfigHistoryStore::list(&*ctx.stores, &key).await?;
+    let Some(hist) = items.into_iter().find(|h| h.version_ts == body.version) else {
+        return Err(ApiError::NotFound("version not found".into()));
+    };
+    let uc = PublishConfig {
+        store: &*ctx.stores,
+        history: &*ctx.stores,
+        notifier: Some(&*ctx.notifier),
+        schemas: Some(&*ctx.stores),
+        cipher: ctx.cipher.as_deref(),
+        deps: Some(&*ctx.stores),
+        betas: Some(&*ctx.stores),
+    };
+    // 历史中保存的是密文，回滚前先还原
+    let content = cipher::open(uc.cipher, &key, &hist.content, hist.data_key.as_deref())?;
+    let before = audit::config_hash(&ctx, &key).await;
+    let res = uc.rollback(key.clone(), content, hist.format, ConfigMeta::default(), caller.actor()).await;
+    audit::record_config(&ctx, &caller, AuditAction::ConfigRollback, &key, before, &res).await;
+    res?;
     ok(true)
 }
 
@@ -293,17 +450,59 @@ pub fn routes() -> Router {
         .route("/health", get(health))
 }
 
-pub fn routes_with_mem(ctx: Arc<MemStores>) -> Router {
-    let notifier = Arc::new(SseHub::new());
-    let app_ctx = AppCtx { stores: ctx.clone(), notifier: notifier.clone() };
-    Router::new()
+#[allow(clippy::too_many_arguments)]
+pub fn routes_with_mem(
+    ctx: Arc<MemStores>,
+    notifier: Arc<dyn Notifier>,
+    events: Arc<SseHub>,
+    cipher: Option<Arc<dyn ConfigCipher>>,
+    webhooks: Arc<WebhookNotifier>,
+    auth: AuthCtx,
+    audit: Arc<dyn AuditStore>,
+    schedules: Arc<dyn ScheduledChangeStore>,
+    limiter: Arc<RateLimiter>,
+) -> Router {
+    let app_ctx = AppCtx { stores: ctx.clone(), notifier, events, cipher, webhooks, auth, audit, schedules, limiter };
+    let public = Router::new()
         .route("/health", get(health))
+        .route("/nacos/v1/auth/login", post(auth::login))
+        .route("/nacos/v1/auth/users/login", post(auth::login))
+        .with_state(app_ctx.clone());
+    let api = Router::new()
         .route("/nacos/v1/cs/configs", post(publish_config).get(get_config).delete(delete_config))
         .route("/nacos/v1/cs/configs/list", get(list_configs))
+        .route("/nacos/v1/cs/configs/search", get(search_configs))
         .route("/nacos/v1/cs/configs/history", get(list_history))
         .route("/nacos/v1/cs/configs/history/rollback", post(rollback_config))
-        .route("/nacos/v1/cs/configs/export", get(export_configs))
-        .route("/nacos/v1/cs/configs/import", post(import_configs))
+        .route("/nacos/v1/cs/configs/export", get(transfer::export_configs))
+        .route("/nacos/v1/cs/configs/export/zip", get(transfer::export_zip))
+        .route("/nacos/v1/cs/configs/import", post(transfer::import_configs))
+        .route("/nacos/v1/cs/configs/import/zip", post(transfer::import_zip))
+        .route("/nacos/v1/cs/configs/clone", post(transfer::clone_configs))
+        // listeners
+        .route("/nacos/v1/cs/configs/listener", post(listeners::long_poll).get(listeners::config_listeners))
+        .route("/nacos/v1/cs/listener", get(listeners::listeners_by_ip))
+        // beta
+        .route("/nacos/v1/cs/configs/beta", post(beta::publish_beta).get(beta::get_beta).delete(beta::stop_beta))
+        .route("/nacos/v1/cs/configs/beta/promote", post(beta::promote_beta))
+        // changesets
+        .route("/nacos/v1/cs/changesets", post(changesets::apply_changeset).get(changesets::list_changesets))
+        .route("/nacos/v1/cs/changesets/:id", get(changesets::get_changeset))
+        .route("/nacos/v1/cs/changesets/:id/rollback", post(changesets::rollback_changeset))
+        // scheduled publishes
+        .route("/nacos/v1/cs/schedules", post(schedules::create_schedule).get(schedules::list_schedules))
+        .route("/nacos/v1/cs/schedules/:id", get(schedules::get_schedule))
+        .route("/nacos/v1/cs/schedules/:id/cancel", post(schedules::cancel_schedule))
+        // schemas
+        .route("/nacos/v1/cs/schemas", post(schemas::create_schema).get(schemas::list_schemas))
+        .route("/nacos/v1/cs/schemas/validate", post(schemas::validate_config))
+        .route("/nacos/v1/cs/schemas/:id", get(schemas::get_schema).put(schemas::update_schema).delete(schemas::delete_schema))
+        // change requests
+        .route("/nacos/v1/cs/change-requests", post(reviews::submit_change_request).get(reviews::list_change_requests))
+        .route("/nacos/v1/cs/change-requests/:id", get(reviews::get_change_request))
+        .route("/nacos/v1/cs/change-requests/:id/approve", post(reviews::approve_change_request))
+        .route("/nacos/v1/cs/change-requests/:id/reject", post(reviews::reject_change_request))
+        .route("/nacos/v1/cs/change-requests/:id/apply", post(reviews::apply_change_request))
         // instance
         .route("/nacos/v1/ns/instance", post(register_instance))
         .route("/nacos/v1/ns/instance/beat", post(beat_instance))
@@ -314,9 +513,35 @@ pub fn routes_with_mem(ctx: Arc<MemStores>) -> Router {
         // namespaces console
         .route("/nacos/v1/console/namespaces", post(create_namespace).get(list_namespaces))
         .route("/nacos/v1/console/namespaces/:namespace", put(update_namespace).delete(delete_namespace))
-        // sse
-        .route("/nacos/v1/events/stream", get(stream_events))
+        // webhooks
+        .route("/nacos/v1/webhooks", post(webhooks::create_webhook).get(webhooks::list_webhooks))
+        .route("/nacos/v1/webhooks/deliveries", get(webhooks::list_deliveries))
+        .route("/nacos/v1/webhooks/deliveries/:id", get(webhooks::get_delivery))
+        .route("/nacos/v1/webhooks/deliveries/:id/retry", post(webhooks::retry_delivery))
+        .route("/nacos/v1/webhooks/:id", get(webhooks::get_webhook).put(webhooks::update_webhook).delete(webhooks::delete_webhook))
+        .route("/nacos/v1/webhooks/:id/test", post(webhooks::test_webhook))
+        // users
+        .route("/nacos/v1/auth/users", post(auth::create_user).get(auth::list_users))
+        .route("/nacos/v1/auth/users/:username", put(auth::change_password).delete(auth::delete_user))
+        // roles & permissions
+        .route("/nacos/v1/auth/roles", post(auth::bind_role).get(auth::list_roles).delete(auth::delete_role))
+        .route(
+            "/nacos/v1/auth/permissions",
+            post(auth::add_permission).get(auth::list_permissions).delete(auth::delete_permission),
+        )
+        // audit
+        .route("/nacos/v1/audit", get(audit::list_audit))
+        .route("/nacos/v1/audit/verify", get(audit::verify_audit))
+        // access keys
+        .route("/nacos/v1/auth/keys", post(auth::create_access_key).get(auth::list_access_keys))
+        .route("/nacos/v1/auth/keys/:ak", get(auth::get_access_key).delete(auth::delete_access_key))
+        .route("/nacos/v1/auth/keys/:ak/rotate", post(auth::rotate_access_key))
+        .route("/nacos/v1/auth/keys/:ak/revoke", post(auth::revoke_access_key));
+    // 限流挂在认证之内，按认证后的调用方计数
+    rate_limited(api, app_ctx.limiter.clone(), app_ctx.auth.clone())
+        .route_layer(axum::middleware::from_fn_with_state(app_ctx.clone(), auth::require_token))
         .with_state(app_ctx)
+        .merge(public)
 }
 
 // -------------------- Instance APIs --------------------
@@ -334,6 +559,10 @@ struct InstanceDto {
     last_beat_time: String,
 }
 
+fn service_resource(group: &str, service: &str) -> Resource {
+    Resource::Service { group: group.to_string(), service: service.to_string() }
+}
+
 fn to_instance_dto(i: DomainInstance) -> InstanceDto {
     InstanceDto {
         id: i.id.0,
@@ -362,8 +591,13 @@ struct RegisterInstanceRequest {
 
 async fn register_instance(
     State(ctx): State<AppCtx>,
+    caller: Caller,
     Json(req): Json<RegisterInstanceRequest>,
-) -> Json<ApiResponse<String>> {
+) -> ApiResult<String> {
+    let group = req.group_name.unwrap_or_else(|| "DEFAULT_GROUP".into());
+    authorize(&caller, &[service_resource(&group, &req.service_name)], Action::Write)?;
+    let metadata = req.metadata.unwrap_or_default();
+    check_metadata_size(&ctx, &metadata)?;
     let id = Uuid::new_v4().to_string();
     let service = ServiceName(req.service_name.clone());
     let instance = DomainInstance {
@@ -371,20 +605,27 @@ async fn register_instance(
         ip: req.ip,
         port: req.port,
         service: service.clone(),
-        group: req.group_name.unwrap_or_else(|| "DEFAULT_GROUP".into()),
+        group,
         cluster: req.cluster_name.unwrap_or_else(|| "DEFAULT".into()),
         weight: req.weight.unwrap_or(1.0),
         healthy: true,
-        metadata: req.metadata.unwrap_or_default(),
+        metadata,
         last_beat_at: Utc::now(),
     };
-    let res = InstanceStore::register(&*ctx.stores, instance).await;
-    // 通知
-    Notifier::notify_instance_change(&*ctx.notifier, &service).await;
-    match res {
-        Ok(_) => ok(id),
-        Err(e) => Json(ApiResponse { code: 500, message: e.to_string(), data: None, timestamp: Utc::now().timestamp() }),
+    let res = InstanceStore::register(&*ctx.stores, instance.clone()).await;
+    let after = res.is_ok().then(|| audit::entity_hash(&instance));
+    audit::record(&ctx, &caller, AuditAction::InstanceRegister, audit::instance_resource(&instance), (None, after), &res).await;
+    if res.is_ok() {
+        let event = InstanceChangeEvent { op: InstanceOp::Added, instance };
+        ctx.notifier.notify_instance_event(&event).await;
     }
+    res?;
+    ok(id)
+}
+
+async fn find_instance(ctx: &AppCtx, service: &ServiceName, id: &InstanceId) -> Option<DomainInstance> {
+    let list = InstanceStore::list(&*ctx.stores, Some(service)).await.ok()?;
+    list.into_iter().find(|i| i.id.0 == id.0)
 }
 
 #[derive(Deserialize)]
@@ -395,72 +636,50 @@ struct BeatRequest {
 
 async fn beat_instance(
     State(ctx): State<AppCtx>,
+    caller: Caller,
     Json(req): Json<BeatRequest>,
-) -> Json<ApiResponse<bool>> {
+) -> ApiResult<bool> {
     let service = ServiceName(req.service_name);
     let id = InstanceId(req.instance_id);
+    let before = find_instance(&ctx, &service, &id).await;
+    if let Some(i) = &before {
+        authorize(&caller, &[service_resource(&i.group, &i.service.0)], Action::Write)?;
+    }
     let res = InstanceStore::beat(&*ctx.stores, &service, &id).await;
-    // 心跳也可触发变更通知（可选）
-    Notifier::notify_instance_change(&*ctx.notifier, &service).await;
-    match res {
-        Ok(v) => ok(v),
-        Err(e) => Json(ApiResponse { code: 500, message: e.to_string(), data: None, timestamp: Utc::now().timestamp() }),
+    // 仅在不健康实例恢复心跳时通知并审计，普通心跳不记录
+    if let Some(prev) = before.filter(|i| !i.healthy) {
+        if let Some(instance) = find_instance(&ctx, &service, &id).await {
+            let hashes = (Some(audit::entity_hash(&prev)), Some(audit::entity_hash(&instance)));
+            audit::record(&ctx, &caller, AuditAction::InstanceUpdate, audit::instance_resource(&instance), hashes, &res).await;
+            let event = InstanceChangeEvent { op: InstanceOp::HealthChanged, instance };
+            ctx.notifier.notify_instance_event(&event).await;
+        }
     }
+    ok(res?)
 }
 
 async fn deregister_instance(
     State(ctx): State<AppCtx>,
+    caller: Caller,
     Path((service_name, instance_id)): Path<(String, String)>,
-) -> Json<ApiResponse<()>> {
+) -> ApiResult<()> {
     let service = ServiceName(service_name);
     let id = InstanceId(instance_id);
+    let before = find_instance(&ctx, &service, &id).await;
+    if let Some(i) = &before {
+        authorize(&caller, &[service_resource(&i.group, &i.service.0)], Action::Write)?;
+    }
     let res = InstanceStore::deregister(&*ctx.stores, &service, &id).await;
-    Notifier::notify_instance_change(&*ctx.notifier, &service).await;
-    match res {
-        Ok(_v) => ok(()),
-        Err(e) => Json(ApiResponse { code: 500, message: e.to_string(), data: None, timestamp: Utc::now().timestamp() }),
+    if let Some(instance) = &before {
+        let hashes = (Some(audit::entity_hash(instance)), None);
+        audit::record(&ctx, &caller, AuditAction::InstanceDeregister, audit::instance_resource(instance), hashes, &res).await;
     }
-}
-
-#[derive(Deserialize)]
-struct StreamQuery {
-    topic: Option<String>, // "config" or "instance"
-    access_token: Option<String>,
-}
-
-async fn stream_events(
-    State(ctx): State<AppCtx>,
-    Query(q): Query<StreamQuery>,
-    headers: HeaderMap,
-) -> Result<Sse<impl Stream<Item = Result<Event, axum::Error>>>, StatusCode> {
-    // 简易鉴权：若启用 SSE_AUTH_REQUIRED（默认启用），则要求 Authorization 或 accessToken 存在
-    let auth_required = std::env::var("SSE_AUTH_REQUIRED")
-        .map(|v| !matches!(v.as_str(), "0" | "false" | "False" | "FALSE"))
-        .unwrap_or(true);
-    if auth_required {
-        let has_auth = headers.get("authorization").is_some() || q.access_token.is_some();
-        if !has_auth {
-            return Err(StatusCode::UNAUTHORIZED);
-        }
+    if let (Ok(true), Some(instance)) = (&res, before) {
+        let event = InstanceChangeEvent { op: InstanceOp::Removed, instance };
+        ctx.notifier.notify_instance_event(&event).await;
     }
-    let topic = q.topic.unwrap_or_else(|| "config".into());
-    let mut rx = if topic == "instance" {
-        ctx.notifier.tx_instance.subscribe()
-    } else {
-        ctx.notifier.tx_config.subscribe()
-    };
-    let s = stream! {
-        loop {
-            match rx.recv().await {
-                Ok(msg) => {
-                    yield Ok(Event::default().json_data(msg).unwrap_or(Event::default().data("invalid")));
-                }
-                Err(broadcast::error::RecvError::Lagged(_)) => continue,
-                Err(broadcast::error::RecvError::Closed) => break,
-            }
-        }
-    };
-    Ok(Sse::new(s).keep_alive(KeepAlive::new()))
+    res?;
+    ok(())
 }
 
 #[derive(Deserialize)]
@@ -470,21 +689,28 @@ struct ListInstanceQuery {
 
 async fn list_instances(
     State(ctx): State<AppCtx>,
+    caller: Caller,
     Query(q): Query<ListInstanceQuery>,
-) -> Json<ApiResponse<Vec<InstanceDto>>> {
+) -> ApiResult<Vec<InstanceDto>> {
     let service = q.service_name.map(ServiceName);
-    match InstanceStore::list(&*ctx.stores, service.as_ref()).await {
-        Ok(items) => ok(items.into_iter().map(to_instance_dto).collect()),
-        Err(e) => Json(ApiResponse { code: 500, message: e.to_string(), data: None, timestamp: Utc::now().timestamp() }),
-    }
+    let items = InstanceStore::list(&*ctx.stores, service.as_ref()).await?;
+    ok(items
+        .into_iter()
+        .filter(|i| caller.can(&service_resource(&i.group, &i.service.0), Action::Read))
+        .map(to_instance_dto)
+        .collect())
 }
 
 async fn list_services(
     State(ctx): State<AppCtx>,
-) -> Json<ApiResponse<Vec<String>>> {
+    caller: Caller,
+) -> ApiResult<Vec<String>> {
     let mut set: HashSet<String> = HashSet::new();
     for it in ctx.stores.instances.iter() {
-        set.insert(it.value().service.0.clone());
+        let i = it.value();
+        if caller.can(&service_resource(&i.group, &i.service.0), Action::Read) {
+            set.insert(i.service.0.clone());
+        }
     }
     ok(set.into_iter().collect())
 }
@@ -498,6 +724,9 @@ struct NamespaceDto {
     quota: u32,
     create_time: i64,
     update_time: i64,
+    variables: HashMap<String, String>,
+    require_approval: bool,
+    reviewers: Vec<String>,
 }
 
 fn to_namespace_dto(n: DomainNamespace) -> NamespaceDto {
@@ -508,6 +737,9 @@ fn to_namespace_dto(n: DomainNamespace) -> NamespaceDto {
         quota: n.quota,
         create_time: n.created_at,
         update_time: n.updated_at,
+        variables: n.variables,
+        require_approval: n.require_approval,
+        reviewers: n.reviewers,
     }
 }
 
@@ -517,6 +749,12 @@ struct CreateNamespaceRequest {
     namespace_show_name: String,
     #[serde(default)]
     namespace_desc: Option<String>,
+    #[serde(default)]
+    variables: HashMap<String, String>,
+    #[serde(default)]
+    require_approval: bool,
+    #[serde(default)]
+    reviewers: Vec<String>,
 }
 
 #[derive(Deserialize)]
@@ -526,12 +764,21 @@ struct UpdateNamespaceRequest {
     namespace_desc: Option<String>,
     #[serde(default)]
     quota: Option<u32>,
+    /// 整体替换命名空间变量；省略表示不修改
+    #[serde(default)]
+    variables: Option<HashMap<String, String>>,
+    #[serde(default)]
+    require_approval: Option<bool>,
+    #[serde(default)]
+    reviewers: Option<Vec<String>>,
 }
 
 async fn create_namespace(
     State(ctx): State<AppCtx>,
+    caller: Caller,
     Json(req): Json<CreateNamespaceRequest>,
-) -> Json<ApiResponse<bool>> {
+) -> ApiResult<bool> {
+    admin_only(&caller)?;
     let now = Utc::now().timestamp();
     let ns = DomainNamespace {
         id: req.namespace,
@@ -540,27 +787,54 @@ async fn create_namespace(
         quota: 0,
         created_at: now,
         updated_at: now,
+        variables: req.variables,
+        require_approval: req.require_approval,
+        reviewers: req.reviewers,
     };
-    match NamespaceStore::create(&*ctx.stores, ns).await {
-        Ok(_) => ok(true),
-        Err(e) => Json(ApiResponse { code: 500, message: e.to_string(), data: None, timestamp: Utc::now().timestamp() }),
+    check_reviewers(&ns)?;
+    let (resource, after) = (audit::namespace_resource(&ns.id), audit::entity_hash(&ns));
+    let res = NamespaceStore::create(&*ctx.stores, ns).await;
+    audit::record(&ctx, &caller, AuditAction::NamespaceCreate, resource, (None, res.is_ok().then_some(after)), &res).await;
+    res?;
+    ok(true)
+}
+
+/// 开启审批的命名空间必须指定审批人，否则任何人都能放行变更
+fn check_reviewers(ns: &DomainNamespace) -> Result<(), ApiError> {
+    if ns.require_approval && ns.reviewers.is_empty() {
+        return Err(ApiError::Validation("require_approval needs at least one reviewer".into()));
     }
+    Ok(())
 }
 
+/// 变量与审批人只返回给管理员或对整个命名空间可读的调用方，其余调用方只看到基本信息
 async fn list_namespaces(
     State(ctx): State<AppCtx>,
-) -> Json<ApiResponse<Vec<NamespaceDto>>> {
-    match NamespaceStore::list(&*ctx.stores).await {
-        Ok(items) => ok(items.into_iter().map(to_namespace_dto).collect()),
-        Err(e) => Json(ApiResponse { code: 500, message: e.to_string(), data: None, timestamp: Utc::now().timestamp() }),
-    }
+    caller: Caller,
+) -> ApiResult<Vec<NamespaceDto>> {
+    let items = NamespaceStore::list(&*ctx.stores).await?;
+    ok(items
+        .into_iter()
+        .map(|n| {
+            let whole = ConfigKey { namespace: n.id.clone(), group: "*".into(), data_id: "*".into() };
+            let readable = caller.is_admin() || caller.can(&Resource::Config(whole), Action::Read);
+            let mut dto = to_namespace_dto(n);
+            if !readable {
+                dto.variables.clear();
+                dto.reviewers.clear();
+            }
+            dto
+        })
+        .collect())
 }
 
 async fn update_namespace(
     State(ctx): State<AppCtx>,
+    caller: Caller,
     Path(namespace): Path<String>,
     Json(req): Json<UpdateNamespaceRequest>,
-) -> Json<ApiResponse<bool>> {
+) -> ApiResult<bool> {
+    admin_only(&caller)?;
     let now = Utc::now().timestamp();
     // 读旧值
     let mut found = None;
@@ -571,27 +845,50 @@ async fn update_namespace(
         }
     }
     if let Some(mut ns) = found {
+        let before = audit::entity_hash(&ns);
         ns.show_name = req.namespace_show_name;
         if let Some(desc) = req.namespace_desc { ns.desc = desc; }
         if let Some(q) = req.quota { ns.quota = q; }
+        if let Some(r) = req.require_approval { ns.require_approval = r; }
+        if let Some(r) = req.reviewers { ns.reviewers = r; }
+        check_reviewers(&ns)?;
+        // 记录取值发生变化的变量，更新后通知引用方
+        let mut changed = Vec::new();
+        if let Some(vars) = req.variables {
+            for name in ns.variables.keys().chain(vars.keys()) {
+                if ns.variables.get(name) != vars.get(name) && !changed.contains(name) {
+                    changed.push(name.clone());
+                }
+            }
+            ns.variables = vars;
+        }
         ns.updated_at = now;
-        match NamespaceStore::update(&*ctx.stores, ns).await {
-            Ok(v) => ok(v),
-            Err(e) => Json(ApiResponse { code: 500, message: e.to_string(), data: None, timestamp: Utc::now().timestamp() }),
+        let after = audit::entity_hash(&ns);
+        let res = NamespaceStore::update(&*ctx.stores, ns).await;
+        // 写入失败时内容未变
+        let hashes = (Some(before.clone()), Some(if res.is_ok() { after } else { before }));
+        audit::record(&ctx, &caller, AuditAction::NamespaceUpdate, audit::namespace_resource(&namespace), hashes, &res).await;
+        let updated = res?;
+        for name in changed {
+            let target = RefTarget::Var { namespace: namespace.clone(), name };
+            notify_dependents(&*ctx.stores, &*ctx.notifier, target).await;
         }
+        ok(updated)
     } else {
-        Json(ApiResponse { code: 404, message: "namespace not found".into(), data: None, timestamp: Utc::now().timestamp() })
+        Err(ApiError::NotFound("namespace not found".into()))
     }
 }
 
 async fn delete_namespace(
     State(ctx): State<AppCtx>,
+    caller: Caller,
     Path(namespace): Path<String>,
-) -> Json<ApiResponse<bool>> {
-    match NamespaceStore::delete(&*ctx.stores, &namespace).await {
-        Ok(v) => ok(v),
-        Err(e) => Json(ApiResponse { code: 500, message: e.to_string(), data: None, timestamp: Utc::now().timestamp() }),
-    }
+) -> ApiResult<bool> {
+    admin_only(&caller)?;
+    let before = NamespaceStore::get(&*ctx.stores, &namespace).await.map(|ns| audit::entity_hash(&ns));
+    let res = NamespaceStore::delete(&*ctx.stores, &namespace).await;
+    audit::record(&ctx, &caller, AuditAction::NamespaceDelete, audit::namespace_resource(&namespace), (before, None), &res).await;
+    ok(res?)
 }
 
 
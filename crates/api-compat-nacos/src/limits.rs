@@ -0,0 +1,322 @@
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    http::{HeaderMap, Method},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Router,
+};
+use dashmap::DashMap;
+use core_model::auth::ClientCertificate;
+
+use super::{auth, client, ApiError, AppCtx, AuthCtx, Principal};
+
+/// 令牌桶预算：每秒补充 per_sec 次，最多累积 burst 次；per_sec 为 0 表示不限
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Budget {
+    pub per_sec: f64,
+    pub burst: f64,
+}
+
+impl Budget {
+    pub const UNLIMITED: Budget = Budget { per_sec: 0.0, burst: 0.0 };
+
+    pub fn new(per_sec: f64, burst: f64) -> Self {
+        Self { per_sec, burst: burst.max(1.0) }
+    }
+
+    /// `每秒次数/突发上限`，如 `20/50`；省略突发上限时取每秒次数的 2 倍；`0` 表示不限
+    pub fn parse(raw: &str) -> Option<Self> {
+        let (rate, burst) = match raw.split_once('/') {
+            Some((rate, burst)) => (rate.trim().parse::<f64>().ok()?, Some(burst.trim().parse::<f64>().ok()?)),
+            None => (raw.trim().parse::<f64>().ok()?, None),
+        };
+        if !rate.is_finite() || rate < 0.0 || burst.is_some_and(|b| !b.is_finite() || b < 0.0) {
+            return None;
+        }
+        if rate == 0.0 {
+            return Some(Self::UNLIMITED);
+        }
+        Some(Self::new(rate, burst.unwrap_or(rate * 2.0)))
+    }
+
+    fn unlimited(&self) -> bool {
+        self.per_sec <= 0.0
+    }
+}
+
+/// 分别计数的请求类别
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Category {
+    ConfigRead,
+    ConfigWrite,
+    NamingWrite,
+    SseConnect,
+    /// 登录尝试，由登录接口按来源地址与用户名分别计数
+    Login,
+}
+
+impl Category {
+    fn describe(self) -> &'static str {
+        match self {
+            Category::ConfigRead => "config reads",
+            Category::ConfigWrite => "config writes",
+            Category::NamingWrite => "naming writes",
+            Category::SseConnect => "event stream connections",
+            Category::Login => "login attempts",
+        }
+    }
+
+    /// 按路由归类；心跳不计入，避免限流把存活实例判为不健康；登录在处理函数中另行计数
+    fn of(method: &Method, path: &str) -> Option<Self> {
+        let read = matches!(*method, Method::GET | Method::HEAD);
+        match path {
+            "/nacos/v1/events/stream" | "/nacos/v1/events/ws" => Some(Category::SseConnect),
+            "/nacos/v1/cs/configs/listener" => Some(Category::ConfigRead),
+            "/nacos/v1/ns/instance/beat" => None,
+            p if p.starts_with("/nacos/v1/cs/") => Some(if read { Category::ConfigRead } else { Category::ConfigWrite }),
+            p if p.starts_with("/nacos/v1/ns/") && !read => Some(Category::NamingWrite),
+            _ => None,
+        }
+    }
+}
+
+/// 限流预算与请求内容上限
+#[derive(Debug, Clone)]
+pub struct Limits {
+    pub enabled: bool,
+    pub config_read: Budget,
+    pub config_write: Budget,
+    pub naming_write: Budget,
+    pub sse_connect: Budget,
+    /// 同一来源地址、同一用户名各自的登录尝试预算
+    pub login: Budget,
+    /// 单条配置内容的字节上限（发布、灰度、变更集、审批、定时发布与导入）
+    pub max_content_bytes: usize,
+    /// 实例元数据序列化为 JSON 后的字节上限
+    pub max_metadata_bytes: usize,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            config_read: Budget::new(200.0, 400.0),
+            config_write: Budget::new(20.0, 50.0),
+            naming_write: Budget::new(50.0, 100.0),
+            sse_connect: Budget::new(2.0, 10.0),
+            login: Budget::new(0.2, 10.0),
+            max_content_bytes: 1024 * 1024,
+            max_metadata_bytes: 16 * 1024,
+        }
+    }
+}
+
+impl Limits {
+    /// RATE_LIMIT_ENABLED / RATE_LIMIT_CONFIG_READ / RATE_LIMIT_CONFIG_WRITE / RATE_LIMIT_NAMING_WRITE /
+    /// RATE_LIMIT_SSE_CONNECT / RATE_LIMIT_LOGIN / CONFIG_MAX_CONTENT_BYTES / INSTANCE_MAX_METADATA_BYTES，无法解析时取默认值
+    pub fn from_env() -> Self {
+        let d = Self::default();
+        let var = |name: &str| std::env::var(name).ok();
+        let budget = |name: &str, default: Budget| var(name).and_then(|v| Budget::parse(&v)).unwrap_or(default);
+        let bytes = |name: &str, default: usize| var(name).and_then(|v| v.trim().parse().ok()).unwrap_or(default);
+        Self {
+            enabled: var("RATE_LIMIT_ENABLED").map_or(d.enabled, |v| !matches!(v.as_str(), "0" | "false" | "False" | "FALSE")),
+            config_read: budget("RATE_LIMIT_CONFIG_READ", d.config_read),
+            config_write: budget("RATE_LIMIT_CONFIG_WRITE", d.config_write),
+            naming_write: budget("RATE_LIMIT_NAMING_WRITE", d.naming_write),
+            sse_connect: budget("RATE_LIMIT_SSE_CONNECT", d.sse_connect),
+            login: budget("RATE_LIMIT_LOGIN", d.login),
+            max_content_bytes: bytes("CONFIG_MAX_CONTENT_BYTES", d.max_content_bytes),
+            max_metadata_bytes: bytes("INSTANCE_MAX_METADATA_BYTES", d.max_metadata_bytes),
+        }
+    }
+
+    fn budget(&self, category: Category) -> Budget {
+        match category {
+            Category::ConfigRead => self.config_read,
+            Category::ConfigWrite => self.config_write,
+            Category::NamingWrite => self.naming_write,
+            Category::SseConnect => self.sse_connect,
+            Category::Login => self.login,
+        }
+    }
+}
+
+struct Bucket {
+    tokens: f64,
+    at: Instant,
+}
+
+/// 空闲桶的清理间隔
+const PRUNE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// 按（类别, 调用方）计数的令牌桶，进程内有效
+pub struct RateLimiter {
+    limits: Limits,
+    buckets: DashMap<(Category, String), Bucket>,
+    pruned_at: Mutex<Instant>,
+    /// 多桶同时扣减时持有
+    joint: Mutex<()>,
+}
+
+impl RateLimiter {
+    pub fn new(limits: Limits) -> Self {
+        Self { limits, buckets: DashMap::new(), pruned_at: Mutex::new(Instant::now()), joint: Mutex::new(()) }
+    }
+
+    pub fn limits(&self) -> &Limits {
+        &self.limits
+    }
+
+    /// 补充令牌后检查余量，debit 为 true 时扣除一个；余量不足时返回需等待的时长
+    fn take(&self, category: Category, client: &str, budget: Budget, now: Instant, debit: bool) -> Result<(), Duration> {
+        let mut bucket = self
+            .buckets
+            .entry((category, client.to_string()))
+            .or_insert_with(|| Bucket { tokens: budget.burst, at: now });
+        let refill = now.duration_since(bucket.at).as_secs_f64() * budget.per_sec;
+        bucket.tokens = (bucket.tokens + refill).min(budget.burst);
+        bucket.at = now;
+        if bucket.tokens < 1.0 {
+            return Err(Duration::from_secs_f64((1.0 - bucket.tokens) / budget.per_sec));
+        }
+        if debit {
+            bucket.tokens -= 1.0;
+        }
+        Ok(())
+    }
+
+    /// 取一个令牌；预算用尽时返回需等待的时长
+    fn acquire(&self, category: Category, client: &str) -> Result<(), Duration> {
+        let budget = self.limits.budget(category);
+        if !self.limits.enabled || budget.unlimited() {
+            return Ok(());
+        }
+        self.prune();
+        self.take(category, client, budget, Instant::now(), true)
+    }
+
+    /// 从多个桶各取一个令牌：全部有余量才一起扣除，任一不足时都不扣，返回该桶与需等待的时长
+    fn acquire_all<'c>(&self, category: Category, clients: &'c [String]) -> Result<(), (&'c str, Duration)> {
+        let budget = self.limits.budget(category);
+        if !self.limits.enabled || budget.unlimited() {
+            return Ok(());
+        }
+        self.prune();
+        // 同类别的多桶扣减串行进行，检查与扣除之间不会被其他请求插入
+        let _joint = self.joint.lock().unwrap();
+        let now = Instant::now();
+        for client in clients {
+            self.take(category, client, budget, now, false).map_err(|wait| (client.as_str(), wait))?;
+        }
+        for client in clients {
+            self.take(category, client, budget, now, true).map_err(|wait| (client.as_str(), wait))?;
+        }
+        Ok(())
+    }
+
+    /// 登录前调用：来源地址与（来源地址, 用户名）的预算都须有余量。用户名的预算不跨来源共享，
+    /// 他人从别处反复试错不会把该账号锁住
+    pub(crate) fn check_login(&self, addr: &str, username: &str) -> Result<(), ApiError> {
+        let clients = [format!("ip {}", addr), format!("ip {} user {}", addr, username)];
+        self.acquire_all(Category::Login, &clients)
+            .map_err(|(client, wait)| quota_exceeded(Category::Login, client, wait))
+    }
+
+    /// 丢弃已经补满的桶，它们与新建的桶等价
+    fn prune(&self) {
+        let now = Instant::now();
+        {
+            let mut pruned_at = self.pruned_at.lock().unwrap();
+            if now.duration_since(*pruned_at) < PRUNE_INTERVAL {
+                return;
+            }
+            *pruned_at = now;
+        }
+        self.buckets.retain(|(category, _), b| {
+            let budget = self.limits.budget(*category);
+            b.tokens + now.duration_since(b.at).as_secs_f64() * budget.per_sec < budget.burst
+        });
+    }
+}
+
+#[derive(Clone)]
+struct LimitCtx {
+    limiter: Arc<RateLimiter>,
+    auth: AuthCtx,
+}
+
+/// 来源地址：只有对端为 TRUSTED_PROXIES 时才采信转发头，客户端自填的转发头不能绕开计数
+pub(crate) fn peer_addr(headers: &HeaderMap, peer: Option<SocketAddr>) -> String {
+    match client::client_ip(headers, peer) {
+        ip if ip.is_empty() => "unknown".into(),
+        ip => ip,
+    }
+}
+
+/// 计数对象：已认证的用户、AK 或证书身份；事件流等未经认证中间件的路由取令牌中的用户；否则按来源地址
+fn client_key(auth: &AuthCtx, req: &Request) -> String {
+    if let Some(Principal(name)) = req.extensions().get::<Principal>() {
+        return name.clone();
+    }
+    if auth.enabled {
+        if let Some(cert) = req.extensions().get::<ClientCertificate>() {
+            return cert.principal();
+        }
+        let token = auth::request_token(req.headers(), req.uri().query());
+        if let Some(user) = token.and_then(|t| auth.tokens.verify(&t).ok()) {
+            return user;
+        }
+    }
+    let peer = req.extensions().get::<ConnectInfo<SocketAddr>>().map(|c| c.0);
+    format!("ip {}", peer_addr(req.headers(), peer))
+}
+
+async fn limit_requests(State(lc): State<LimitCtx>, req: Request, next: Next) -> Response {
+    let Some(category) = Category::of(req.method(), req.uri().path()) else {
+        return next.run(req).await;
+    };
+    let client = client_key(&lc.auth, &req);
+    match lc.limiter.acquire(category, &client) {
+        Ok(()) => next.run(req).await,
+        Err(wait) => quota_exceeded(category, &client, wait).into_response(),
+    }
+}
+
+/// 同 Nacos：HTTP 429，并在 Retry-After 中给出建议等待的秒数
+fn quota_exceeded(category: Category, client: &str, wait: Duration) -> ApiError {
+    let secs = wait.as_secs_f64().ceil().max(1.0) as u64;
+    let message = format!("too many {} from {}, retry after {}s", category.describe(), client, secs);
+    ApiError::QuotaExceeded { message, retry_after: Some(wait) }
+}
+
+/// 为路由挂上限流；API 路由在认证之后计数，事件流路由在 app-bootstrap 中单独挂载
+pub fn rate_limited<S: Clone + Send + Sync + 'static>(router: Router<S>, limiter: Arc<RateLimiter>, auth: AuthCtx) -> Router<S> {
+    router.route_layer(axum::middleware::from_fn_with_state(LimitCtx { limiter, auth }, limit_requests))
+}
+
+/// 配置内容超出上限时返回 413
+pub(crate) fn check_content_size(ctx: &AppCtx, content: &str) -> Result<(), ApiError> {
+    let max = ctx.limiter.limits().max_content_bytes;
+    if content.len() > max {
+        return Err(ApiError::PayloadTooLarge(format!(
+            "config content is {} bytes, exceeds the limit of {} bytes",
+            content.len(),
+            max
+        )));
+    }
+    Ok(())
+}
+
+/// 实例元数据超出上限时返回 413
+pub(crate) fn check_metadata_size(ctx: &AppCtx, metadata: &std::collections::HashMap<String, String>) -> Result<(), ApiError> {
+    let max = ctx.limiter.limits().max_metadata_bytes;
+    let len = serde_json::to_string(metadata).map(|s| s.len()).unwrap_or_default();
+    if len > max {
+        return Err(ApiError::PayloadTooLarge(format!("instance metadata is {} bytes, exceeds the limit of {} bytes", len, max)));
+    }
+    Ok(())
+}
@@ -0,0 +1,251 @@
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+use axum::{extract::{ConnectInfo, Form, Query, State}, http::HeaderMap};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use adapters_notify_sse::watch::{beta_event_matches, watchers_for};
+use core_model::auth::{Action, Resource};
+use core_model::config::ConfigKey;
+use core_model::listener::WatchTransport;
+use core_ports::ConfigWatcherRegistry;
+use core_usecase::beta::resolve_config;
+use core_usecase::config::content_md5;
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+use super::auth::{authorize, Caller};
+use super::{client, ok, ApiResult, AppCtx};
+
+// -------------------- Config listeners --------------------
+const FIELD_SEP: char = '\u{2}';
+const LINE_SEP: char = '\u{1}';
+
+/// 单次长轮询的监听登记，请求结束（返回或客户端断开）时注销
+struct PollGuard {
+    id: String,
+    registry: Arc<dyn ConfigWatcherRegistry>,
+}
+
+impl Drop for PollGuard {
+    fn drop(&mut self) {
+        let (id, registry) = (std::mem::take(&mut self.id), self.registry.clone());
+        tokio::spawn(async move { registry.remove_watches(&id).await });
+    }
+}
+
+/// 配置事件涉及的 key；变更集事件带 items
+pub(crate) fn event_keys(msg: &Value) -> Vec<ConfigKey> {
+    let key_of = |v: &Value| {
+        Some(ConfigKey {
+            namespace: v.get("namespace")?.as_str()?.to_string(),
+            group: v.get("group")?.as_str()?.to_string(),
+            data_id: v.get("data_id")?.as_str()?.to_string(),
+        })
+    };
+    match msg.get("items").and_then(|x| x.as_array()) {
+        Some(items) => items.iter().filter_map(key_of).collect(),
+        None => key_of(msg).into_iter().collect(),
+    }
+}
+
+/// Nacos `Listening-Configs`：`dataId^2group^2md5[^2tenant]^1...`
+fn parse_listening_configs(raw: &str) -> Vec<(ConfigKey, Option<String>)> {
+    raw.split(LINE_SEP)
+        .filter_map(|line| {
+            let f: Vec<&str> = line.split(FIELD_SEP).collect();
+            if f.len() < 3 || f[0].is_empty() {
+                return None;
+            }
+            let namespace = f.get(3).filter(|t| !t.is_empty()).copied().unwrap_or("public");
+            let key = ConfigKey { namespace: namespace.into(), group: f[1].into(), data_id: f[0].into() };
+            Some((key, Some(f[2].to_string()).filter(|m| !m.is_empty())))
+        })
+        .collect()
+}
+
+/// 变更的 key 按 Nacos 格式编码：`dataId^2group[^2tenant]^1`，整体 URL 编码
+fn encode_changed(keys: &[ConfigKey]) -> String {
+    let mut out = String::new();
+    for k in keys {
+        out.push_str(&k.data_id);
+        out.push(FIELD_SEP);
+        out.push_str(&k.group);
+        if k.namespace != "public" {
+            out.push(FIELD_SEP);
+            out.push_str(&k.namespace);
+        }
+        out.push(LINE_SEP);
+    }
+    urlencoding::encode(&out).into_owned()
+}
+
+#[derive(Deserialize)]
+pub(crate) struct LongPollQuery {
+    app_name: Option<String>,
+    labels: Option<String>,
+}
+
+/// 兼容 Nacos 长轮询：客户端 MD5 与服务端不一致立即返回，否则挂起直到监听的配置变更或超时。
+/// 无读权限的配置不参与监听
+pub(crate) async fn long_poll(
+    State(ctx): State<AppCtx>,
+    caller: Caller,
+    Query(q): Query<LongPollQuery>,
+    peer: Option<ConnectInfo<SocketAddr>>,
+    headers: HeaderMap,
+    Form(form): Form<HashMap<String, String>>,
+) -> String {
+    let client = client::client_info(&headers, peer.map(|c| c.0), q.labels.as_deref());
+    let app_name = client::app_name(&headers, q.app_name.as_deref());
+    let mut listening = form.get("Listening-Configs").map(|s| parse_listening_configs(s)).unwrap_or_default();
+    listening.retain(|(key, _)| caller.can(&Resource::Config(key.clone()), Action::Read));
+    let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok());
+    let timeout_ms = header("long-pulling-timeout").and_then(|v| v.parse::<u64>().ok()).unwrap_or(30_000).min(120_000);
+    let no_hangup = header("long-pulling-timeout-no-hangup") == Some("true");
+
+    // 每个请求独立登记，同一 IP 与应用的并发轮询互不覆盖
+    let connection_id = format!("long-poll:{}", Uuid::new_v4());
+    let watches = watchers_for(&connection_id, &client, app_name, WatchTransport::LongPoll, listening.clone());
+    ctx.stores.set_watches(&connection_id, watches).await;
+    let _guard = PollGuard { id: connection_id, registry: ctx.stores.clone() };
+
+    // 先订阅再比对，避免比对与挂起之间的变更丢失
+    let mut rx = ctx.events.tx_config.subscribe();
+    let mut changed = Vec::new();
+    for (key, md5) in &listening {
+        let server = resolve_config(&*ctx.stores, &*ctx.stores, key, &client)
+            .await
+            .map(|(item, _)| content_md5(&item.content));
+        if server != *md5 {
+            changed.push(key.clone());
+        }
+    }
+    if !changed.is_empty() || no_hangup || listening.is_empty() {
+        return encode_changed(&changed);
+    }
+    let wait = async {
+        loop {
+            match rx.recv().await {
+                Ok(msg) => {
+                    if !beta_event_matches(&msg, &client) {
+                        continue;
+                    }
+                    let hit: Vec<ConfigKey> = event_keys(&msg)
+                        .into_iter()
+                        .filter(|k| listening.iter().any(|(l, _)| l == k))
+                        .collect();
+                    if !hit.is_empty() {
+                        return hit;
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(broadcast::error::RecvError::Closed) => return vec![],
+            }
+        }
+    };
+    let changed = tokio::time::timeout(Duration::from_millis(timeout_ms), wait).await.unwrap_or_default();
+    encode_changed(&changed)
+}
+
+#[derive(Serialize)]
+pub(crate) struct WatcherDto {
+    ip: String,
+    app_name: Option<String>,
+    md5: Option<String>,
+    /// sse / long_poll
+    transport: WatchTransport,
+    last_seen: i64,
+    /// 客户端 MD5 与当前正式版本一致
+    up_to_date: Option<bool>,
+}
+
+#[derive(Serialize)]
+pub(crate) struct ConfigListenersDto {
+    data_id: String,
+    group: String,
+    namespace: String,
+    /// 当前正式版本的 MD5；配置不存在时为空
+    md5: Option<String>,
+    watchers: Vec<WatcherDto>,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct ConfigListenerQuery {
+    data_id: String,
+    group: Option<String>,
+    namespace: Option<String>,
+}
+
+/// 某条配置的在线监听者
+pub(crate) async fn config_listeners(
+    State(ctx): State<AppCtx>,
+    caller: Caller,
+    Query(q): Query<ConfigListenerQuery>,
+) -> ApiResult<ConfigListenersDto> {
+    let key = ConfigKey {
+        namespace: q.namespace.filter(|n| !n.is_empty()).unwrap_or_else(|| "public".into()),
+        group: q.group.unwrap_or_else(|| "DEFAULT_GROUP".into()),
+        data_id: q.data_id,
+    };
+    authorize(&caller, &[Resource::Config(key.clone())], Action::Read)?;
+    let md5 = core_ports::ConfigStore::get(&*ctx.stores, &key).await.map(|c| content_md5(&c.content));
+    let mut watchers: Vec<WatcherDto> = ctx
+        .stores
+        .watchers_of(&key)
+        .await
+        .into_iter()
+        .map(|w| WatcherDto {
+            up_to_date: w.md5.as_ref().map(|m| Some(m) == md5.as_ref()),
+            ip: w.ip,
+            app_name: w.app_name,
+            md5: w.md5,
+            transport: w.transport,
+            last_seen: w.last_seen.timestamp(),
+        })
+        .collect();
+    watchers.sort_by(|a, b| a.ip.cmp(&b.ip));
+    ok(ConfigListenersDto { data_id: key.data_id, group: key.group, namespace: key.namespace, md5, watchers })
+}
+
+#[derive(Serialize)]
+pub(crate) struct IpWatchDto {
+    data_id: String,
+    group: String,
+    namespace: String,
+    app_name: Option<String>,
+    md5: Option<String>,
+    transport: WatchTransport,
+    last_seen: i64,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct IpListenerQuery {
+    ip: String,
+}
+
+/// 某个客户端 IP 监听的配置，仅含调用方可读的条目
+pub(crate) async fn listeners_by_ip(
+    State(ctx): State<AppCtx>,
+    caller: Caller,
+    Query(q): Query<IpListenerQuery>,
+) -> ApiResult<Vec<IpWatchDto>> {
+    let items = ctx
+        .stores
+        .watches_by_ip(&q.ip)
+        .await
+        .into_iter()
+        .filter(|w| caller.can(&Resource::Config(w.key.clone()), Action::Read))
+        .map(|w| IpWatchDto {
+            data_id: w.key.data_id,
+            group: w.key.group,
+            namespace: w.key.namespace,
+            app_name: w.app_name,
+            md5: w.md5,
+            transport: w.transport,
+            last_seen: w.last_seen.timestamp(),
+        })
+        .collect();
+    ok(items)
+}
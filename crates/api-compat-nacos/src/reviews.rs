@@ -0,0 +1,203 @@
+use axum::{extract::{Path, Query, State}, response::Json};
+use serde::{Deserialize, Serialize};
+use core_model::audit::AuditAction;
+use core_model::auth::{Action, Resource};
+use core_model::config::ConfigKey;
+use core_model::review::{ChangeRequest, ChangeRequestState};
+use core_ports::{ChangeRequestStore, NamespaceStore};
+use core_usecase::config::PublishConfig;
+use core_usecase::review::{ApplyChangeRequest, ReviewChangeRequest, ReviewError, SubmitChangeRequest};
+use uuid::Uuid;
+
+use super::audit;
+use super::auth::{authorize, Caller};
+use super::limits::check_content_size;
+use super::{meta_of, ok, ApiResult, AppCtx};
+
+// -------------------- Change requests --------------------
+#[derive(Deserialize)]
+pub(crate) struct SubmitChangeRequestBody {
+    data_id: String,
+    group: String,
+    content: String,
+    namespace: Option<String>,
+    #[serde(default, alias = "type")]
+    format: Option<String>,
+    #[serde(default)]
+    config_tags: Option<String>,
+    #[serde(default)]
+    desc: Option<String>,
+    #[serde(default)]
+    app_name: Option<String>,
+    #[serde(default)]
+    comment: Option<String>,
+}
+
+#[derive(Serialize)]
+pub(crate) struct ChangeRequestDto {
+    id: String,
+    data_id: String,
+    group: String,
+    namespace: String,
+    format: Option<String>,
+    /// 当前版本 → 草稿的 unified diff；加密配置为空
+    diff: String,
+    /// 草稿对应的正式版本不存在时为新建
+    create: bool,
+    state: ChangeRequestState,
+    author: String,
+    comment: Option<String>,
+    reviewer: Option<String>,
+    review_comment: Option<String>,
+    published_by: Option<String>,
+    create_time: i64,
+    update_time: i64,
+}
+
+fn to_change_request_dto(cr: ChangeRequest) -> ChangeRequestDto {
+    ChangeRequestDto {
+        id: cr.id,
+        data_id: cr.key.data_id,
+        group: cr.key.group,
+        namespace: cr.key.namespace,
+        format: cr.format,
+        diff: cr.diff,
+        create: cr.base_version.is_none(),
+        state: cr.state,
+        author: cr.author,
+        comment: cr.comment,
+        reviewer: cr.reviewer,
+        review_comment: cr.review_comment,
+        published_by: cr.published_by,
+        create_time: cr.created_at.timestamp(),
+        update_time: cr.updated_at.timestamp(),
+    }
+}
+
+pub(crate) async fn submit_change_request(
+    State(ctx): State<AppCtx>,
+    caller: Caller,
+    Json(req): Json<SubmitChangeRequestBody>,
+) -> ApiResult<ChangeRequestDto> {
+    let key = ConfigKey {
+        namespace: req.namespace.unwrap_or_else(|| "public".into()),
+        group: req.group,
+        data_id: req.data_id,
+    };
+    authorize(&caller, &[Resource::Config(key.clone())], Action::Write)?;
+    check_content_size(&ctx, &req.content)?;
+    let uc = SubmitChangeRequest {
+        store: &*ctx.stores,
+        requests: &*ctx.stores,
+        schemas: Some(&*ctx.stores),
+        cipher: ctx.cipher.as_deref(),
+    };
+    let meta = meta_of(req.config_tags, req.desc, req.app_name);
+    let id = Uuid::new_v4().to_string();
+    let cr = uc.exec(id, key, req.content, req.format, meta, req.comment, caller.name).await?;
+    ok(to_change_request_dto(cr))
+}
+
+#[derive(Deserialize)]
+pub(crate) struct ListChangeRequestQuery {
+    namespace: Option<String>,
+    /// draft / approved / rejected / published
+    state: Option<ChangeRequestState>,
+}
+
+pub(crate) async fn list_change_requests(
+    State(ctx): State<AppCtx>,
+    caller: Caller,
+    Query(q): Query<ListChangeRequestQuery>,
+) -> ApiResult<Vec<ChangeRequestDto>> {
+    let namespace = q.namespace.as_deref().filter(|n| !n.is_empty());
+    let items = ctx.stores.list_change_requests(namespace, q.state).await?;
+    ok(items
+        .into_iter()
+        .filter(|cr| caller.can(&Resource::Config(cr.key.clone()), Action::Read))
+        .map(to_change_request_dto)
+        .collect())
+}
+
+pub(crate) async fn get_change_request(
+    State(ctx): State<AppCtx>,
+    caller: Caller,
+    Path(id): Path<String>,
+) -> ApiResult<ChangeRequestDto> {
+    let cr = ctx.stores.get_change_request(&id).await.ok_or(ReviewError::NotFound)?;
+    authorize(&caller, &[Resource::Config(cr.key.clone())], Action::Read)?;
+    ok(to_change_request_dto(cr))
+}
+
+#[derive(Deserialize, Default)]
+pub(crate) struct ReviewBody {
+    #[serde(default)]
+    comment: Option<String>,
+}
+
+// 名单内的审批人只需能读取该配置；名单为空时要求对该配置有写权限
+async fn review(ctx: AppCtx, caller: Caller, id: String, approve: bool, body: Option<ReviewBody>) -> ApiResult<ChangeRequestDto> {
+    if let Some(cr) = ctx.stores.get_change_request(&id).await {
+        let listed = NamespaceStore::get(&*ctx.stores, &cr.key.namespace)
+            .await
+            .is_some_and(|ns| ns.reviewers.contains(&caller.name));
+        let action = if listed { Action::Read } else { Action::Write };
+        authorize(&caller, &[Resource::Config(cr.key)], action)?;
+    }
+    let uc = ReviewChangeRequest { requests: &*ctx.stores, namespaces: &*ctx.stores };
+    let comment = body.unwrap_or_default().comment;
+    let cr = uc.exec(&id, approve, caller.name, comment).await?;
+    ok(to_change_request_dto(cr))
+}
+
+pub(crate) async fn approve_change_request(
+    State(ctx): State<AppCtx>,
+    caller: Caller,
+    Path(id): Path<String>,
+    body: Option<Json<ReviewBody>>,
+) -> ApiResult<ChangeRequestDto> {
+    review(ctx, caller, id, true, body.map(|b| b.0)).await
+}
+
+pub(crate) async fn reject_change_request(
+    State(ctx): State<AppCtx>,
+    caller: Caller,
+    Path(id): Path<String>,
+    body: Option<Json<ReviewBody>>,
+) -> ApiResult<ChangeRequestDto> {
+    review(ctx, caller, id, false, body.map(|b| b.0)).await
+}
+
+pub(crate) async fn apply_change_request(
+    State(ctx): State<AppCtx>,
+    caller: Caller,
+    Path(id): Path<String>,
+) -> ApiResult<ChangeRequestDto> {
+    let mut key = None;
+    if let Some(cr) = ctx.stores.get_change_request(&id).await {
+        authorize(&caller, &[Resource::Config(cr.key.clone())], Action::Write)?;
+        key = Some(cr.key);
+    }
+    let uc = ApplyChangeRequest {
+        publish: PublishConfig {
+            store: &*ctx.stores,
+            history: &*ctx.stores,
+            notifier: Some(&*ctx.notifier),
+            schemas: Some(&*ctx.stores),
+            cipher: ctx.cipher.as_deref(),
+            deps: Some(&*ctx.stores),
+            betas: Some(&*ctx.stores),
+        },
+        requests: &*ctx.stores,
+    };
+    let before = match &key {
+        Some(k) => audit::config_hash(&ctx, k).await,
+        None => None,
+    };
+    let res = uc.exec(&id, caller.name.clone()).await;
+    if let Some(k) = &key {
+        audit::record_config(&ctx, &caller, AuditAction::ConfigPublish, k, before, &res).await;
+    }
+    let cr = res?;
+    ok(to_change_request_dto(cr))
+}
@@ -0,0 +1,141 @@
+use axum::{extract::{Path, Query, State}, response::Json};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use core_model::auth::{Action, Resource};
+use core_model::config::ConfigKey;
+use core_model::schedule::{ScheduleState, ScheduledChange};
+use core_usecase::schedule::{CancelScheduled, ScheduleConfig, ScheduleError};
+use uuid::Uuid;
+
+use super::auth::{authorize, Caller};
+use super::limits::check_content_size;
+use super::{check_approval, meta_of, ok, ApiResult, AppCtx};
+
+// -------------------- Scheduled publishes --------------------
+#[derive(Deserialize)]
+pub(crate) struct ScheduleRequest {
+    data_id: String,
+    group: String,
+    content: String,
+    namespace: Option<String>,
+    #[serde(default, alias = "type")]
+    format: Option<String>,
+    #[serde(default)]
+    config_tags: Option<String>,
+    #[serde(default)]
+    desc: Option<String>,
+    #[serde(default)]
+    app_name: Option<String>,
+    /// 生效时间，Unix 秒
+    publish_at: i64,
+    /// 生效后保持的秒数，到期自动回退；省略表示不回退
+    #[serde(default)]
+    revert_after_secs: Option<i64>,
+}
+
+#[derive(Serialize)]
+pub(crate) struct ScheduledChangeDto {
+    id: String,
+    data_id: String,
+    group: String,
+    namespace: String,
+    format: Option<String>,
+    publish_at: i64,
+    revert_after_secs: Option<i64>,
+    revert_at: Option<i64>,
+    state: ScheduleState,
+    error: Option<String>,
+    created_by: String,
+    create_time: i64,
+    update_time: i64,
+}
+
+fn to_scheduled_dto(c: ScheduledChange) -> ScheduledChangeDto {
+    ScheduledChangeDto {
+        id: c.id,
+        data_id: c.key.data_id,
+        group: c.key.group,
+        namespace: c.key.namespace,
+        format: c.format,
+        publish_at: c.publish_at.timestamp(),
+        revert_after_secs: c.revert_after_secs,
+        revert_at: c.revert_at.map(|t| t.timestamp()),
+        state: c.state,
+        error: c.error,
+        created_by: c.created_by,
+        create_time: c.created_at.timestamp(),
+        update_time: c.updated_at.timestamp(),
+    }
+}
+
+pub(crate) async fn create_schedule(
+    State(ctx): State<AppCtx>,
+    caller: Caller,
+    Json(req): Json<ScheduleRequest>,
+) -> ApiResult<ScheduledChangeDto> {
+    let key = ConfigKey {
+        namespace: req.namespace.unwrap_or_else(|| "public".into()),
+        group: req.group,
+        data_id: req.data_id,
+    };
+    authorize(&caller, &[Resource::Config(key.clone())], Action::Write)?;
+    check_content_size(&ctx, &req.content)?;
+    check_approval(&ctx, &[&key.namespace]).await?;
+    let Some(publish_at) = DateTime::<Utc>::from_timestamp(req.publish_at, 0) else {
+        return Err(ScheduleError::Invalid("invalid publish_at".into()).into());
+    };
+    let uc = ScheduleConfig {
+        schedules: &*ctx.schedules,
+        schemas: Some(&*ctx.stores),
+        cipher: ctx.cipher.as_deref(),
+    };
+    let meta = meta_of(req.config_tags, req.desc, req.app_name);
+    let id = Uuid::new_v4().to_string();
+    let c = uc
+        .exec(id, key, req.content, req.format, meta, publish_at, req.revert_after_secs, caller.name)
+        .await?;
+    ok(to_scheduled_dto(c))
+}
+
+#[derive(Deserialize)]
+pub(crate) struct ListScheduleQuery {
+    namespace: Option<String>,
+    /// pending / published / reverted / cancelled / failed
+    state: Option<ScheduleState>,
+}
+
+pub(crate) async fn list_schedules(
+    State(ctx): State<AppCtx>,
+    caller: Caller,
+    Query(q): Query<ListScheduleQuery>,
+) -> ApiResult<Vec<ScheduledChangeDto>> {
+    let namespace = q.namespace.as_deref().filter(|n| !n.is_empty());
+    let items = ctx.schedules.list_scheduled(namespace, q.state).await?;
+    ok(items
+        .into_iter()
+        .filter(|c| caller.can(&Resource::Config(c.key.clone()), Action::Read))
+        .map(to_scheduled_dto)
+        .collect())
+}
+
+pub(crate) async fn get_schedule(
+    State(ctx): State<AppCtx>,
+    caller: Caller,
+    Path(id): Path<String>,
+) -> ApiResult<ScheduledChangeDto> {
+    let c = ctx.schedules.get_scheduled(&id).await.ok_or(ScheduleError::NotFound)?;
+    authorize(&caller, &[Resource::Config(c.key.clone())], Action::Read)?;
+    ok(to_scheduled_dto(c))
+}
+
+pub(crate) async fn cancel_schedule(
+    State(ctx): State<AppCtx>,
+    caller: Caller,
+    Path(id): Path<String>,
+) -> ApiResult<ScheduledChangeDto> {
+    if let Some(c) = ctx.schedules.get_scheduled(&id).await {
+        authorize(&caller, &[Resource::Config(c.key)], Action::Write)?;
+    }
+    let c = (CancelScheduled { schedules: &*ctx.schedules }).exec(&id).await?;
+    ok(to_scheduled_dto(c))
+}
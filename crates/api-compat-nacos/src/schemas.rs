@@ -0,0 +1,169 @@
+use axum::{extract::{Path, Query, State}, response::Json};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use core_model::auth::{Action, Resource};
+use core_model::config::ConfigKey;
+use core_model::schema::{ConfigSchema, SchemaViolation};
+use core_ports::SchemaStore;
+use core_usecase::schema::{check_schema, ValidateConfig};
+use uuid::Uuid;
+
+use super::auth::{authorize, Caller};
+use super::{ok, ApiError, ApiResult, AppCtx};
+
+// -------------------- Schema APIs --------------------
+#[derive(Serialize)]
+pub(crate) struct SchemaDto {
+    id: String,
+    namespace: String,
+    group: String,
+    data_id: String,
+    schema: Value,
+    desc: String,
+    create_time: i64,
+    update_time: i64,
+}
+
+/// Schema 作用范围按字面视为一条配置资源：只有权限模式覆盖整个范围才可读写
+fn schema_resource(s: &ConfigSchema) -> Resource {
+    Resource::Config(ConfigKey { namespace: s.namespace.clone(), group: s.group.clone(), data_id: s.data_id.clone() })
+}
+
+fn to_schema_dto(s: ConfigSchema) -> SchemaDto {
+    SchemaDto {
+        id: s.id,
+        namespace: s.namespace,
+        group: s.group,
+        data_id: s.data_id,
+        schema: s.schema,
+        desc: s.desc,
+        create_time: s.created_at,
+        update_time: s.updated_at,
+    }
+}
+
+#[derive(Deserialize)]
+pub(crate) struct SaveSchemaRequest {
+    #[serde(default)]
+    id: Option<String>,
+    namespace: Option<String>,
+    group: Option<String>,
+    data_id: String,
+    schema: Value,
+    #[serde(default)]
+    desc: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct ListSchemaQuery {
+    namespace: Option<String>,
+}
+
+pub(crate) async fn create_schema(
+    State(ctx): State<AppCtx>,
+    caller: Caller,
+    Json(req): Json<SaveSchemaRequest>,
+) -> ApiResult<String> {
+    check_schema(&req.schema).map_err(|e| ApiError::Validation(e.to_string()))?;
+    let now = Utc::now().timestamp();
+    let id = req.id.unwrap_or_else(|| Uuid::new_v4().to_string());
+    let schema = ConfigSchema {
+        id: id.clone(),
+        namespace: req.namespace.unwrap_or_else(|| "public".into()),
+        group: req.group.unwrap_or_else(|| "*".into()),
+        data_id: req.data_id,
+        schema: req.schema,
+        desc: req.desc.unwrap_or_default(),
+        created_at: now,
+        updated_at: now,
+    };
+    authorize(&caller, &[schema_resource(&schema)], Action::Write)?;
+    SchemaStore::put(&*ctx.stores, schema).await?;
+    ok(id)
+}
+
+pub(crate) async fn list_schemas(
+    State(ctx): State<AppCtx>,
+    caller: Caller,
+    Query(q): Query<ListSchemaQuery>,
+) -> ApiResult<Vec<SchemaDto>> {
+    let items = SchemaStore::list(&*ctx.stores, q.namespace.as_deref()).await?;
+    ok(items
+        .into_iter()
+        .filter(|s| caller.can(&schema_resource(s), Action::Read))
+        .map(to_schema_dto)
+        .collect())
+}
+
+pub(crate) async fn get_schema(
+    State(ctx): State<AppCtx>,
+    caller: Caller,
+    Path(id): Path<String>,
+) -> ApiResult<SchemaDto> {
+    let Some(s) = SchemaStore::get(&*ctx.stores, &id).await else {
+        return Err(ApiError::NotFound("schema not found".into()));
+    };
+    authorize(&caller, &[schema_resource(&s)], Action::Read)?;
+    ok(to_schema_dto(s))
+}
+
+pub(crate) async fn update_schema(
+    State(ctx): State<AppCtx>,
+    caller: Caller,
+    Path(id): Path<String>,
+    Json(req): Json<SaveSchemaRequest>,
+) -> ApiResult<bool> {
+    check_schema(&req.schema).map_err(|e| ApiError::Validation(e.to_string()))?;
+    let Some(mut s) = SchemaStore::get(&*ctx.stores, &id).await else {
+        return Err(ApiError::NotFound("schema not found".into()));
+    };
+    // 原范围与新范围都需可写
+    authorize(&caller, &[schema_resource(&s)], Action::Write)?;
+    if let Some(ns) = req.namespace { s.namespace = ns; }
+    if let Some(g) = req.group { s.group = g; }
+    if let Some(desc) = req.desc { s.desc = desc; }
+    s.data_id = req.data_id;
+    s.schema = req.schema;
+    s.updated_at = Utc::now().timestamp();
+    authorize(&caller, &[schema_resource(&s)], Action::Write)?;
+    SchemaStore::put(&*ctx.stores, s).await?;
+    ok(true)
+}
+
+pub(crate) async fn delete_schema(
+    State(ctx): State<AppCtx>,
+    caller: Caller,
+    Path(id): Path<String>,
+) -> ApiResult<bool> {
+    if let Some(s) = SchemaStore::get(&*ctx.stores, &id).await {
+        authorize(&caller, &[schema_resource(&s)], Action::Write)?;
+    }
+    ok(SchemaStore::delete(&*ctx.stores, &id).await?)
+}
+
+#[derive(Deserialize)]
+pub(crate) struct ValidateRequest {
+    data_id: String,
+    group: Option<String>,
+    namespace: Option<String>,
+    content: String,
+    #[serde(default)]
+    format: Option<String>,
+}
+
+// 仅校验不发布，返回按路径定位的错误列表（为空表示通过）
+pub(crate) async fn validate_config(
+    State(ctx): State<AppCtx>,
+    caller: Caller,
+    Json(req): Json<ValidateRequest>,
+) -> ApiResult<Vec<SchemaViolation>> {
+    let key = ConfigKey {
+        namespace: req.namespace.unwrap_or_else(|| "public".into()),
+        group: req.group.unwrap_or_else(|| "DEFAULT_GROUP".into()),
+        data_id: req.data_id,
+    };
+    authorize(&caller, &[Resource::Config(key.clone())], Action::Read)?;
+    let uc = ValidateConfig { schemas: &*ctx.stores };
+    ok(uc.violations(&key, &req.content, req.format.as_deref()).await?)
+}
@@ -0,0 +1,525 @@
+use std::collections::{HashMap, HashSet};
+use std::io::{Cursor, Read, Write};
+use axum::{
+    extract::{Multipart, Query, State},
+    http::header,
+    response::{IntoResponse, Json, Response},
+};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use core_model::audit::AuditAction;
+use core_model::auth::{Action, Resource};
+use core_model::config::{ConfigFilter, ConfigItem, ConfigKey, ConfigMeta};
+use core_ports::ConfigStore;
+use core_usecase::clone::{CloneAction, CloneConfigs};
+use core_usecase::config::PublishConfig;
+use core_usecase::schema::detect_format;
+use core_usecase::transfer::{ImportConfigs, ImportItem, ImportPolicy, ImportResult};
+use zip::write::SimpleFileOptions;
+
+use super::audit;
+use super::auth::{authorize, Caller};
+use super::limits::check_content_size;
+use super::{check_approval, meta_of, ok, parse_tags, to_config_dto, ApiError, ApiResult, AppCtx, ConfigItemDto};
+
+// -------------------- Export / Import / Clone --------------------
+/// Nacos 1.x 元数据文件：`{group}.{dataId}.app={appName}`，dataId 中的 `.` 替换为 `~`
+const META_V1: &str = ".meta.yml";
+/// Nacos 2.x 元数据文件：带 type / desc 的 YAML 列表
+const META_V2: &str = ".metadata.yml";
+/// 导入 ZIP 解压后的总字节上限，单个条目另受 CONFIG_MAX_CONTENT_BYTES 限制
+const ZIP_MAX_TOTAL_BYTES: usize = 64 * 1024 * 1024;
+
+#[derive(Deserialize)]
+pub(crate) struct ExportQuery {
+    namespace: String,
+    group: Option<String>,
+    /// 逗号分隔的 data_id 列表
+    data_ids: Option<String>,
+    /// 逗号分隔，需同时具备
+    tags: Option<String>,
+    /// ZIP 元数据格式：v1（默认，.meta.yml）或 v2（.metadata.yml）
+    meta: Option<String>,
+}
+
+/// 按条件选出要导出的配置，跳过调用方无读权限的条目
+async fn select(ctx: &AppCtx, caller: &Caller, q: &ExportQuery) -> anyhow::Result<Vec<ConfigItem>> {
+    let filter = ConfigFilter {
+        group: q.group.clone().filter(|g| !g.trim().is_empty()),
+        tags: q.tags.as_deref().map(parse_tags).unwrap_or_default(),
+        ..Default::default()
+    };
+    let (_, mut items) = ConfigStore::list(&*ctx.stores, &q.namespace, 1, u32::MAX, &filter).await?;
+    if let Some(ids) = q.data_ids.as_deref().map(parse_tags).filter(|v| !v.is_empty()) {
+        items.retain(|c| ids.contains(&c.key.data_id));
+    }
+    items.retain(|c| caller.can(&Resource::Config(c.key.clone()), Action::Read));
+    Ok(items)
+}
+
+pub(crate) async fn export_configs(
+    State(ctx): State<AppCtx>,
+    caller: Caller,
+    Query(q): Query<ExportQuery>,
+) -> ApiResult<Vec<ConfigItemDto>> {
+    let items = select(&ctx, &caller, &q).await?;
+    ok(items.into_iter().map(to_config_dto).collect())
+}
+
+#[derive(Serialize, Deserialize)]
+struct MetadataV2 {
+    metadata: Vec<MetadataItem>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MetadataItem {
+    data_id: String,
+    group: String,
+    #[serde(default, rename = "type", skip_serializing_if = "Option::is_none")]
+    format: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    app_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    desc: Option<String>,
+    /// 加密配置的数据密钥引用
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    encrypted_data_key: Option<String>,
+    /// 加密配置导出时所在的命名空间，导入到其他命名空间时据此解密
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    namespace: Option<String>,
+}
+
+fn meta_v1_key(group: &str, data_id: &str) -> String {
+    format!("{}.{}.app", group, data_id.replace('.', "~"))
+}
+
+fn encode_zip(items: &[ConfigItem], v2: bool) -> anyhow::Result<Vec<u8>> {
+    let mut zw = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    let opts = SimpleFileOptions::default();
+    for c in items {
+        zw.start_file(format!("{}/{}", c.key.group, c.key.data_id), opts)?;
+        zw.write_all(c.content.as_bytes())?;
+    }
+    if v2 {
+        let meta = MetadataV2 {
+            metadata: items
+                .iter()
+                .map(|c| MetadataItem {
+                    data_id: c.key.data_id.clone(),
+                    group: c.key.group.clone(),
+                    // Nacos 导入要求 type，未设置时按扩展名推断
+                    format: Some(detect_format(&c.key.data_id, c.format.as_deref())),
+                    app_name: c.app_name.clone(),
+                    desc: c.desc.clone(),
+                    encrypted_data_key: c.data_key.clone(),
+                    namespace: c.data_key.as_ref().map(|_| c.key.namespace.clone()),
+                })
+                .collect(),
+        };
+        zw.start_file(META_V2, opts)?;
+        zw.write_all(serde_yaml::to_string(&meta)?.as_bytes())?;
+    } else {
+        let mut meta = String::new();
+        for c in items {
+            if let Some(app) = c.app_name.as_deref().filter(|a| !a.is_empty()) {
+                meta.push_str(&format!("{}={}\r\n", meta_v1_key(&c.key.group, &c.key.data_id), app));
+            }
+        }
+        zw.start_file(META_V1, opts)?;
+        zw.write_all(meta.as_bytes())?;
+    }
+    Ok(zw.finish()?.into_inner())
+}
+
+pub(crate) async fn export_zip(
+    State(ctx): State<AppCtx>,
+    caller: Caller,
+    Query(q): Query<ExportQuery>,
+) -> Response {
+    let v2 = q.meta.as_deref().is_some_and(|m| m.eq_ignore_ascii_case("v2"));
+    let items = match select(&ctx, &caller, &q).await {
+        Ok(items) => items,
+        Err(e) => return ApiError::from(e).into_response(),
+    };
+    // v1 元数据无处保存数据密钥，导出的密文将无法再导入
+    if let (false, Some(c)) = (v2, items.iter().find(|c| c.data_key.is_some())) {
+        let message = format!("{} is encrypted, export with meta=v2 to keep its data key", c.key.data_id);
+        return ApiError::Validation(message).into_response();
+    }
+    let bytes = match encode_zip(&items, v2) {
+        Ok(b) => b,
+        Err(e) => return ApiError::from(e).into_response(),
+    };
+    let filename = format!("nacos_config_export_{}.zip", Utc::now().format("%Y%m%d%H%M%S"));
+    (
+        [
+            (header::CONTENT_TYPE, "application/zip".to_string()),
+            (header::CONTENT_DISPOSITION, format!("attachment; filename={}", filename)),
+        ],
+        bytes,
+    )
+        .into_response()
+}
+
+/// 解析 Nacos ZIP：条目路径为 `group/dataId`，元数据来自 .meta.yml 或 .metadata.yml；
+/// 按实际解压的字节数限制单个条目与总量，不信任 ZIP 头中声明的大小
+fn decode_zip(bytes: Vec<u8>, namespace: &str, max_entry_bytes: usize) -> anyhow::Result<Vec<ImportItem>> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(bytes))?;
+    let mut files: Vec<(String, String, String)> = Vec::new();
+    let mut meta_v1: HashMap<String, String> = HashMap::new();
+    let mut meta_v2: HashMap<(String, String), MetadataItem> = HashMap::new();
+    let mut total = 0usize;
+    for i in 0..archive.len() {
+        let f = archive.by_index(i)?;
+        if f.is_dir() {
+            continue;
+        }
+        let name = f.name().to_string();
+        let mut raw = Vec::new();
+        f.take(max_entry_bytes as u64 + 1)
+            .read_to_end(&mut raw)
+            .map_err(|e| anyhow::anyhow!("{}: {}", name, e))?;
+        if raw.len() > max_entry_bytes {
+            return Err(anyhow::anyhow!("{} exceeds {} bytes", name, max_entry_bytes));
+        }
+        total += raw.len();
+        if total > ZIP_MAX_TOTAL_BYTES {
+            return Err(anyhow::anyhow!("archive exceeds {} bytes uncompressed", ZIP_MAX_TOTAL_BYTES));
+        }
+        let content = String::from_utf8(raw).map_err(|e| anyhow::anyhow!("{}: {}", name, e))?;
+        match name.as_str() {
+            META_V1 => {
+                for line in content.lines() {
+                    if let Some((k, v)) = line.split_once('=') {
+                        meta_v1.insert(k.trim().to_string(), v.trim().to_string());
+                    }
+                }
+            }
+            META_V2 => {
+                let meta: MetadataV2 = serde_yaml::from_str(&content)?;
+                for m in meta.metadata {
+                    meta_v2.insert((m.group.clone(), m.data_id.clone()), m);
+                }
+            }
+            _ => match name.split_once('/') {
+                Some((group, data_id)) if !group.is_empty() && !data_id.is_empty() && !data_id.contains('/') => {
+                    files.push((group.to_string(), data_id.to_string(), content));
+                }
+                _ => return Err(anyhow::anyhow!("unexpected zip entry `{}`", name)),
+            },
+        }
+    }
+    let items = files
+        .into_iter()
+        .map(|(group, data_id, content)| {
+            let m = meta_v2.remove(&(group.clone(), data_id.clone()));
+            let app_name = match &m {
+                Some(m) => m.app_name.clone(),
+                None => meta_v1.get(&meta_v1_key(&group, &data_id)).cloned(),
+            };
+            let (format, desc, data_key, source_namespace) = match m {
+                Some(m) => (m.format, m.desc, m.encrypted_data_key, m.namespace),
+                None => (None, None, None, None),
+            };
+            ImportItem {
+                key: ConfigKey { namespace: namespace.to_string(), group, data_id },
+                content,
+                format,
+                meta: ConfigMeta { tags: None, desc, app_name },
+                data_key,
+                source_namespace,
+            }
+        })
+        .collect();
+    Ok(items)
+}
+
+#[derive(Deserialize)]
+pub(crate) struct ImportQuery {
+    /// ABORT（默认）/ SKIP / OVERWRITE
+    policy: Option<String>,
+    /// ZIP 导入的目标命名空间
+    namespace: Option<String>,
+}
+
+#[derive(Serialize)]
+struct ImportKeyDto {
+    data_id: String,
+    group: String,
+    namespace: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reason: Option<String>,
+}
+
+#[derive(Serialize)]
+pub(crate) struct ImportResultDto {
+    succ_count: usize,
+    skip_count: usize,
+    fail_count: usize,
+    skip_data: Vec<ImportKeyDto>,
+    fail_data: Vec<ImportKeyDto>,
+}
+
+fn to_import_dto(r: ImportResult) -> ImportResultDto {
+    let dto = |key: ConfigKey, reason: Option<String>| ImportKeyDto {
+        data_id: key.data_id,
+        group: key.group,
+        namespace: key.namespace,
+        reason,
+    };
+    ImportResultDto {
+        succ_count: r.succ_count,
+        skip_count: r.skip_count,
+        fail_count: r.fail_count,
+        skip_data: r.skip_data.into_iter().map(|k| dto(k, None)).collect(),
+        fail_data: r.fail_data.into_iter().map(|f| dto(f.key, Some(f.reason))).collect(),
+    }
+}
+
+async fn run_import(ctx: &AppCtx, caller: &Caller, items: Vec<ImportItem>, policy: Option<&str>) -> ApiResult<ImportResultDto> {
+    let policy = policy
+        .map(str::parse::<ImportPolicy>)
+        .transpose()
+        .map_err(|e| ApiError::Validation(e.to_string()))?
+        .unwrap_or_default();
+    let resources: Vec<Resource> = items.iter().map(|it| Resource::Config(it.key.clone())).collect();
+    authorize(caller, &resources, Action::Write)?;
+    for it in &items {
+        check_content_size(ctx, &it.content)?;
+    }
+    let namespaces: Vec<&str> = items.iter().map(|it| it.key.namespace.as_str()).collect();
+    check_approval(ctx, &namespaces).await?;
+    let uc = ImportConfigs {
+        publish: PublishConfig {
+            store: &*ctx.stores,
+            history: &*ctx.stores,
+            notifier: Some(&*ctx.notifier),
+            schemas: Some(&*ctx.stores),
+            cipher: ctx.cipher.as_deref(),
+            deps: Some(&*ctx.stores),
+            betas: Some(&*ctx.stores),
+        },
+    };
+    let mut before = Vec::with_capacity(items.len());
+    for it in &items {
+        before.push((it.key.clone(), audit::config_hash(ctx, &it.key).await));
+    }
+    let result = uc.exec(items, policy, caller.actor()).await;
+    audit_import(ctx, caller, before, &result).await;
+    ok(to_import_dto(result))
+}
+
+/// 逐条审计实际写入与失败的配置，跳过的不记录
+async fn audit_import(ctx: &AppCtx, caller: &Caller, before: Vec<(ConfigKey, Option<String>)>, result: &ImportResult) {
+    let skipped: HashSet<&ConfigKey> = result.skip_data.iter().collect();
+    let failed: HashMap<&ConfigKey, &str> = result.fail_data.iter().map(|f| (&f.key, f.reason.as_str())).collect();
+    for (key, hash) in before {
+        if skipped.contains(&key) {
+            continue;
+        }
+        let outcome = failed.get(&key).map_or(Ok(()), |reason| Err(*reason));
+        audit::record_config(ctx, caller, AuditAction::ConfigImport, &key, hash, &outcome).await;
+    }
+}
+
+#[derive(Deserialize)]
+pub(crate) struct JsonImportItem {
+    data_id: String,
+    group: String,
+    namespace: String,
+    content: String,
+    #[serde(default)]
+    format: Option<String>,
+    #[serde(default)]
+    config_tags: Option<String>,
+    #[serde(default)]
+    desc: Option<String>,
+    #[serde(default)]
+    app_name: Option<String>,
+    /// 导出时附带的数据密钥引用，content 为对应密文
+    #[serde(default)]
+    data_key: Option<String>,
+    /// 密文导出时所在的命名空间；改写 namespace 导入到其他命名空间时需保留原值
+    #[serde(default)]
+    source_namespace: Option<String>,
+}
+
+pub(crate) async fn import_configs(
+    State(ctx): State<AppCtx>,
+    caller: Caller,
+    Query(q): Query<ImportQuery>,
+    Json(items): Json<Vec<JsonImportItem>>,
+) -> ApiResult<ImportResultDto> {
+    let items = items
+        .into_iter()
+        .map(|it| ImportItem {
+            key: ConfigKey { namespace: it.namespace, group: it.group, data_id: it.data_id },
+            content: it.content,
+            format: it.format,
+            meta: meta_of(it.config_tags, it.desc, it.app_name),
+            data_key: it.data_key,
+            source_namespace: it.source_namespace,
+        })
+        .collect();
+    run_import(&ctx, &caller, items, q.policy.as_deref()).await
+}
+
+/// multipart 表单的 `file` 字段为 Nacos 导出的 ZIP
+pub(crate) async fn import_zip(
+    State(ctx): State<AppCtx>,
+    caller: Caller,
+    Query(q): Query<ImportQuery>,
+    mut form: Multipart,
+) -> ApiResult<ImportResultDto> {
+    let bad = |e: axum::extract::multipart::MultipartError| ApiError::Validation(e.to_string());
+    let mut bytes = None;
+    while let Some(field) = form.next_field().await.map_err(bad)? {
+        if field.name() == Some("file") {
+            bytes = Some(field.bytes().await.map_err(bad)?.to_vec());
+        }
+    }
+    let Some(bytes) = bytes else {
+        return Err(ApiError::Validation("missing multipart field `file`".into()));
+    };
+    let namespace = q.namespace.clone().filter(|n| !n.is_empty()).unwrap_or_else(|| "public".into());
+    let max_entry = ctx.limiter.limits().max_content_bytes;
+    let items = decode_zip(bytes, &namespace, max_entry).map_err(|e| ApiError::Validation(format!("invalid nacos zip: {}", e)))?;
+    run_import(&ctx, &caller, items, q.policy.as_deref()).await
+}
+
+// -------------------- Clone between namespaces --------------------
+#[derive(Deserialize)]
+pub(crate) struct CloneKey {
+    group: String,
+    data_id: String,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct CloneRequest {
+    source_namespace: String,
+    target_namespace: String,
+    /// 非空时目标配置统一改写到该 group
+    #[serde(default)]
+    target_group: Option<String>,
+    items: Vec<CloneKey>,
+    /// ABORT / SKIP / OVERWRITE，默认 SKIP
+    #[serde(default)]
+    policy: Option<String>,
+    #[serde(default)]
+    dry_run: bool,
+}
+
+#[derive(Serialize)]
+struct ClonePlanDto {
+    data_id: String,
+    source_group: String,
+    target_group: String,
+    action: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    diff: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reason: Option<String>,
+}
+
+#[derive(Serialize, Default)]
+pub(crate) struct CloneResultDto {
+    dry_run: bool,
+    create_count: usize,
+    update_count: usize,
+    unchanged_count: usize,
+    skip_count: usize,
+    fail_count: usize,
+    items: Vec<ClonePlanDto>,
+}
+
+pub(crate) async fn clone_configs(
+    State(ctx): State<AppCtx>,
+    caller: Caller,
+    Json(req): Json<CloneRequest>,
+) -> ApiResult<CloneResultDto> {
+    let policy = req
+        .policy
+        .as_deref()
+        .map(str::parse::<ImportPolicy>)
+        .transpose()
+        .map_err(|e| ApiError::Validation(e.to_string()))?
+        .unwrap_or(ImportPolicy::Skip);
+    let target_group = req.target_group.as_deref().map(str::trim).filter(|g| !g.is_empty());
+    if req.source_namespace == req.target_namespace && target_group.is_none() {
+        return Err(ApiError::Validation("source and target are the same".into()));
+    }
+    if !req.dry_run {
+        check_approval(&ctx, &[&req.target_namespace]).await?;
+    }
+    let sources: Vec<ConfigKey> = req
+        .items
+        .into_iter()
+        .map(|k| ConfigKey { namespace: req.source_namespace.clone(), group: k.group, data_id: k.data_id })
+        .collect();
+    // 源需可读，目标需可写（预演同样检查，避免借预演读取目标差异）
+    let source_res: Vec<Resource> = sources.iter().map(|k| Resource::Config(k.clone())).collect();
+    let target_res: Vec<Resource> = sources
+        .iter()
+        .map(|k| {
+            Resource::Config(ConfigKey {
+                namespace: req.target_namespace.clone(),
+                group: target_group.unwrap_or(&k.group).to_string(),
+                data_id: k.data_id.clone(),
+            })
+        })
+        .collect();
+    authorize(&caller, &source_res, Action::Read)?;
+    authorize(&caller, &target_res, Action::Write)?;
+    let uc = CloneConfigs {
+        publish: PublishConfig {
+            store: &*ctx.stores,
+            history: &*ctx.stores,
+            notifier: Some(&*ctx.notifier),
+            schemas: Some(&*ctx.stores),
+            cipher: ctx.cipher.as_deref(),
+            deps: Some(&*ctx.stores),
+            betas: Some(&*ctx.stores),
+        },
+    };
+    let mut before = HashMap::new();
+    if !req.dry_run {
+        for r in &target_res {
+            if let Resource::Config(key) = r {
+                before.insert(key.clone(), audit::config_hash(&ctx, key).await);
+            }
+        }
+    }
+    let plan = uc
+        .exec(sources, &req.target_namespace, target_group, policy, req.dry_run, caller.actor())
+        .await;
+    let mut out = CloneResultDto { dry_run: req.dry_run, ..Default::default() };
+    for p in plan {
+        // 审计实际写入与失败的目标配置
+        let outcome = match p.action {
+            CloneAction::Create | CloneAction::Update => Some(Ok(())),
+            CloneAction::Fail => Some(Err(p.reason.clone().unwrap_or_default())),
+            CloneAction::Unchanged | CloneAction::Skip => None,
+        };
+        if let (false, Some(outcome)) = (req.dry_run, outcome) {
+            let hash = before.get(&p.target).cloned().flatten();
+            audit::record_config(&ctx, &caller, AuditAction::ConfigImport, &p.target, hash, &outcome).await;
+        }
+        let action = match p.action {
+            CloneAction::Create => { out.create_count += 1; "create" }
+            CloneAction::Update => { out.update_count += 1; "update" }
+            CloneAction::Unchanged => { out.unchanged_count += 1; "unchanged" }
+            CloneAction::Skip => { out.skip_count += 1; "skip" }
+            CloneAction::Fail => { out.fail_count += 1; "fail" }
+        };
+        out.items.push(ClonePlanDto {
+            data_id: p.source.data_id,
+            source_group: p.source.group,
+            target_group: p.target.group,
+            action,
+            diff: p.diff,
+            reason: p.reason,
+        });
+    }
+    ok(out)
+}
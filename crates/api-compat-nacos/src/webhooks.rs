@@ -0,0 +1,279 @@
+use axum::{extract::{Path, Query, State}, response::Json};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use adapters_notify_webhook::WebhookError;
+use core_model::audit::AuditAction;
+use core_model::webhook::{DeliveryState, Webhook, WebhookDelivery};
+use core_ports::WebhookStore;
+use uuid::Uuid;
+
+use super::auth::{admin_only, Caller};
+use super::{audit, ok, ApiResult, AppCtx};
+
+// -------------------- Webhooks --------------------
+#[derive(Deserialize)]
+pub(crate) struct WebhookRequest {
+    name: Option<String>,
+    url: Option<String>,
+    /// 省略时创建会生成随机密钥，并仅在创建响应中返回一次
+    secret: Option<String>,
+    /// config / instance，省略表示全部
+    topics: Option<Vec<String>>,
+    namespaces: Option<Vec<String>>,
+    groups: Option<Vec<String>>,
+    services: Option<Vec<String>>,
+    include_content: Option<bool>,
+    enabled: Option<bool>,
+}
+
+#[derive(Serialize)]
+pub(crate) struct WebhookDto {
+    id: String,
+    name: String,
+    url: String,
+    topics: Vec<String>,
+    namespaces: Vec<String>,
+    groups: Vec<String>,
+    services: Vec<String>,
+    include_content: bool,
+    enabled: bool,
+    has_secret: bool,
+    /// 仅在创建或轮换密钥时返回
+    #[serde(skip_serializing_if = "Option::is_none")]
+    secret: Option<String>,
+    create_time: i64,
+    update_time: i64,
+}
+
+fn to_webhook_dto(h: Webhook, reveal_secret: bool) -> WebhookDto {
+    WebhookDto {
+        id: h.id,
+        name: h.name,
+        url: h.url,
+        topics: h.topics,
+        namespaces: h.namespaces,
+        groups: h.groups,
+        services: h.services,
+        include_content: h.include_content,
+        enabled: h.enabled,
+        has_secret: !h.secret.is_empty(),
+        secret: reveal_secret.then_some(h.secret),
+        create_time: h.created_at.timestamp(),
+        update_time: h.updated_at.timestamp(),
+    }
+}
+
+#[derive(Serialize)]
+pub(crate) struct DeliveryDto {
+    id: String,
+    webhook_id: String,
+    topic: String,
+    payload: serde_json::Value,
+    state: DeliveryState,
+    attempts: u32,
+    last_status: Option<u16>,
+    last_error: Option<String>,
+    next_attempt_at: Option<i64>,
+    create_time: i64,
+    update_time: i64,
+}
+
+fn to_delivery_dto(d: WebhookDelivery) -> DeliveryDto {
+    DeliveryDto {
+        id: d.id,
+        webhook_id: d.webhook_id,
+        topic: d.topic,
+        payload: d.payload,
+        state: d.state,
+        attempts: d.attempts,
+        last_status: d.last_status,
+        last_error: d.last_error,
+        next_attempt_at: d.next_attempt_at.map(|t| t.timestamp()),
+        create_time: d.created_at.timestamp(),
+        update_time: d.updated_at.timestamp(),
+    }
+}
+
+fn generate_secret() -> String {
+    format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple())
+}
+
+fn clean(list: Vec<String>) -> Vec<String> {
+    list.into_iter().map(|s| s.trim().to_string()).filter(|s| !s.is_empty()).collect()
+}
+
+fn validate(hook: &Webhook) -> Result<(), WebhookError> {
+    if hook.name.trim().is_empty() {
+        return Err(WebhookError::Invalid("name is required".into()));
+    }
+    if !(hook.url.starts_with("http://") || hook.url.starts_with("https://")) {
+        return Err(WebhookError::Invalid("url must start with http:// or https://".into()));
+    }
+    if let Some(t) = hook.topics.iter().find(|t| !matches!(t.as_str(), "config" | "instance")) {
+        return Err(WebhookError::Invalid(format!("unknown topic: {}", t)));
+    }
+    Ok(())
+}
+
+pub(crate) async fn create_webhook(
+    State(ctx): State<AppCtx>,
+    caller: Caller,
+    Json(req): Json<WebhookRequest>,
+) -> ApiResult<WebhookDto> {
+    admin_only(&caller)?;
+    let now = Utc::now();
+    let hook = Webhook {
+        id: Uuid::new_v4().to_string(),
+        name: req.name.unwrap_or_default(),
+        url: req.url.unwrap_or_default().trim().to_string(),
+        secret: req.secret.filter(|s| !s.is_empty()).unwrap_or_else(generate_secret),
+        topics: clean(req.topics.unwrap_or_default()),
+        namespaces: clean(req.namespaces.unwrap_or_default()),
+        groups: clean(req.groups.unwrap_or_default()),
+        services: clean(req.services.unwrap_or_default()),
+        include_content: req.include_content.unwrap_or(false),
+        enabled: req.enabled.unwrap_or(true),
+        created_at: now,
+        updated_at: now,
+    };
+    validate(&hook)?;
+    let res = ctx.stores.put_webhook(hook.clone()).await;
+    let after = res.is_ok().then(|| audit::entity_hash(&hook));
+    audit::record(&ctx, &caller, AuditAction::WebhookCreate, audit::webhook_resource(&hook.id), (None, after), &res).await;
+    res?;
+    ok(to_webhook_dto(hook, true))
+}
+
+pub(crate) async fn list_webhooks(State(ctx): State<AppCtx>, caller: Caller) -> ApiResult<Vec<WebhookDto>> {
+    admin_only(&caller)?;
+    let items = ctx.stores.list_webhooks().await?;
+    ok(items.into_iter().map(|h| to_webhook_dto(h, false)).collect())
+}
+
+pub(crate) async fn get_webhook(
+    State(ctx): State<AppCtx>,
+    caller: Caller,
+    Path(id): Path<String>,
+) -> ApiResult<WebhookDto> {
+    admin_only(&caller)?;
+    let h = ctx.stores.get_webhook(&id).await.ok_or(WebhookError::NotFound)?;
+    ok(to_webhook_dto(h, false))
+}
+
+/// 只更新请求中出现的字段；传入 secret 即轮换密钥
+pub(crate) async fn update_webhook(
+    State(ctx): State<AppCtx>,
+    caller: Caller,
+    Path(id): Path<String>,
+    Json(req): Json<WebhookRequest>,
+) -> ApiResult<WebhookDto> {
+    admin_only(&caller)?;
+    let mut hook = ctx.stores.get_webhook(&id).await.ok_or(WebhookError::NotFound)?;
+    let before = audit::entity_hash(&hook);
+    let rotated = req.secret.as_ref().is_some_and(|s| !s.is_empty());
+    if let Some(v) = req.name {
+        hook.name = v;
+    }
+    if let Some(v) = req.url {
+        hook.url = v.trim().to_string();
+    }
+    if let Some(v) = req.secret.filter(|s| !s.is_empty()) {
+        hook.secret = v;
+    }
+    if let Some(v) = req.topics {
+        hook.topics = clean(v);
+    }
+    if let Some(v) = req.namespaces {
+        hook.namespaces = clean(v);
+    }
+    if let Some(v) = req.groups {
+        hook.groups = clean(v);
+    }
+    if let Some(v) = req.services {
+        hook.services = clean(v);
+    }
+    if let Some(v) = req.include_content {
+        hook.include_content = v;
+    }
+    if let Some(v) = req.enabled {
+        hook.enabled = v;
+    }
+    hook.updated_at = Utc::now();
+    validate(&hook)?;
+    let res = ctx.stores.put_webhook(hook.clone()).await;
+    // 写入失败时内容未变
+    let after = if res.is_ok() { audit::entity_hash(&hook) } else { before.clone() };
+    audit::record(&ctx, &caller, AuditAction::WebhookUpdate, audit::webhook_resource(&id), (Some(before), Some(after)), &res).await;
+    res?;
+    ok(to_webhook_dto(hook, rotated))
+}
+
+pub(crate) async fn delete_webhook(
+    State(ctx): State<AppCtx>,
+    caller: Caller,
+    Path(id): Path<String>,
+) -> ApiResult<bool> {
+    admin_only(&caller)?;
+    let before = ctx.stores.get_webhook(&id).await.ok_or(WebhookError::NotFound)?;
+    let before = audit::entity_hash(&before);
+    let res = ctx.stores.delete_webhook(&id).await;
+    let after = if res.is_ok() { None } else { Some(before.clone()) };
+    audit::record(&ctx, &caller, AuditAction::WebhookDelete, audit::webhook_resource(&id), (Some(before), after), &res).await;
+    if !res? {
+        return Err(WebhookError::NotFound.into());
+    }
+    ok(true)
+}
+
+/// 立即发送一条 ping 事件，投递结果见投递记录
+pub(crate) async fn test_webhook(
+    State(ctx): State<AppCtx>,
+    caller: Caller,
+    Path(id): Path<String>,
+) -> ApiResult<DeliveryDto> {
+    admin_only(&caller)?;
+    let d = ctx.webhooks.ping(&id).await?;
+    ok(to_delivery_dto(d))
+}
+
+#[derive(Deserialize)]
+pub(crate) struct ListDeliveryQuery {
+    webhook_id: Option<String>,
+    /// pending / delivered / dead_letter
+    state: Option<DeliveryState>,
+    limit: Option<usize>,
+}
+
+/// 投递记录，`state=dead_letter` 即死信列表
+pub(crate) async fn list_deliveries(
+    State(ctx): State<AppCtx>,
+    caller: Caller,
+    Query(q): Query<ListDeliveryQuery>,
+) -> ApiResult<Vec<DeliveryDto>> {
+    admin_only(&caller)?;
+    let webhook_id = q.webhook_id.as_deref().filter(|w| !w.is_empty());
+    let limit = q.limit.unwrap_or(100).clamp(1, 1000);
+    let items = ctx.stores.list_deliveries(webhook_id, q.state, limit).await?;
+    ok(items.into_iter().map(to_delivery_dto).collect())
+}
+
+pub(crate) async fn get_delivery(
+    State(ctx): State<AppCtx>,
+    caller: Caller,
+    Path(id): Path<String>,
+) -> ApiResult<DeliveryDto> {
+    admin_only(&caller)?;
+    let d = ctx.stores.get_delivery(&id).await.ok_or(WebhookError::NotFound)?;
+    ok(to_delivery_dto(d))
+}
+
+/// 重新投递一条死信
+pub(crate) async fn retry_delivery(
+    State(ctx): State<AppCtx>,
+    caller: Caller,
+    Path(id): Path<String>,
+) -> ApiResult<DeliveryDto> {
+    admin_only(&caller)?;
+    let d = ctx.webhooks.redeliver(&id).await?;
+    ok(to_delivery_dto(d))
+}
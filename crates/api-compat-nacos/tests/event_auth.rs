@@ -0,0 +1,61 @@
+use std::sync::Arc;
+use adapters_auth_jwt::{BcryptHasher, JwtTokenService};
+use adapters_notify_sse::{SseHub, SseService};
+use adapters_storage_memory::MemStores;
+use api_compat_nacos::{event_auth, AuthCtx};
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use axum::Router;
+use tower::ServiceExt;
+
+fn auth_ctx(enabled: bool) -> AuthCtx {
+    let mem = Arc::new(MemStores::default());
+    AuthCtx {
+        enabled,
+        users: mem.clone(),
+        roles: mem.clone(),
+        permissions: mem.clone(),
+        hasher: Arc::new(BcryptHasher::with_cost(4)),
+        tokens: Arc::new(JwtTokenService::new(&[7u8; 32], 60).unwrap()),
+        keys: mem.clone(),
+        nonces: mem,
+        signature_window_secs: 300,
+        rotation_grace_secs: 300,
+    }
+}
+
+fn events(auth: AuthCtx) -> Router {
+    event_auth(SseService::new(Arc::new(SseHub::new())).routes(), auth)
+}
+
+async fn status(app: Router, uri: &str) -> StatusCode {
+    app.oneshot(Request::get(uri).body(Body::empty()).unwrap()).await.unwrap().status()
+}
+
+#[tokio::test]
+async fn rejects_stream_without_credentials() {
+    let app = events(auth_ctx(true));
+    assert_eq!(status(app.clone(), "/nacos/v1/events/stream?topic=config").await, StatusCode::UNAUTHORIZED);
+    assert_eq!(status(app, "/nacos/v1/events/ws").await, StatusCode::UNAUTHORIZED);
+}
+
+/// 空的 accessToken 不能让后面的 access_token 绕过校验
+#[tokio::test]
+async fn rejects_empty_token_followed_by_arbitrary_token() {
+    let app = events(auth_ctx(true));
+    let uri = "/nacos/v1/events/stream?topic=config&accessToken=&access_token=x";
+    assert_eq!(status(app, uri).await, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn rejects_token_of_unknown_user() {
+    let auth = auth_ctx(true);
+    let token = auth.tokens.issue("ghost").unwrap().token;
+    let uri = format!("/nacos/v1/events/stream?topic=config&access_token={}", token);
+    assert_eq!(status(events(auth), &uri).await, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn open_when_auth_disabled() {
+    assert_eq!(status(events(auth_ctx(false)), "/nacos/v1/events/stream?topic=config").await, StatusCode::OK);
+}
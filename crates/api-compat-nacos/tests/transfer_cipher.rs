@@ -0,0 +1,112 @@
+use std::sync::Arc;
+use adapters_auth_jwt::{BcryptHasher, JwtTokenService};
+use adapters_cipher_aes::LocalAesCipher;
+use adapters_notify_sse::SseHub;
+use adapters_notify_webhook::{DeliveryPolicy, WebhookNotifier};
+use adapters_storage_memory::MemStores;
+use api_compat_nacos::{routes_with_mem, AuthCtx, Limits, RateLimiter};
+use axum::body::{to_bytes, Body};
+use axum::http::{header, Request, StatusCode};
+use axum::Router;
+use core_ports::{ConfigCipher, Notifier};
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+const DATA_ID: &str = "cipher-aes-db.properties";
+const PLAIN: &str = "password=s3cret";
+
+fn app() -> Router {
+    let mem = Arc::new(MemStores::default());
+    let hub = Arc::new(SseHub::new());
+    let cipher: Arc<dyn ConfigCipher> = Arc::new(LocalAesCipher::new("test", &[9u8; 32]));
+    let auth = AuthCtx {
+        enabled: false,
+        users: mem.clone(),
+        roles: mem.clone(),
+        permissions: mem.clone(),
+        hasher: Arc::new(BcryptHasher::with_cost(4)),
+        tokens: Arc::new(JwtTokenService::new(&[7u8; 32], 60).unwrap()),
+        keys: mem.clone(),
+        nonces: mem.clone(),
+        signature_window_secs: 300,
+        rotation_grace_secs: 300,
+    };
+    let webhooks = Arc::new(WebhookNotifier::new(mem.clone(), DeliveryPolicy::default()));
+    let limiter = Arc::new(RateLimiter::new(Limits::default()));
+    let notifier: Arc<dyn Notifier> = hub.clone();
+    routes_with_mem(mem.clone(), notifier, hub, Some(cipher), webhooks, auth, mem.clone(), mem, limiter)
+}
+
+async fn send(app: &Router, req: Request<Body>) -> (StatusCode, Vec<u8>) {
+    let res = app.clone().oneshot(req).await.unwrap();
+    let status = res.status();
+    (status, to_bytes(res.into_body(), usize::MAX).await.unwrap().to_vec())
+}
+
+async fn send_json(app: &Router, method: &str, uri: &str, body: Value) -> Value {
+    let req = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap();
+    let (status, body) = send(app, req).await;
+    assert_eq!(status, StatusCode::OK, "{}", String::from_utf8_lossy(&body));
+    serde_json::from_slice(&body).unwrap()
+}
+
+async fn publish_in_dev(app: &Router) {
+    let body = json!({ "data_id": DATA_ID, "group": "DEFAULT_GROUP", "namespace": "dev", "content": PLAIN });
+    send_json(app, "POST", "/nacos/v1/cs/configs", body).await;
+}
+
+async fn read_plain(app: &Router, namespace: &str) -> String {
+    let uri = format!("/nacos/v1/cs/configs?data_id={}&namespace={}", DATA_ID, namespace);
+    let (status, body) = send(app, Request::get(uri).body(Body::empty()).unwrap()).await;
+    assert_eq!(status, StatusCode::OK, "{}", String::from_utf8_lossy(&body));
+    let v: Value = serde_json::from_slice(&body).unwrap();
+    v["data"]["content"].as_str().unwrap().to_string()
+}
+
+fn assert_imported(result: &Value) {
+    assert_eq!(result["data"]["succ_count"], 1, "{}", result);
+}
+
+/// dev 导出的 v2 ZIP 导入 staging 后仍能解密
+#[tokio::test]
+async fn zip_import_into_other_namespace_decrypts() {
+    let app = app();
+    publish_in_dev(&app).await;
+    let (status, zip) = send(&app, Request::get("/nacos/v1/cs/configs/export/zip?namespace=dev&meta=v2").body(Body::empty()).unwrap()).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let boundary = "rustacos-test-boundary";
+    let mut body = format!(
+        "--{b}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"export.zip\"\r\nContent-Type: application/zip\r\n\r\n",
+        b = boundary
+    )
+    .into_bytes();
+    body.extend_from_slice(&zip);
+    body.extend_from_slice(format!("\r\n--{}--\r\n", boundary).as_bytes());
+    let req = Request::post("/nacos/v1/cs/configs/import/zip?namespace=staging")
+        .header(header::CONTENT_TYPE, format!("multipart/form-data; boundary={}", boundary))
+        .body(Body::from(body))
+        .unwrap();
+    let (status, body) = send(&app, req).await;
+    assert_eq!(status, StatusCode::OK, "{}", String::from_utf8_lossy(&body));
+    assert_imported(&serde_json::from_slice(&body).unwrap());
+    assert_eq!(read_plain(&app, "staging").await, PLAIN);
+}
+
+/// JSON 导入改写 namespace 时以 source_namespace 指明密文来源
+#[tokio::test]
+async fn json_import_into_other_namespace_decrypts() {
+    let app = app();
+    publish_in_dev(&app).await;
+    let exported = send_json(&app, "GET", "/nacos/v1/cs/configs/export?namespace=dev", json!(null)).await;
+    let mut item = exported["data"][0].clone();
+    item["source_namespace"] = item["namespace"].clone();
+    item["namespace"] = json!("staging");
+    assert_imported(&send_json(&app, "POST", "/nacos/v1/cs/configs/import", json!([item])).await);
+    assert_eq!(read_plain(&app, "staging").await, PLAIN);
+}
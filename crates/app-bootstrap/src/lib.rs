@@ -1,52 +1,162 @@
+use std::net::SocketAddr;
+use std::path::Path;
 use std::sync::Arc;
 use axum::Router;
+use tokio::net::TcpListener;
 use tower_http::services::ServeDir;
+use adapters_storage_file::{
+    FileAccessKeyStore, FileAuditStore, FilePermissionStore, FileRoleStore, FileScheduledChangeStore, FileUserStore,
+};
 use adapters_storage_memory::MemStores;
-use chrono::Utc;
+use adapters_tls::TlsSettings;
+use adapters_auth_jwt::{BcryptHasher, JwtTokenService, DEFAULT_TOKEN_TTL_SECS};
+use adapters_cipher_aes::LocalAesCipher;
+use adapters_notify_sse::{ConfigSource, FanoutNotifier, SseHub, SseService, DEFAULT_REPLAY_CAPACITY};
+use adapters_notify_webhook::{DeliveryPolicy, WebhookNotifier};
+use api_compat_nacos::{event_auth, rate_limited, AuthCtx, Limits, RateLimiter};
+use core_ports::{
+    AccessKeyStore, AuditStore, ConfigCipher, ConfigWatcherRegistry, Notifier, PasswordHasher, PermissionStore, RoleStore,
+    ScheduledChangeStore, SchedulerPort, UserStore,
+};
+use core_usecase::auth::seed_admin;
 
-pub fn build_app() -> Router {
+mod scheduler;
+pub use scheduler::TokioScheduler;
+
+pub async fn build_app() -> Router {
     let mem = Arc::new(MemStores::default());
-    // 简易 TTL 调度：每 10s 扫描一次，超过 30s 未心跳的实例标记为 unhealthy
+    // CIPHER_KEY_FILE 指向 AES-256 密钥文件时启用 cipher-aes-* 配置加密
+    let cipher: Option<Arc<dyn ConfigCipher>> = std::env::var("CIPHER_KEY_FILE").ok().map(|path| {
+        let c = LocalAesCipher::from_key_file(&path)
+            .unwrap_or_else(|e| panic!("failed to load cipher key file {}: {}", path, e));
+        Arc::new(c) as Arc<dyn ConfigCipher>
+    });
+    // SSE_REPLAY_CAPACITY：保留供断线续传的最近事件条数
+    let replay = std::env::var("SSE_REPLAY_CAPACITY").ok().and_then(|v| v.parse().ok());
+    let hub = Arc::new(SseHub::with_replay_capacity(replay.unwrap_or(DEFAULT_REPLAY_CAPACITY)));
+    // 所有推送通道挂在同一个组合通知器上，用例与调度器只依赖它
+    // 出站 Webhook 重试策略见 WEBHOOK_MAX_ATTEMPTS / WEBHOOK_RETRY_BASE_MS 等
+    let webhooks = Arc::new(WebhookNotifier::new(mem.clone(), DeliveryPolicy::from_env()));
+    let notifier: Arc<dyn Notifier> = Arc::new(FanoutNotifier::new(vec![hub.clone(), webhooks.clone()]));
+    let auth = build_auth(&mem).await;
+    // 开启认证时事件订阅的令牌、AK 签名与客户端证书由 event_auth 校验并按权限过滤事件，无需令牌校验回调
+    let sse = SseService::new(hub.clone())
+        .auth_from_env()
+        .watchers(mem.clone() as Arc<dyn ConfigWatcherRegistry>)
+        .config_source(ConfigSource { store: mem.clone(), beta: mem.clone() });
+    // SCHEDULE_FILE 指定时定时发布任务持久化到该 JSON 文件，重启后补执行已到期的任务，否则仅存于内存
+    let schedules: Arc<dyn ScheduledChangeStore> = match std::env::var("SCHEDULE_FILE") {
+        Ok(path) => Arc::new(
+            FileScheduledChangeStore::open(&path).unwrap_or_else(|e| panic!("failed to open schedule file {}: {}", path, e)),
+        ),
+        Err(_) => mem.clone(),
+    };
+    // AUDIT_LOG_FILE 指定时审计日志以 JSON Lines 追加写入该文件，否则仅存于内存
+    let audit: Arc<dyn AuditStore> = match std::env::var("AUDIT_LOG_FILE") {
+        Ok(path) => Arc::new(FileAuditStore::open(&path).unwrap_or_else(|e| panic!("failed to open audit log {}: {}", path, e))),
+        Err(_) => mem.clone(),
+    };
+    let scheduler = Arc::new(TokioScheduler {
+        configs: mem.clone(),
+        history: mem.clone(),
+        schemas: mem.clone(),
+        deps: mem.clone(),
+        betas: mem.clone(),
+        instances: mem.clone(),
+        namespaces: mem.clone(),
+        schedules: schedules.clone(),
+        notifier: notifier.clone(),
+        audit: audit.clone(),
+        cipher: cipher.clone(),
+    });
     {
-        let mem_clone = mem.clone();
-        tokio::spawn(async move {
-            let ttl_secs: i64 = std::env::var("HEARTBEAT_TTL_SECS")
-                .ok()
-                .and_then(|v| v.parse::<i64>().ok())
-                .unwrap_or(30);
-            let sweep_secs: u64 = std::env::var("HEARTBEAT_SWEEP_SECS")
-                .ok()
-                .and_then(|v| v.parse::<u64>().ok())
-                .unwrap_or(10);
-            loop {
-                tokio::time::sleep(std::time::Duration::from_secs(sweep_secs)).await;
-                let now = Utc::now();
-                // 先收集需要更新的键，避免持锁过久
-                let keys: Vec<String> = mem_clone
-                    .instances
-                    .iter()
-                    .filter_map(|entry| {
-                        let v = entry.value();
-                        let diff = now.signed_duration_since(v.last_beat_at).num_seconds();
-                        if diff > ttl_secs && v.healthy {
-                            Some(entry.key().clone())
-                        } else {
-                            None
-                        }
-                    })
-                    .collect();
-                for k in keys {
-                    if let Some(mut v) = mem_clone.instances.get_mut(&k) {
-                        v.healthy = false;
-                    }
-                }
-            }
-        });
+        let s = scheduler.clone();
+        tokio::spawn(async move { s.schedule_heartbeat_cleanup().await });
     }
-    let api = api_compat_nacos::routes_with_mem(mem);
+    tokio::spawn(async move { scheduler.schedule_config_publishes().await });
+    // 限流预算与内容上限见 RATE_LIMIT_* / CONFIG_MAX_CONTENT_BYTES / INSTANCE_MAX_METADATA_BYTES；
+    // 事件流不经过 API 的认证中间件，单独挂认证与限流
+    let limiter = Arc::new(RateLimiter::new(Limits::from_env()));
+    let events = rate_limited(event_auth(sse.routes(), auth.clone()), limiter.clone(), auth.clone());
+    let api = api_compat_nacos::routes_with_mem(mem, notifier, hub, cipher, webhooks, auth, audit, schedules, limiter);
     Router::new()
         .merge(api)
+        .merge(events)
         .fallback_service(ServeDir::new("static").append_index_html_on_directories(true))
 }
 
+/// TLS_CERT_FILE / TLS_KEY_FILE 设置时以 TLS 提供服务（证书文件变化后自动重新加载），
+/// 另设 TLS_CLIENT_CA_FILE 时要求客户端证书；否则为明文 HTTP
+pub async fn serve(listener: TcpListener, app: Router) -> anyhow::Result<()> {
+    match TlsSettings::from_env()? {
+        Some(settings) => {
+            tracing::info!(mtls = settings.client_ca_file.is_some(), "serving over TLS");
+            adapters_tls::serve(listener, app, settings).await
+        }
+        None => Ok(axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>()).await?),
+    }
+}
+
+fn env_flag(name: &str, default: bool) -> bool {
+    std::env::var(name)
+        .map(|v| !matches!(v.as_str(), "0" | "false" | "False" | "FALSE"))
+        .unwrap_or(default)
+}
 
+type AuthStores = (Arc<dyn UserStore>, Arc<dyn RoleStore>, Arc<dyn PermissionStore>, Arc<dyn AccessKeyStore>);
+
+/// AUTH_ENABLED 控制 API 是否要求令牌（默认开启）；AUTH_USER_FILE 指定时用户持久化到该 JSON 文件；
+/// 令牌密钥取 AUTH_TOKEN_SECRET（base64），未设置时随机生成，重启后需重新登录；
+/// AK 签名的时间窗口取 AUTH_SIGNATURE_WINDOW_SECS，轮换 SK 的默认宽限期取 AUTH_KEY_ROTATION_GRACE_SECS
+async fn build_auth(mem: &Arc<MemStores>) -> AuthCtx {
+    // 角色、权限与 AK 表与用户表同目录：roles.json / permissions.json / access_keys.json
+    let (users, roles, permissions, keys): AuthStores = match std::env::var("AUTH_USER_FILE") {
+        Ok(path) => {
+            let dir = Path::new(&path).parent().map(Path::to_path_buf).unwrap_or_default();
+            (
+                Arc::new(FileUserStore::open(&path).unwrap_or_else(|e| panic!("failed to open user file {}: {}", path, e))),
+                Arc::new(FileRoleStore::open(dir.join("roles.json")).unwrap_or_else(|e| panic!("failed to open role file: {}", e))),
+                Arc::new(
+                    FilePermissionStore::open(dir.join("permissions.json"))
+                        .unwrap_or_else(|e| panic!("failed to open permission file: {}", e)),
+                ),
+                Arc::new(
+                    FileAccessKeyStore::open(dir.join("access_keys.json"))
+                        .unwrap_or_else(|e| panic!("failed to open access key file: {}", e)),
+                ),
+            )
+        }
+        Err(_) => (mem.clone(), mem.clone(), mem.clone(), mem.clone()),
+    };
+    let ttl = std::env::var("AUTH_TOKEN_TTL_SECS").ok().and_then(|v| v.parse().ok()).unwrap_or(DEFAULT_TOKEN_TTL_SECS);
+    let tokens = match std::env::var("AUTH_TOKEN_SECRET") {
+        Ok(secret) => JwtTokenService::from_secret_text(&secret, ttl).unwrap_or_else(|e| panic!("invalid AUTH_TOKEN_SECRET: {}", e)),
+        Err(_) => {
+            tracing::warn!("AUTH_TOKEN_SECRET not set, using a random token secret");
+            JwtTokenService::ephemeral(ttl)
+        }
+    };
+    let hasher: Arc<dyn PasswordHasher> = Arc::new(BcryptHasher::default());
+    // 用户表为空时创建初始管理员（ROLE_ADMIN），默认 nacos / nacos，与 Nacos 一致
+    let admin = std::env::var("AUTH_ADMIN_USERNAME").unwrap_or_else(|_| "nacos".into());
+    let password = std::env::var("AUTH_ADMIN_PASSWORD").unwrap_or_else(|_| "nacos".into());
+    match seed_admin(&*users, &*roles, &*hasher, &admin, &password).await {
+        Ok(true) => tracing::info!("created initial admin user {}", admin),
+        Ok(false) => {}
+        Err(e) => panic!("failed to create initial admin user: {}", e),
+    }
+    let env_secs = |name: &str, default: i64| std::env::var(name).ok().and_then(|v| v.parse().ok()).unwrap_or(default);
+    AuthCtx {
+        enabled: env_flag("AUTH_ENABLED", true),
+        users,
+        roles,
+        permissions,
+        hasher,
+        tokens: Arc::new(tokens),
+        keys,
+        // nonce 只需在时间窗口内去重，不做持久化
+        nonces: mem.clone(),
+        signature_window_secs: env_secs("AUTH_SIGNATURE_WINDOW_SECS", 300),
+        rotation_grace_secs: env_secs("AUTH_KEY_ROTATION_GRACE_SECS", 300),
+    }
+}
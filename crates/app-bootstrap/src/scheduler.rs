@@ -0,0 +1,80 @@
+use std::sync::Arc;
+use std::time::Duration;
+use async_trait::async_trait;
+use chrono::Utc;
+use core_model::event::{InstanceChangeEvent, InstanceOp};
+use core_ports::{
+    AuditStore, BetaConfigStore, ConfigCipher, ConfigDependencyStore, ConfigHistoryStore, ConfigStore, InstanceStore, NamespaceStore, Notifier,
+    ScheduledChangeStore, SchedulerPort, SchemaStore,
+};
+use core_usecase::config::PublishConfig;
+use core_usecase::schedule::RunScheduledChanges;
+
+fn env_or<T: std::str::FromStr>(name: &str, default: T) -> T {
+    std::env::var(name).ok().and_then(|v| v.parse::<T>().ok()).unwrap_or(default)
+}
+
+/// 基于 tokio 的周期任务；每个方法为常驻循环，由 build_app 各自 spawn
+pub struct TokioScheduler {
+    pub configs: Arc<dyn ConfigStore>,
+    pub history: Arc<dyn ConfigHistoryStore>,
+    pub schemas: Arc<dyn SchemaStore>,
+    pub deps: Arc<dyn ConfigDependencyStore>,
+    pub betas: Arc<dyn BetaConfigStore>,
+    pub instances: Arc<dyn InstanceStore>,
+    pub namespaces: Arc<dyn NamespaceStore>,
+    pub schedules: Arc<dyn ScheduledChangeStore>,
+    pub notifier: Arc<dyn Notifier>,
+    pub audit: Arc<dyn AuditStore>,
+    pub cipher: Option<Arc<dyn ConfigCipher>>,
+}
+
+#[async_trait]
+impl SchedulerPort for TokioScheduler {
+    // 简易 TTL 调度：每 10s 扫描一次，超过 30s 未心跳的实例标记为 unhealthy
+    async fn schedule_heartbeat_cleanup(&self) {
+        let ttl_secs: i64 = env_or("HEARTBEAT_TTL_SECS", 30);
+        let sweep_secs: u64 = env_or("HEARTBEAT_SWEEP_SECS", 10);
+        loop {
+            tokio::time::sleep(Duration::from_secs(sweep_secs)).await;
+            let before = Utc::now() - chrono::Duration::seconds(ttl_secs);
+            let expired = match self.instances.expire(before).await {
+                Ok(v) => v,
+                Err(e) => {
+                    tracing::warn!("heartbeat sweep failed: {}", e);
+                    continue;
+                }
+            };
+            for instance in expired {
+                let event = InstanceChangeEvent { op: InstanceOp::HealthChanged, instance };
+                self.notifier.notify_instance_event(&event).await;
+            }
+        }
+    }
+
+    // 每 SCHEDULE_TICK_SECS（默认 1s）执行一次到期的定时发布与回退；
+    // 任务状态在 ScheduledChangeStore 中，使用文件存储时重启后首个周期即补执行已到期的任务
+    async fn schedule_config_publishes(&self) {
+        let tick_secs: u64 = env_or("SCHEDULE_TICK_SECS", 1);
+        loop {
+            let uc = RunScheduledChanges {
+                publish: PublishConfig {
+                    store: &*self.configs,
+                    history: &*self.history,
+                    notifier: Some(&*self.notifier),
+                    schemas: Some(&*self.schemas),
+                    cipher: self.cipher.as_deref(),
+                    deps: Some(&*self.deps),
+                    betas: Some(&*self.betas),
+                },
+                schedules: &*self.schedules,
+                audit: Some(&*self.audit),
+                namespaces: Some(&*self.namespaces),
+            };
+            if let Err(e) = uc.exec(Utc::now()).await {
+                tracing::warn!("scheduled publish tick failed: {}", e);
+            }
+            tokio::time::sleep(Duration::from_secs(tick_secs)).await;
+        }
+    }
+}
@@ -0,0 +1,218 @@
+//! Rustacos 的 Rust 客户端。配置 AK/SK 后每个请求自动按服务端规则签名：
+//! `hex(HMAC-SHA256(sk, METHOD\npath\nquery\ntimestamp\nnonce\nhex(sha256(body))))`
+
+use std::collections::HashMap;
+use std::fmt;
+
+use anyhow::anyhow;
+use chrono::Utc;
+use core_model::auth::{canonical_request, HEADER_ACCESS_KEY, HEADER_NONCE, HEADER_SIGNATURE, HEADER_TIMESTAMP};
+use core_usecase::auth::{body_sha256, request_signature};
+use reqwest::Method;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone)]
+pub struct Credentials {
+    pub access_key: String,
+    pub secret_key: String,
+}
+
+/// 一次签名产生的请求头
+#[derive(Debug, Clone)]
+pub struct Signature {
+    pub timestamp: i64,
+    pub nonce: String,
+    pub signature: String,
+}
+
+impl Credentials {
+    /// path 与 query 须与实际发送的字节一致（已编码），query 不带 `?`
+    pub fn sign(&self, method: &str, path: &str, query: &str, body: &[u8]) -> Signature {
+        let timestamp = Utc::now().timestamp();
+        let nonce = Uuid::new_v4().simple().to_string();
+        let canonical = canonical_request(method, path, query, timestamp, &nonce, &body_sha256(body));
+        Signature { timestamp, signature: request_signature(&self.secret_key, &canonical), nonce }
+    }
+}
+
+#[derive(Deserialize)]
+struct Envelope<T> {
+    code: i32,
+    #[serde(default)]
+    error: Option<String>,
+    message: String,
+    data: Option<T>,
+}
+
+/// 服务端返回的失败，可从 `anyhow::Error` 中 downcast 取出后按 status / code 分支
+#[derive(Debug, Clone)]
+pub struct ApiError {
+    /// 如 `GET /nacos/v1/cs/configs`
+    pub endpoint: String,
+    pub status: u16,
+    /// 稳定错误码：validation_failed / not_found / conflict / unauthorized / forbidden /
+    /// quota_exceeded / payload_too_large / internal；响应不是 JSON 时为 None
+    pub code: Option<String>,
+    pub message: String,
+    /// 限流时服务端建议等待的秒数
+    pub retry_after: Option<u64>,
+}
+
+impl ApiError {
+    pub fn is_not_found(&self) -> bool {
+        self.status == 404
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: HTTP {}", self.endpoint, self.status)?;
+        if let Some(code) = &self.code {
+            write!(f, " {}", code)?;
+        }
+        write!(f, " {}", self.message)
+    }
+}
+
+impl std::error::Error for ApiError {}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ConfigItem {
+    pub data_id: String,
+    pub group: String,
+    pub namespace: String,
+    pub content: String,
+    pub md5: String,
+    pub format: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RegisterInstance {
+    pub service_name: String,
+    pub ip: String,
+    pub port: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cluster_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub weight: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<HashMap<String, String>>,
+}
+
+pub struct Client {
+    http: reqwest::Client,
+    base_url: String,
+    credentials: Option<Credentials>,
+}
+
+impl Client {
+    /// base_url 形如 `http://127.0.0.1:8848`
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self { http: reqwest::Client::new(), base_url: base_url.into().trim_end_matches('/').to_string(), credentials: None }
+    }
+
+    pub fn with_credentials(mut self, access_key: impl Into<String>, secret_key: impl Into<String>) -> Self {
+        self.credentials = Some(Credentials { access_key: access_key.into(), secret_key: secret_key.into() });
+        self
+    }
+
+    /// 发送请求并解出 `data`；失败时返回 ApiError。未设置 AK/SK 时不签名
+    pub async fn request<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        path: &str,
+        query: &[(&str, &str)],
+        body: Option<&serde_json::Value>,
+    ) -> anyhow::Result<Option<T>> {
+        // 先编码好查询串，签名与发送用同一份
+        let query = query
+            .iter()
+            .map(|(k, v)| format!("{}={}", urlencoding::encode(k), urlencoding::encode(v)))
+            .collect::<Vec<_>>()
+            .join("&");
+        let body = body.map(serde_json::to_vec).transpose()?.unwrap_or_default();
+        let url = if query.is_empty() {
+            format!("{}{}", self.base_url, path)
+        } else {
+            format!("{}{}?{}", self.base_url, path, query)
+        };
+        let mut req = self.http.request(method.clone(), url);
+        if !body.is_empty() {
+            req = req.header("content-type", "application/json");
+        }
+        if let Some(c) = &self.credentials {
+            let s = c.sign(method.as_str(), path, &query, &body);
+            req = req
+                .header(HEADER_ACCESS_KEY, &c.access_key)
+                .header(HEADER_TIMESTAMP, s.timestamp.to_string())
+                .header(HEADER_NONCE, s.nonce)
+                .header(HEADER_SIGNATURE, s.signature);
+        }
+        let resp = req.body(body).send().await?;
+        let status = resp.status();
+        let retry_after = resp
+            .headers()
+            .get(reqwest::header::RETRY_AFTER)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse().ok());
+        let bytes = resp.bytes().await?;
+        let error = |code: Option<String>, message: String| ApiError {
+            endpoint: format!("{} {}", method, path),
+            status: status.as_u16(),
+            code,
+            message,
+            retry_after,
+        };
+        let envelope: Envelope<T> = serde_json::from_slice(&bytes)
+            .map_err(|_| error(None, String::from_utf8_lossy(&bytes).into_owned()))?;
+        if !status.is_success() || envelope.code != 200 {
+            return Err(error(envelope.error, envelope.message).into());
+        }
+        Ok(envelope.data)
+    }
+
+    /// 配置不存在（HTTP 404）时返回 None
+    pub async fn get_config(&self, namespace: &str, group: &str, data_id: &str) -> anyhow::Result<Option<ConfigItem>> {
+        let query = [("namespace", namespace), ("group", group), ("data_id", data_id)];
+        match self.request(Method::GET, "/nacos/v1/cs/configs", &query, None).await {
+            Err(e) if e.downcast_ref::<ApiError>().is_some_and(ApiError::is_not_found) => Ok(None),
+            other => other,
+        }
+    }
+
+    pub async fn publish_config(&self, namespace: &str, group: &str, data_id: &str, content: &str) -> anyhow::Result<bool> {
+        let body = serde_json::json!({ "namespace": namespace, "group": group, "data_id": data_id, "content": content });
+        Ok(self.request(Method::POST, "/nacos/v1/cs/configs", &[], Some(&body)).await?.unwrap_or(false))
+    }
+
+    pub async fn delete_config(&self, namespace: &str, group: &str, data_id: &str) -> anyhow::Result<bool> {
+        let query = [("namespace", namespace), ("group", group), ("data_id", data_id)];
+        Ok(self.request(Method::DELETE, "/nacos/v1/cs/configs", &query, None).await?.unwrap_or(false))
+    }
+
+    /// 返回服务端分配的实例 ID，心跳与注销时使用
+    pub async fn register_instance(&self, instance: &RegisterInstance) -> anyhow::Result<String> {
+        let body = serde_json::to_value(instance)?;
+        self.request(Method::POST, "/nacos/v1/ns/instance", &[], Some(&body))
+            .await?
+            .ok_or_else(|| anyhow!("register instance returned no id"))
+    }
+
+    pub async fn send_beat(&self, service_name: &str, instance_id: &str) -> anyhow::Result<bool> {
+        let body = serde_json::json!({ "service_name": service_name, "instance_id": instance_id });
+        Ok(self.request(Method::POST, "/nacos/v1/ns/instance/beat", &[], Some(&body)).await?.unwrap_or(false))
+    }
+
+    pub async fn deregister_instance(&self, service_name: &str, instance_id: &str) -> anyhow::Result<()> {
+        let path = format!(
+            "/nacos/v1/ns/instance/{}/{}",
+            urlencoding::encode(service_name),
+            urlencoding::encode(instance_id)
+        );
+        self.request::<serde_json::Value>(Method::DELETE, &path, &[], None).await?;
+        Ok(())
+    }
+}
@@ -1,8 +1,11 @@
 pub mod config {
     use serde::{Deserialize, Serialize};
     use chrono::{DateTime, Utc};
+    use std::collections::HashMap;
+    use crate::client::ClientInfo;
+    use crate::pattern::glob_match;
 
-    #[derive(Debug, Clone, Serialize, Deserialize)]
+    #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
     pub struct ConfigKey {
         pub namespace: String,
         pub group: String,
@@ -17,6 +20,71 @@ pub mod config {
         pub updated_at: DateTime<Utc>,
         pub updated_by: Option<String>,
         pub version_ts: i64,
+        #[serde(default)]
+        pub tags: Vec<String>,
+        #[serde(default)]
+        pub desc: Option<String>,
+        #[serde(default)]
+        pub app_name: Option<String>,
+        /// 加密配置的数据密钥引用；为空表示 content 为明文
+        #[serde(default)]
+        pub data_key: Option<String>,
+    }
+
+    /// 发布时携带的元数据，字段为 None 表示沿用旧值
+    #[derive(Debug, Clone, Default)]
+    pub struct ConfigMeta {
+        pub tags: Option<Vec<String>>,
+        pub desc: Option<String>,
+        pub app_name: Option<String>,
+    }
+
+    /// 配置列表过滤条件，各条件之间为与关系
+    #[derive(Debug, Clone, Default)]
+    pub struct ConfigFilter {
+        /// data_id 子串
+        pub data_id: Option<String>,
+        /// group 模式，支持通配
+        pub group: Option<String>,
+        pub app_name: Option<String>,
+        /// 需同时具备的标签
+        pub tags: Vec<String>,
+        /// 配置格式；未设置 format 的配置按 data_id 扩展名判断
+        pub format: Option<String>,
+    }
+
+    impl ConfigFilter {
+        pub fn matches(&self, item: &ConfigItem) -> bool {
+            if let Some(f) = self.data_id.as_deref() {
+                if !item.key.data_id.contains(f) {
+                    return false;
+                }
+            }
+            if let Some(g) = self.group.as_deref() {
+                if !glob_match(g, &item.key.group) {
+                    return false;
+                }
+            }
+            if let Some(app) = self.app_name.as_deref() {
+                if item.app_name.as_deref() != Some(app) {
+                    return false;
+                }
+            }
+            if !self.tags.iter().all(|t| item.tags.contains(t)) {
+                return false;
+            }
+            if let Some(fmt) = self.format.as_deref() {
+                let own = item
+                    .format
+                    .clone()
+                    .or_else(|| item.key.data_id.rsplit_once('.').map(|(_, ext)| ext.to_string()))
+                    .unwrap_or_default();
+                if !own.eq_ignore_ascii_case(fmt) {
+                    return false;
+                }
+            }
+            true
+        }
     }
 
     #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -28,6 +96,244 @@ pub mod config {
         pub deleted: bool,
         pub updated_at: DateTime<Utc>,
         pub actor: Option<String>,
+        #[serde(default)]
+        pub data_key: Option<String>,
+    }
+
+    /// 灰度规则：命中任一 IP（支持通配），或声明的标签包含全部规则标签
+    #[derive(Debug, Clone, Default, Serialize, Deserialize)]
+    pub struct BetaRule {
+        #[serde(default)]
+        pub ips: Vec<String>,
+        #[serde(default)]
+        pub labels: HashMap<String, String>,
+    }
+
+    impl BetaRule {
+        pub fn matches(&self, client: &ClientInfo) -> bool {
+            if self.ips.iter().any(|p| glob_match(p, &client.ip)) {
+                return true;
+            }
+            !self.labels.is_empty()
+                && self
+                    .labels
+                    .iter()
+                    .all(|(k, v)| client.labels.get(k).map(|cv| cv == v).unwrap_or(false))
+        }
+    }
+
+    /// 同一 ConfigKey 的灰度版本，仅下发给命中规则的客户端
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    pub struct BetaConfigItem {
+        pub key: ConfigKey,
+        pub content: String,
+        pub format: Option<String>,
+        pub rule: BetaRule,
+        pub updated_at: DateTime<Utc>,
+        pub updated_by: Option<String>,
+        #[serde(default)]
+        pub data_key: Option<String>,
+    }
+}
+
+pub mod changeset {
+    use serde::{Deserialize, Serialize};
+    use chrono::{DateTime, Utc};
+    use crate::config::{ConfigHistoryItem, ConfigItem, ConfigKey};
+
+    /// 一次原子写入：配置的写入 / 删除与对应历史在同一临界区内生效
+    #[derive(Debug, Clone, Default)]
+    pub struct ConfigBatch {
+        pub puts: Vec<ConfigItem>,
+        pub deletes: Vec<ConfigKey>,
+        pub history: Vec<ConfigHistoryItem>,
+        /// 写入前置条件：提交时各配置的当前状态须与快照一致（version_ts 与内容），None 表示须不存在
+        pub expected: Vec<(ConfigKey, Option<ConfigItem>)>,
+    }
+
+    /// 单个配置在变更集中的前后快照；None 表示不存在
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    pub struct ChangesetEntry {
+        pub key: ConfigKey,
+        pub before: Option<ConfigItem>,
+        pub after: Option<ConfigItem>,
+    }
+
+    /// 多配置变更集，所有条目共用同一 revision
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    pub struct Changeset {
+        pub id: String,
+        pub revision: i64,
+        pub description: Option<String>,
+        pub actor: Option<String>,
+        pub created_at: DateTime<Utc>,
+        pub entries: Vec<ChangesetEntry>,
+        /// 本变更集是对哪个变更集的回滚
+        #[serde(default)]
+        pub rollback_of: Option<String>,
+        /// 回滚了本变更集的变更集
+        #[serde(default)]
+        pub rolled_back_by: Option<String>,
+    }
+}
+
+pub mod review {
+    use serde::{Deserialize, Serialize};
+    use chrono::{DateTime, Utc};
+    use crate::config::ConfigKey;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+    #[serde(rename_all = "lowercase")]
+    pub enum ChangeRequestState {
+        /// 待审批
+        Draft,
+        Approved,
+        Rejected,
+        /// 已发布
+        Published,
+    }
+
+    /// 配置变更请求：作者提交草稿，他人审批通过后方可发布
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    pub struct ChangeRequest {
+        pub id: String,
+        pub key: ConfigKey,
+        /// cipher-* 配置保存密文
+        pub content: String,
+        #[serde(default)]
+        pub data_key: Option<String>,
+        pub format: Option<String>,
+        #[serde(default)]
+        pub tags: Option<Vec<String>>,
+        #[serde(default)]
+        pub desc: Option<String>,
+        #[serde(default)]
+        pub app_name: Option<String>,
+        /// 提交时正式版本的 version_ts，发布前据此检测是否过期；None 表示新建
+        pub base_version: Option<i64>,
+        /// 当前版本 → 草稿的 unified diff；加密配置为空
+        pub diff: String,
+        pub author: String,
+        #[serde(default)]
+        pub comment: Option<String>,
+        pub state: ChangeRequestState,
+        #[serde(default)]
+        pub reviewer: Option<String>,
+        #[serde(default)]
+        pub review_comment: Option<String>,
+        #[serde(default)]
+        pub published_by: Option<String>,
+        pub created_at: DateTime<Utc>,
+        pub updated_at: DateTime<Utc>,
+    }
+}
+
+pub mod schedule {
+    use serde::{Deserialize, Serialize};
+    use chrono::{DateTime, Utc};
+    use crate::config::ConfigKey;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+    #[serde(rename_all = "lowercase")]
+    pub enum ScheduleState {
+        /// 等待生效
+        Pending,
+        /// 已发布；设置了窗口时等待到期回退
+        Published,
+        /// 窗口到期，已恢复为发布前的内容
+        Reverted,
+        Cancelled,
+        Failed,
+    }
+
+    /// 发布前的正式版本快照，窗口到期时据此回退；content 与正式存储一致（可能为密文）
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    pub struct ConfigSnapshot {
+        pub content: String,
+        #[serde(default)]
+        pub data_key: Option<String>,
+        pub format: Option<String>,
+        #[serde(default)]
+        pub tags: Vec<String>,
+        #[serde(default)]
+        pub desc: Option<String>,
+        #[serde(default)]
+        pub app_name: Option<String>,
+    }
+
+    /// 定时发布：publish_at 到达后发布，revert_after_secs 非空时到期自动回退
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    pub struct ScheduledChange {
+        pub id: String,
+        pub key: ConfigKey,
+        /// cipher-* 配置保存密文
+        pub content: String,
+        #[serde(default)]
+        pub data_key: Option<String>,
+        pub format: Option<String>,
+        #[serde(default)]
+        pub tags: Option<Vec<String>>,
+        #[serde(default)]
+        pub desc: Option<String>,
+        #[serde(default)]
+        pub app_name: Option<String>,
+        pub publish_at: DateTime<Utc>,
+        #[serde(default)]
+        pub revert_after_secs: Option<i64>,
+        /// 发布后计算：publish 时间 + 窗口
+        #[serde(default)]
+        pub revert_at: Option<DateTime<Utc>>,
+        /// 发布前的正式版本；None 表示发布前不存在，回退时删除
+        #[serde(default)]
+        pub previous: Option<ConfigSnapshot>,
+        /// 本次发布写入的 version_ts，回退前据此判断是否已被他人修改
+        #[serde(default)]
+        pub published_version: Option<i64>,
+        pub state: ScheduleState,
+        #[serde(default)]
+        pub error: Option<String>,
+        pub created_by: String,
+        pub created_at: DateTime<Utc>,
+        pub updated_at: DateTime<Utc>,
+    }
+}
+
+pub mod listener {
+    use serde::{Deserialize, Serialize};
+    use chrono::{DateTime, Utc};
+    use crate::config::ConfigKey;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+    #[serde(rename_all = "snake_case")]
+    pub enum WatchTransport {
+        Sse,
+        LongPoll,
+        Ws,
+    }
+
+    /// 某个客户端连接对一条配置的监听
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    pub struct ConfigWatcher {
+        pub connection_id: String,
+        pub key: ConfigKey,
+        pub ip: String,
+        pub app_name: Option<String>,
+        /// 客户端持有内容的 MD5；未上报时为 None
+        pub md5: Option<String>,
+        pub transport: WatchTransport,
+        pub last_seen: DateTime<Utc>,
+    }
+}
+
+pub mod client {
+    use serde::{Deserialize, Serialize};
+    use std::collections::HashMap;
+
+    /// 请求方标识：来源 IP 与客户端声明的标签
+    #[derive(Debug, Clone, Default, Serialize, Deserialize)]
+    pub struct ClientInfo {
+        pub ip: String,
+        pub labels: HashMap<String, String>,
     }
 }
 
@@ -59,6 +365,7 @@ pub mod instance {
 
 pub mod namespace {
     use serde::{Deserialize, Serialize};
+    use std::collections::HashMap;
 
     #[derive(Debug, Clone, Serialize, Deserialize)]
     pub struct Namespace {
@@ -68,7 +375,820 @@ pub mod namespace {
         pub quota: u32,
         pub created_at: i64,
         pub updated_at: i64,
+        /// 命名空间级变量，配置模板中以 `${name}` 引用
+        #[serde(default)]
+        pub variables: HashMap<String, String>,
+        /// 开启后配置变更须经变更请求审批，禁止直接发布
+        #[serde(default)]
+        pub require_approval: bool,
+        /// 可审批的用户；为空表示除作者外任何人均可审批
+        #[serde(default)]
+        pub reviewers: Vec<String>,
+    }
+}
+
+
+
+pub mod schema {
+    use serde::{Deserialize, Serialize};
+    use serde_json::Value;
+
+    /// 挂载到 data_id / group 模式上的 JSON Schema
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    pub struct ConfigSchema {
+        pub id: String,
+        pub namespace: String,
+        /// group 模式，支持 `*` / `?` 通配
+        pub group: String,
+        /// data_id 模式，支持 `*` / `?` 通配
+        pub data_id: String,
+        pub schema: Value,
+        pub desc: String,
+        pub created_at: i64,
+        pub updated_at: i64,
+    }
+
+    /// 单条校验错误，path 为 JSON Pointer（如 `/datasource/port`）
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    pub struct SchemaViolation {
+        pub schema_id: String,
+        pub path: String,
+        pub message: String,
+    }
+}
+
+pub mod pattern {
+    /// 简单通配匹配：`*` 匹配任意串，`?` 匹配单个字符
+    pub fn glob_match(pattern: &str, text: &str) -> bool {
+        let p: Vec<char> = pattern.chars().collect();
+        let t: Vec<char> = text.chars().collect();
+        let (mut pi, mut ti) = (0usize, 0usize);
+        let mut star: Option<usize> = None;
+        let mut mark = 0usize;
+        while ti < t.len() {
+            if pi < p.len() && (p[pi] == '?' || p[pi] == t[ti]) {
+                pi += 1;
+                ti += 1;
+            } else if pi < p.len() && p[pi] == '*' {
+                star = Some(pi);
+                mark = ti;
+                pi += 1;
+            } else if let Some(s) = star {
+                pi = s + 1;
+                mark += 1;
+                ti = mark;
+            } else {
+                return false;
+            }
+        }
+        while pi < p.len() && p[pi] == '*' {
+            pi += 1;
+        }
+        pi == p.len()
     }
 }
 
+pub mod search {
+    use serde::{Deserialize, Serialize};
+    use chrono::{DateTime, Utc};
+    use regex::{Regex, RegexBuilder};
+    use crate::config::ConfigKey;
+
+    /// 单个配置最多返回的命中行数
+    pub const MAX_LINES_PER_HIT: usize = 5;
+    /// 命中行超过该长度时截取命中附近的片段
+    const MAX_SNIPPET_LEN: usize = 200;
+
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    pub struct ContentQuery {
+        pub pattern: String,
+        #[serde(default)]
+        pub regex: bool,
+        #[serde(default)]
+        pub ignore_case: bool,
+    }
+
+    /// 命中行；ranges 为 line 内的字节区间，用于高亮
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    pub struct LineMatch {
+        pub line_no: usize,
+        pub line: String,
+        pub ranges: Vec<(usize, usize)>,
+    }
+
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    pub struct ContentSearchHit {
+        pub key: ConfigKey,
+        pub updated_at: DateTime<Utc>,
+        pub matches: Vec<LineMatch>,
+    }
+
+    /// 子串查询也编译为转义后的正则，两种模式共用一套匹配逻辑
+    pub struct ContentMatcher {
+        re: Regex,
+    }
 
+    impl ContentMatcher {
+        pub fn new(q: &ContentQuery) -> Result<Self, regex::Error> {
+            let pattern = if q.regex { q.pattern.clone() } else { regex::escape(&q.pattern) };
+            let re = RegexBuilder::new(&pattern)
+                .case_insensitive(q.ignore_case)
+                .size_limit(1 << 20)
+                .build()?;
+            Ok(Self { re })
+        }
+
+        pub fn find_lines(&self, content: &str) -> Vec<LineMatch> {
+            let mut out = Vec::new();
+            for (i, line) in content.lines().enumerate() {
+                let ranges: Vec<(usize, usize)> = self
+                    .re
+                    .find_iter(line)
+                    .filter(|m| m.end() > m.start())
+                    .map(|m| (m.start(), m.end()))
+                    .collect();
+                if ranges.is_empty() {
+                    continue;
+                }
+                out.push(snippet(i + 1, line, ranges));
+                if out.len() >= MAX_LINES_PER_HIT {
+                    break;
+                }
+            }
+            out
+        }
+    }
+
+    fn snippet(line_no: usize, line: &str, ranges: Vec<(usize, usize)>) -> LineMatch {
+        if line.len() <= MAX_SNIPPET_LEN {
+            return LineMatch { line_no, line: line.to_string(), ranges };
+        }
+        // 以首个命中为中心截取，并对齐到字符边界
+        let first = ranges[0].0;
+        let mut start = first.saturating_sub(MAX_SNIPPET_LEN / 4);
+        while !line.is_char_boundary(start) {
+            start -= 1;
+        }
+        let mut end = (start + MAX_SNIPPET_LEN).min(line.len());
+        while !line.is_char_boundary(end) {
+            end -= 1;
+        }
+        let ranges = ranges
+            .into_iter()
+            .filter(|(s, e)| *s >= start && *e <= end)
+            .map(|(s, e)| (s - start, e - start))
+            .collect();
+        LineMatch { line_no, line: line[start..end].to_string(), ranges }
+    }
+}
+
+pub mod template {
+    use crate::config::ConfigKey;
+
+    /// 模板引用的目标：另一条配置，或命名空间变量
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum RefTarget {
+        Config(ConfigKey),
+        Var { namespace: String, name: String },
+    }
+
+    impl RefTarget {
+        /// 依赖图中使用的唯一标识
+        pub fn id(&self) -> String {
+            match self {
+                RefTarget::Config(k) => format!("cfg:{}+{}+{}", k.namespace, k.group, k.data_id),
+                RefTarget::Var { namespace, name } => format!("var:{}+{}", namespace, name),
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct TemplateRef {
+        pub target: RefTarget,
+        /// `#` 之后的取值路径，如 `db.port`、`hosts.0`
+        pub path: Option<String>,
+        /// 原始表达式（不含 `${}`），用于错误信息
+        pub raw: String,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum Segment {
+        Text(String),
+        Ref(TemplateRef),
+    }
+
+    /// 解析表达式：
+    /// - `ns:group:data_id#path` / `group:data_id#path`（当前命名空间）引用配置
+    /// - `name` 引用命名空间变量
+    fn parse_expr(expr: &str, namespace: &str) -> Option<TemplateRef> {
+        let expr = expr.trim();
+        let (target, path) = match expr.split_once('#') {
+            Some((t, p)) => (t.trim(), Some(p.trim().to_string()).filter(|p| !p.is_empty())),
+            None => (expr, None),
+        };
+        if target.is_empty() {
+            return None;
+        }
+        let parts: Vec<&str> = target.split(':').map(str::trim).collect();
+        let target = match parts.as_slice() {
+            [name] => {
+                if path.is_some() {
+                    return None;
+                }
+                RefTarget::Var { namespace: namespace.to_string(), name: name.to_string() }
+            }
+            [group, data_id] => RefTarget::Config(ConfigKey {
+                namespace: namespace.to_string(),
+                group: group.to_string(),
+                data_id: data_id.to_string(),
+            }),
+            [ns, group, data_id] => RefTarget::Config(ConfigKey {
+                namespace: ns.to_string(),
+                group: group.to_string(),
+                data_id: data_id.to_string(),
+            }),
+            _ => return None,
+        };
+        if let RefTarget::Config(k) = &target {
+            if k.namespace.is_empty() || k.group.is_empty() || k.data_id.is_empty() {
+                return None;
+            }
+        }
+        Some(TemplateRef { target, path, raw: expr.to_string() })
+    }
+
+    /// 切分模板；`$${` 转义为字面量 `${`，无法识别的表达式原样保留
+    pub fn parse(content: &str, namespace: &str) -> Vec<Segment> {
+        let mut out = Vec::new();
+        let mut text = String::new();
+        let mut rest = content;
+        while let Some(i) = rest.find("${") {
+            if rest[..i].ends_with('$') {
+                text.push_str(&rest[..i - 1]);
+                text.push_str("${");
+                rest = &rest[i + 2..];
+                continue;
+            }
+            text.push_str(&rest[..i]);
+            let after = &rest[i + 2..];
+            match after.find('}').and_then(|end| parse_expr(&after[..end], namespace).map(|r| (end, r))) {
+                Some((end, r)) => {
+                    if !text.is_empty() {
+                        out.push(Segment::Text(std::mem::take(&mut text)));
+                    }
+                    out.push(Segment::Ref(r));
+                    rest = &after[end + 1..];
+                }
+                None => {
+                    text.push_str("${");
+                    rest = after;
+                }
+            }
+        }
+        text.push_str(rest);
+        if !text.is_empty() {
+            out.push(Segment::Text(text));
+        }
+        out
+    }
+
+    /// 内容中引用的全部目标（去重）
+    pub fn targets(content: &str, namespace: &str) -> Vec<RefTarget> {
+        let mut v: Vec<RefTarget> = Vec::new();
+        for seg in parse(content, namespace) {
+            if let Segment::Ref(r) = seg {
+                if !v.contains(&r.target) {
+                    v.push(r.target);
+                }
+            }
+        }
+        v
+    }
+}
+
+pub mod webhook {
+    use serde::{Deserialize, Serialize};
+    use chrono::{DateTime, Utc};
+    use crate::pattern::glob_match;
+
+    /// 出站 Webhook：配置 / 实例变更时向 url POST 签名后的 JSON
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    pub struct Webhook {
+        pub id: String,
+        pub name: String,
+        pub url: String,
+        /// HMAC-SHA256 签名密钥
+        pub secret: String,
+        /// 订阅的事件：config / instance，为空表示全部
+        #[serde(default)]
+        pub topics: Vec<String>,
+        /// 以下过滤条件支持通配，为空表示不限
+        #[serde(default)]
+        pub namespaces: Vec<String>,
+        #[serde(default)]
+        pub groups: Vec<String>,
+        #[serde(default)]
+        pub services: Vec<String>,
+        /// 配置事件是否附带明文 content，默认不附带
+        #[serde(default)]
+        pub include_content: bool,
+        pub enabled: bool,
+        pub created_at: DateTime<Utc>,
+        pub updated_at: DateTime<Utc>,
+    }
+
+    fn any_match(patterns: &[String], value: &str) -> bool {
+        patterns.is_empty() || patterns.iter().any(|p| glob_match(p, value))
+    }
+
+    impl Webhook {
+        fn wants(&self, topic: &str) -> bool {
+            self.enabled && (self.topics.is_empty() || self.topics.iter().any(|t| t == topic))
+        }
+
+        pub fn matches_config(&self, namespace: &str, group: &str) -> bool {
+            self.wants("config") && any_match(&self.namespaces, namespace) && any_match(&self.groups, group)
+        }
+
+        pub fn matches_service(&self, service: &str) -> bool {
+            self.wants("instance") && any_match(&self.services, service)
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+    #[serde(rename_all = "snake_case")]
+    pub enum DeliveryState {
+        /// 等待投递或重试中
+        Pending,
+        Delivered,
+        /// 重试次数用尽，进入死信列表
+        DeadLetter,
+    }
+
+    /// 一次事件投递及其重试记录
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    pub struct WebhookDelivery {
+        pub id: String,
+        pub webhook_id: String,
+        /// config / instance / ping
+        pub topic: String,
+        pub payload: serde_json::Value,
+        pub state: DeliveryState,
+        pub attempts: u32,
+        pub last_status: Option<u16>,
+        pub last_error: Option<String>,
+        pub next_attempt_at: Option<DateTime<Utc>>,
+        pub created_at: DateTime<Utc>,
+        pub updated_at: DateTime<Utc>,
+    }
+}
+
+pub mod audit {
+    use serde::{Deserialize, Serialize};
+    use chrono::{DateTime, Utc};
+    use sha2::{Digest, Sha256};
+    use crate::pattern::glob_match;
+
+    /// 被审计的写操作
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+    #[serde(rename_all = "snake_case")]
+    pub enum AuditAction {
+        /// 直接发布，以及变更集、灰度转正式与变更请求的发布
+        ConfigPublish,
+        ConfigDelete,
+        /// 历史回滚与变更集回滚
+        ConfigRollback,
+        /// 导入与跨命名空间克隆，按配置逐条记录
+        ConfigImport,
+        NamespaceCreate,
+        NamespaceUpdate,
+        NamespaceDelete,
+        InstanceRegister,
+        InstanceDeregister,
+        /// 不健康实例恢复心跳
+        InstanceUpdate,
+        UserCreate,
+        /// 修改密码
+        UserUpdate,
+        UserDelete,
+        RoleBind,
+        /// 解除单个用户的绑定，或删除整个角色及其权限
+        RoleDelete,
+        PermissionGrant,
+        PermissionRevoke,
+        AccessKeyCreate,
+        AccessKeyRotate,
+        AccessKeyRevoke,
+        AccessKeyDelete,
+        WebhookCreate,
+        WebhookUpdate,
+        WebhookDelete,
+    }
+
+    impl AuditAction {
+        pub const ALL: [AuditAction; 24] = [
+            AuditAction::ConfigPublish,
+            AuditAction::ConfigDelete,
+            AuditAction::ConfigRollback,
+            AuditAction::ConfigImport,
+            AuditAction::NamespaceCreate,
+            AuditAction::NamespaceUpdate,
+            AuditAction::NamespaceDelete,
+            AuditAction::InstanceRegister,
+            AuditAction::InstanceDeregister,
+            AuditAction::InstanceUpdate,
+            AuditAction::UserCreate,
+            AuditAction::UserUpdate,
+            AuditAction::UserDelete,
+            AuditAction::RoleBind,
+            AuditAction::RoleDelete,
+            AuditAction::PermissionGrant,
+            AuditAction::PermissionRevoke,
+            AuditAction::AccessKeyCreate,
+            AuditAction::AccessKeyRotate,
+            AuditAction::AccessKeyRevoke,
+            AuditAction::AccessKeyDelete,
+            AuditAction::WebhookCreate,
+            AuditAction::WebhookUpdate,
+            AuditAction::WebhookDelete,
+        ];
+
+        pub fn as_str(&self) -> &'static str {
+            match self {
+                AuditAction::ConfigPublish => "config_publish",
+                AuditAction::ConfigDelete => "config_delete",
+                AuditAction::ConfigRollback => "config_rollback",
+                AuditAction::ConfigImport => "config_import",
+                AuditAction::NamespaceCreate => "namespace_create",
+                AuditAction::NamespaceUpdate => "namespace_update",
+                AuditAction::NamespaceDelete => "namespace_delete",
+                AuditAction::InstanceRegister => "instance_register",
+                AuditAction::InstanceDeregister => "instance_deregister",
+                AuditAction::InstanceUpdate => "instance_update",
+                AuditAction::UserCreate => "user_create",
+                AuditAction::UserUpdate => "user_update",
+                AuditAction::UserDelete => "user_delete",
+                AuditAction::RoleBind => "role_bind",
+                AuditAction::RoleDelete => "role_delete",
+                AuditAction::PermissionGrant => "permission_grant",
+                AuditAction::PermissionRevoke => "permission_revoke",
+                AuditAction::AccessKeyCreate => "access_key_create",
+                AuditAction::AccessKeyRotate => "access_key_rotate",
+                AuditAction::AccessKeyRevoke => "access_key_revoke",
+                AuditAction::AccessKeyDelete => "access_key_delete",
+                AuditAction::WebhookCreate => "webhook_create",
+                AuditAction::WebhookUpdate => "webhook_update",
+                AuditAction::WebhookDelete => "webhook_delete",
+            }
+        }
+    }
+
+    impl std::str::FromStr for AuditAction {
+        type Err = String;
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            let s = s.trim();
+            AuditAction::ALL.into_iter().find(|a| a.as_str() == s).ok_or_else(|| format!("unknown audit action `{}`", s))
+        }
+    }
+
+    /// 一次写操作的审计内容：谁、何时、从哪里、对什么资源、改前改后的内容摘要及结果
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    pub struct AuditRecord {
+        pub at: DateTime<Utc>,
+        pub operator: String,
+        pub source_ip: String,
+        pub user_agent: String,
+        pub action: AuditAction,
+        /// 配置与服务同权限资源路径（如 `public:DEFAULT_GROUP:config/app.yaml`），命名空间为 `namespace/{id}`；
+        /// 用户、角色、权限、AK 与 Webhook 分别为 `user/{name}`、`role/{role}[/{user}]`、`permission/{role}/{action}/{resource}`、
+        /// `access_key/{ak}`、`webhook/{id}`
+        pub resource: String,
+        /// 变更前后的内容 MD5；不存在时为 None
+        pub before_hash: Option<String>,
+        pub after_hash: Option<String>,
+        pub success: bool,
+        /// 失败原因或批量操作的摘要
+        pub message: Option<String>,
+    }
+
+    /// 定时发布与回退等后台任务的操作者
+    pub const SYSTEM_OPERATOR: &str = "system";
+
+    /// 哈希链的起点
+    pub const AUDIT_GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+    /// 审计日志中的一条记录。hash 覆盖序号、前一条的 hash 与记录内容，
+    /// 任意修改、删除或插入都会使其后的链校验失败
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    pub struct AuditEntry {
+        /// 从 1 开始连续递增
+        pub seq: u64,
+        #[serde(flatten)]
+        pub record: AuditRecord,
+        pub prev_hash: String,
+        pub hash: String,
+    }
+
+    impl AuditEntry {
+        /// 接在 prev 之后生成新记录；prev 为 None 时从创世哈希开始
+        pub fn chain(prev: Option<&AuditEntry>, record: AuditRecord) -> Self {
+            let (seq, prev_hash) = match prev {
+                Some(p) => (p.seq + 1, p.hash.clone()),
+                None => (1, AUDIT_GENESIS_HASH.to_string()),
+            };
+            let hash = Self::digest(seq, &prev_hash, &record);
+            Self { seq, record, prev_hash, hash }
+        }
+
+        /// `hex(sha256(seq \n prev_hash \n 记录 JSON))`
+        pub fn digest(seq: u64, prev_hash: &str, record: &AuditRecord) -> String {
+            let body = serde_json::to_string(record).unwrap_or_default();
+            hex::encode(Sha256::digest(format!("{}\n{}\n{}", seq, prev_hash, body).as_bytes()))
+        }
+
+        /// 自身 hash 与内容一致
+        pub fn is_intact(&self) -> bool {
+            Self::digest(self.seq, &self.prev_hash, &self.record) == self.hash
+        }
+    }
+
+    /// 审计查询条件，字段为空表示不限；resource 支持 `*` / `?` 通配
+    #[derive(Debug, Clone, Default)]
+    pub struct AuditFilter {
+        pub operator: Option<String>,
+        pub action: Option<AuditAction>,
+        pub resource: Option<String>,
+        pub success: Option<bool>,
+        pub since: Option<DateTime<Utc>>,
+        pub until: Option<DateTime<Utc>>,
+    }
+
+    impl AuditFilter {
+        pub fn matches(&self, e: &AuditEntry) -> bool {
+            let r = &e.record;
+            self.operator.as_ref().is_none_or(|o| *o == r.operator)
+                && self.action.is_none_or(|a| a == r.action)
+                && self.resource.as_ref().is_none_or(|p| glob_match(p, &r.resource))
+                && self.success.is_none_or(|s| s == r.success)
+                && self.since.is_none_or(|t| r.at >= t)
+                && self.until.is_none_or(|t| r.at < t)
+        }
+
+        /// 在按序号升序的记录中筛选，按序号倒序分页，返回命中总数与本页
+        pub fn page(&self, entries: &[AuditEntry], offset: usize, limit: usize) -> (usize, Vec<AuditEntry>) {
+            let hits: Vec<&AuditEntry> = entries.iter().rev().filter(|e| self.matches(e)).collect();
+            let total = hits.len();
+            (total, hits.into_iter().skip(offset).take(limit).cloned().collect())
+        }
+    }
+}
+
+pub mod event {
+    use serde::{Deserialize, Serialize};
+    use serde_json::{json, Value};
+    use crate::config::ConfigKey;
+    use crate::instance::Instance;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+    #[serde(rename_all = "snake_case")]
+    pub enum ConfigOp {
+        Published,
+        Deleted,
+        /// 回滚到历史版本或定时发布到期回退
+        RolledBack,
+    }
+
+    /// 配置变更事件，订阅者无需回查即可得知变更内容
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    pub struct ConfigChangeEvent {
+        pub key: ConfigKey,
+        pub op: ConfigOp,
+        /// 变更后的版本（version_ts）；删除时为删除时间
+        pub revision: i64,
+        /// 变更后存储内容的 MD5，删除时为 None
+        pub md5: Option<String>,
+        pub actor: Option<String>,
+        /// 明文内容；加密配置或内容过大时省略
+        pub content: Option<String>,
+    }
+
+    impl ConfigChangeEvent {
+        /// 推送负载：在原有 namespace / group / data_id 之外附带变更详情
+        pub fn payload(&self) -> Value {
+            let mut v = json!({
+                "topic": "config",
+                "namespace": self.key.namespace,
+                "group": self.key.group,
+                "data_id": self.key.data_id,
+                "op": self.op,
+                "revision": self.revision,
+                "md5": self.md5,
+                "actor": self.actor,
+            });
+            if let Some(content) = &self.content {
+                v["content"] = json!(content);
+            }
+            v
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+    #[serde(rename_all = "snake_case")]
+    pub enum InstanceOp {
+        Added,
+        Removed,
+        /// 心跳超时转为不健康，或恢复心跳转为健康
+        HealthChanged,
+    }
+
+    /// 实例变更事件，携带变更后的实例（移除时为移除前）
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    pub struct InstanceChangeEvent {
+        pub op: InstanceOp,
+        pub instance: Instance,
+    }
+
+    impl InstanceChangeEvent {
+        /// 推送负载：实例字段与实例查询 API 一致
+        pub fn payload(&self) -> Value {
+            let i = &self.instance;
+            json!({
+                "topic": "instance",
+                "service_name": i.service.0,
+                "op": self.op,
+                "instance": {
+                    "id": i.id.0,
+                    "ip": i.ip,
+                    "port": i.port,
+                    "service_name": i.service.0,
+                    "group_name": i.group,
+                    "cluster_name": i.cluster,
+                    "weight": i.weight,
+                    "healthy": i.healthy,
+                    "metadata": i.metadata,
+                    "last_beat_time": i.last_beat_at.to_rfc3339(),
+                },
+            })
+        }
+    }
+}
+
+pub mod auth {
+    use serde::{Deserialize, Serialize};
+    use chrono::{DateTime, Utc};
+    use crate::config::ConfigKey;
+    use crate::pattern::glob_match;
+
+    /// 控制台 / API 用户；密码只保存 bcrypt 摘要
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    pub struct User {
+        pub username: String,
+        pub password_hash: String,
+        pub created_at: DateTime<Utc>,
+        pub updated_at: DateTime<Utc>,
+    }
+
+    /// 已签发的访问令牌
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    pub struct IssuedToken {
+        pub token: String,
+        /// 有效期，秒
+        pub ttl: i64,
+    }
+
+    /// 机器客户端的 AK/SK 凭据。通过角色绑定授权（绑定中的 username 即 access_key），
+    /// secret_key 用于请求签名，仅在创建与轮换时返回一次
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    pub struct AccessKey {
+        pub access_key: String,
+        pub secret_key: String,
+        /// 轮换前的密钥，在 previous_expires_at 之前仍可验签，便于客户端平滑切换
+        #[serde(default)]
+        pub previous_secret_key: Option<String>,
+        #[serde(default)]
+        pub previous_expires_at: Option<DateTime<Utc>>,
+        #[serde(default)]
+        pub desc: String,
+        /// 吊销后保留记录但拒绝所有签名
+        #[serde(default)]
+        pub revoked_at: Option<DateTime<Utc>>,
+        pub created_at: DateTime<Utc>,
+        pub updated_at: DateTime<Utc>,
+    }
+
+    pub const HEADER_ACCESS_KEY: &str = "X-Rustacos-AccessKey";
+    pub const HEADER_TIMESTAMP: &str = "X-Rustacos-Timestamp";
+    pub const HEADER_NONCE: &str = "X-Rustacos-Nonce";
+    pub const HEADER_SIGNATURE: &str = "X-Rustacos-Signature";
+
+    /// 签名原文：方法、路径、原样查询串、时间戳（Unix 秒）、nonce 与请求体 SHA-256（小写 hex）逐行拼接，
+    /// 签名为 `hex(HMAC-SHA256(secret_key, 原文))`
+    pub fn canonical_request(method: &str, path: &str, query: &str, timestamp: i64, nonce: &str, body_sha256: &str) -> String {
+        format!("{}\n{}\n{}\n{}\n{}\n{}", method.to_ascii_uppercase(), path, query, timestamp, nonce, body_sha256)
+    }
+
+    /// mTLS 连接上经 CA 校验的客户端证书，由监听层放入请求扩展
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct ClientCertificate {
+        /// RFC 4514 形式的主题，如 `CN=svc-a, O=acme`
+        pub subject: String,
+        pub common_name: Option<String>,
+    }
+
+    /// 证书身份的用户名前缀，与普通用户及 AK 区分
+    pub const CERT_PRINCIPAL_PREFIX: &str = "cert:";
+
+    impl ClientCertificate {
+        /// 参与授权的用户名：`cert:` 加主题 CN，无 CN 时用完整主题
+        pub fn principal(&self) -> String {
+            format!("{}{}", CERT_PRINCIPAL_PREFIX, self.common_name.as_deref().unwrap_or(&self.subject))
+        }
+    }
+
+    /// 内置全局管理员角色（同 Nacos），不受权限表约束，可管理用户、角色、权限、命名空间与 Webhook
+    pub const GLOBAL_ADMIN_ROLE: &str = "ROLE_ADMIN";
+
+    /// 用户与角色的绑定；角色本身不单独建表，存在绑定即存在
+    #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+    pub struct RoleBinding {
+        pub role: String,
+        pub username: String,
+    }
+
+    /// 读 `r`、写 `w` 或读写 `rw`
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+    pub enum Action {
+        #[serde(rename = "r")]
+        Read,
+        #[serde(rename = "w")]
+        Write,
+        #[serde(rename = "rw")]
+        ReadWrite,
+    }
+
+    impl Action {
+        /// 授予的动作是否覆盖请求的动作
+        pub fn covers(self, wanted: Action) -> bool {
+            self == Action::ReadWrite || self == wanted
+        }
+
+        pub fn as_str(self) -> &'static str {
+            match self {
+                Action::Read => "r",
+                Action::Write => "w",
+                Action::ReadWrite => "rw",
+            }
+        }
+    }
+
+    impl std::str::FromStr for Action {
+        type Err = String;
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            match s.trim() {
+                "r" => Ok(Action::Read),
+                "w" => Ok(Action::Write),
+                "rw" => Ok(Action::ReadWrite),
+                other => Err(format!("unknown action `{}`, expected r / w / rw", other)),
+            }
+        }
+    }
+
+    /// 受权限控制的资源
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum Resource {
+        Config(ConfigKey),
+        /// 注册中心尚不区分命名空间，服务统一归入 public
+        Service { group: String, service: String },
+    }
+
+    impl Resource {
+        /// 权限匹配用的路径：`命名空间:分组:config/dataId` 或 `public:分组:naming/服务名`
+        pub fn path(&self) -> String {
+            match self {
+                Resource::Config(k) => format!("{}:{}:config/{}", k.namespace, k.group, k.data_id),
+                Resource::Service { group, service } => format!("public:{}:naming/{}", group, service),
+            }
+        }
+    }
+
+    /// 角色在资源模式上的权限。resource 与 Nacos 相同为 `命名空间:分组:类型/名称`，
+    /// 每段支持 `*` / `?` 通配，缺省的段视为 `*`，如 `dev:*:*`、`public:DEFAULT_GROUP:config/app-*`
+    #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+    pub struct Permission {
+        pub role: String,
+        pub resource: String,
+        pub action: Action,
+    }
+
+    impl Permission {
+        pub fn grants(&self, resource: &Resource, action: Action) -> bool {
+            if !self.action.covers(action) {
+                return false;
+            }
+            let path = resource.path();
+            let mut wanted = path.splitn(3, ':');
+            let mut pattern = self.resource.splitn(3, ':');
+            (0..3).all(|_| glob_match(pattern.next().unwrap_or("*"), wanted.next().unwrap_or_default()))
+        }
+    }
+}
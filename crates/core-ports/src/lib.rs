@@ -1,53 +1,315 @@
+use std::fmt;
 use async_trait::async_trait;
-use core_model::config::{ConfigHistoryItem, ConfigItem, ConfigKey};
+use chrono::{DateTime, Utc};
+use core_model::audit::{AuditEntry, AuditFilter, AuditRecord};
+use core_model::auth::{AccessKey, Action, IssuedToken, Permission, RoleBinding, User};
+use core_model::changeset::{Changeset, ConfigBatch};
+use core_model::config::{BetaConfigItem, BetaRule, ConfigFilter, ConfigHistoryItem, ConfigItem, ConfigKey};
+use core_model::event::{ConfigChangeEvent, InstanceChangeEvent};
 use core_model::instance::{Instance, InstanceId, ServiceName};
+use core_model::listener::ConfigWatcher;
 use core_model::namespace::Namespace;
+use core_model::review::{ChangeRequest, ChangeRequestState};
+use core_model::schedule::{ScheduleState, ScheduledChange};
+use core_model::schema::ConfigSchema;
+use core_model::search::{ContentQuery, ContentSearchHit};
+use core_model::template::RefTarget;
+use core_model::webhook::{DeliveryState, Webhook, WebhookDelivery};
+
+/// 存储端口的失败原因；API 层据此映射 404 / 409 / 400 / 500，其余端口（加密、令牌等）仍返回 anyhow
+#[derive(Debug)]
+pub enum StoreError {
+    NotFound(String),
+    /// 已存在或与当前状态冲突
+    Conflict(String),
+    /// 查询条件或写入内容不合法
+    Invalid(String),
+    /// 底层存储读写失败
+    Backend(String),
+    /// 批量写入的前置条件不满足：这些配置在快照之后已被修改
+    Stale(Vec<ConfigKey>),
+}
+
+impl fmt::Display for StoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StoreError::NotFound(msg) | StoreError::Conflict(msg) | StoreError::Invalid(msg) => write!(f, "{}", msg),
+            StoreError::Backend(msg) => write!(f, "storage error: {}", msg),
+            StoreError::Stale(keys) => {
+                let keys: Vec<String> = keys
+                    .iter()
+                    .map(|k| format!("{}:{}:{}", k.namespace, k.group, k.data_id))
+                    .collect();
+                write!(f, "configs changed concurrently: {}", keys.join(", "))
+            }
+        }
+    }
+}
+
+impl std::error::Error for StoreError {}
+
+impl From<std::io::Error> for StoreError {
+    fn from(e: std::io::Error) -> Self {
+        StoreError::Backend(e.to_string())
+    }
+}
+
+pub type StoreResult<T> = Result<T, StoreError>;
 
 #[async_trait]
 pub trait ConfigStore: Send + Sync {
     async fn get(&self, key: &ConfigKey) -> Option<ConfigItem>;
-    async fn put(&self, item: ConfigItem) -> anyhow::Result<()>;
-    async fn delete(&self, key: &ConfigKey) -> anyhow::Result<bool>;
+    async fn put(&self, item: ConfigItem) -> StoreResult<()>;
+    async fn delete(&self, key: &ConfigKey) -> StoreResult<bool>;
     async fn list(
-        self: &Self,
+        &self,
         namespace: &str,
         page: u32,
         size: u32,
-        filter: Option<&str>,
-    ) -> anyhow::Result<(usize, Vec<ConfigItem>)>;
+        filter: &ConfigFilter,
+    ) -> StoreResult<(usize, Vec<ConfigItem>)>;
+    /// 按内容全文检索（子串或正则），按更新时间倒序返回至多 limit 条
+    async fn search_content(
+        &self,
+        namespace: &str,
+        query: &ContentQuery,
+        limit: usize,
+    ) -> StoreResult<Vec<ContentSearchHit>>;
+}
+
+/// 配置模板的依赖图：记录每条配置引用了哪些配置 / 变量
+#[async_trait]
+pub trait ConfigDependencyStore: Send + Sync {
+    /// 覆盖 key 的引用集合；空集合表示移除
+    async fn set_refs(&self, key: &ConfigKey, targets: Vec<RefTarget>);
+    /// 直接引用了 target 的配置
+    async fn dependents(&self, target: &RefTarget) -> Vec<ConfigKey>;
 }
 
 #[async_trait]
 pub trait ConfigHistoryStore: Send + Sync {
-    async fn append(&self, item: ConfigHistoryItem) -> anyhow::Result<()>;
-    async fn list(&self, key: &ConfigKey) -> anyhow::Result<Vec<ConfigHistoryItem>>;
+    async fn append(&self, item: ConfigHistoryItem) -> StoreResult<()>;
+    async fn list(&self, key: &ConfigKey) -> StoreResult<Vec<ConfigHistoryItem>>;
+}
+
+/// 多配置原子写入，跨 ConfigStore 与 ConfigHistoryStore
+#[async_trait]
+pub trait ConfigBatchWriter: Send + Sync {
+    /// 与写入同一临界区内校验 `batch.expected`，不满足时返回 `StoreError::Stale` 且不做任何写入
+    async fn commit(&self, batch: ConfigBatch) -> StoreResult<()>;
+    /// 分配变更集 revision：严格递增，且不小于当前秒级时间戳
+    async fn next_revision(&self) -> i64;
+}
+
+#[async_trait]
+pub trait ChangesetStore: Send + Sync {
+    async fn put_changeset(&self, cs: Changeset) -> StoreResult<()>;
+    async fn get_changeset(&self, id: &str) -> Option<Changeset>;
+    /// 按 revision 倒序
+    async fn list_changesets(&self, limit: usize) -> StoreResult<Vec<Changeset>>;
+}
+
+#[async_trait]
+pub trait ChangeRequestStore: Send + Sync {
+    async fn put_change_request(&self, cr: ChangeRequest) -> StoreResult<()>;
+    async fn get_change_request(&self, id: &str) -> Option<ChangeRequest>;
+    /// 按创建时间倒序
+    async fn list_change_requests(
+        &self,
+        namespace: Option<&str>,
+        state: Option<ChangeRequestState>,
+    ) -> StoreResult<Vec<ChangeRequest>>;
+}
+
+/// 在线监听登记：SSE 连接、长轮询等按连接 ID 登记所监听的配置
+#[async_trait]
+pub trait ConfigWatcherRegistry: Send + Sync {
+    /// 覆盖 connection_id 的监听集合
+    async fn set_watches(&self, connection_id: &str, watches: Vec<ConfigWatcher>);
+    async fn remove_watches(&self, connection_id: &str);
+    /// 刷新连接的 last_seen
+    async fn touch(&self, connection_id: &str);
+    async fn watchers_of(&self, key: &ConfigKey) -> Vec<ConfigWatcher>;
+    async fn watches_by_ip(&self, ip: &str) -> Vec<ConfigWatcher>;
+}
+
+/// 用户存储；持久化实现需在重启后保留用户与密码摘要
+#[async_trait]
+pub trait UserStore: Send + Sync {
+    async fn get_user(&self, username: &str) -> Option<User>;
+    async fn list_users(&self) -> StoreResult<Vec<User>>;
+    async fn put_user(&self, user: User) -> StoreResult<()>;
+    async fn delete_user(&self, username: &str) -> StoreResult<bool>;
+}
+
+/// 用户与角色的绑定
+#[async_trait]
+pub trait RoleStore: Send + Sync {
+    /// username 为 None 时列出全部绑定
+    async fn list_roles(&self, username: Option<&str>) -> StoreResult<Vec<RoleBinding>>;
+    /// 已绑定时返回 false
+    async fn add_role(&self, binding: RoleBinding) -> StoreResult<bool>;
+    /// 解除绑定；username 为 None 时删除该角色的全部绑定，返回删除条数
+    async fn delete_role(&self, role: &str, username: Option<&str>) -> StoreResult<usize>;
+}
+
+/// 角色的资源权限
+#[async_trait]
+pub trait PermissionStore: Send + Sync {
+    /// role 为 None 时列出全部权限
+    async fn list_permissions(&self, role: Option<&str>) -> StoreResult<Vec<Permission>>;
+    /// 已存在时返回 false
+    async fn add_permission(&self, permission: Permission) -> StoreResult<bool>;
+    async fn delete_permission(&self, role: &str, resource: &str, action: Action) -> StoreResult<bool>;
+}
+
+/// 机器客户端的 AK/SK 凭据
+#[async_trait]
+pub trait AccessKeyStore: Send + Sync {
+    async fn get_access_key(&self, access_key: &str) -> Option<AccessKey>;
+    async fn list_access_keys(&self) -> StoreResult<Vec<AccessKey>>;
+    async fn put_access_key(&self, key: AccessKey) -> StoreResult<()>;
+    async fn delete_access_key(&self, access_key: &str) -> StoreResult<bool>;
+}
+
+/// 签名请求的 nonce 去重，只需保留到时间窗口结束
+#[async_trait]
+pub trait NonceStore: Send + Sync {
+    /// 首次出现返回 true；expires_at 之前重复出现返回 false
+    async fn remember_nonce(&self, scope: &str, nonce: &str, expires_at: DateTime<Utc>) -> bool;
+}
+
+/// 密码摘要算法；耗时的计算由实现移出异步运行时
+#[async_trait]
+pub trait PasswordHasher: Send + Sync {
+    async fn hash(&self, password: &str) -> anyhow::Result<String>;
+    async fn verify(&self, password: &str, hash: &str) -> bool;
+    /// 与真实摘要同等成本、不对应任何用户的摘要；用户不存在时仍据此校验一次，避免按响应时间探测用户名
+    fn dummy_hash(&self) -> &str;
+}
+
+/// 访问令牌的签发与校验
+pub trait TokenService: Send + Sync {
+    fn issue(&self, username: &str) -> anyhow::Result<IssuedToken>;
+    /// 校验通过返回用户名
+    fn verify(&self, token: &str) -> anyhow::Result<String>;
+}
+
+/// 只追加的审计日志。实现需在同一把锁内取链尾并追加（见 `AuditEntry::chain`），不提供修改与删除
+#[async_trait]
+pub trait AuditStore: Send + Sync {
+    async fn append_audit(&self, record: AuditRecord) -> StoreResult<AuditEntry>;
+    /// 按序号倒序分页，返回命中总数与本页
+    async fn query_audit(&self, filter: &AuditFilter, offset: usize, limit: usize) -> StoreResult<(usize, Vec<AuditEntry>)>;
+    /// 按序号升序返回全部记录，用于校验哈希链
+    async fn audit_chain(&self) -> StoreResult<Vec<AuditEntry>>;
+}
+
+/// Webhook 登记与投递记录
+#[async_trait]
+pub trait WebhookStore: Send + Sync {
+    async fn put_webhook(&self, hook: Webhook) -> StoreResult<()>;
+    async fn get_webhook(&self, id: &str) -> Option<Webhook>;
+    async fn list_webhooks(&self) -> StoreResult<Vec<Webhook>>;
+    async fn delete_webhook(&self, id: &str) -> StoreResult<bool>;
+    /// 实现可按条数或时长清理已投递的记录，待投递与死信须保留
+    async fn put_delivery(&self, delivery: WebhookDelivery) -> StoreResult<()>;
+    async fn get_delivery(&self, id: &str) -> Option<WebhookDelivery>;
+    /// 按创建时间倒序，最多 limit 条
+    async fn list_deliveries(
+        &self,
+        webhook_id: Option<&str>,
+        state: Option<DeliveryState>,
+        limit: usize,
+    ) -> StoreResult<Vec<WebhookDelivery>>;
+}
+
+/// 定时发布任务；持久化实现需保证重启后未完成的任务仍可被调度
+#[async_trait]
+pub trait ScheduledChangeStore: Send + Sync {
+    async fn put_scheduled(&self, change: ScheduledChange) -> StoreResult<()>;
+    async fn get_scheduled(&self, id: &str) -> Option<ScheduledChange>;
+    /// 按生效时间升序
+    async fn list_scheduled(
+        &self,
+        namespace: Option<&str>,
+        state: Option<ScheduleState>,
+    ) -> StoreResult<Vec<ScheduledChange>>;
+}
+
+#[async_trait]
+pub trait BetaConfigStore: Send + Sync {
+    async fn get_beta(&self, key: &ConfigKey) -> Option<BetaConfigItem>;
+    async fn put_beta(&self, item: BetaConfigItem) -> StoreResult<()>;
+    async fn delete_beta(&self, key: &ConfigKey) -> StoreResult<bool>;
 }
 
 #[async_trait]
 pub trait InstanceStore: Send + Sync {
-    async fn register(&self, ins: Instance) -> anyhow::Result<()>;
-    async fn deregister(&self, service: &ServiceName, id: &InstanceId) -> anyhow::Result<bool>;
-    async fn beat(&self, service: &ServiceName, id: &InstanceId) -> anyhow::Result<bool>;
-    async fn list(&self, service: Option<&ServiceName>) -> anyhow::Result<Vec<Instance>>;
+    async fn register(&self, ins: Instance) -> StoreResult<()>;
+    async fn deregister(&self, service: &ServiceName, id: &InstanceId) -> StoreResult<bool>;
+    async fn beat(&self, service: &ServiceName, id: &InstanceId) -> StoreResult<bool>;
+    async fn list(&self, service: Option<&ServiceName>) -> StoreResult<Vec<Instance>>;
+    /// 将最近心跳早于 before 的健康实例标记为不健康，返回被标记的实例
+    async fn expire(&self, before: DateTime<Utc>) -> StoreResult<Vec<Instance>>;
 }
 
 #[async_trait]
 pub trait NamespaceStore: Send + Sync {
-    async fn create(&self, ns: Namespace) -> anyhow::Result<()>;
-    async fn update(&self, ns: Namespace) -> anyhow::Result<bool>;
-    async fn delete(&self, id: &str) -> anyhow::Result<bool>;
-    async fn list(&self) -> anyhow::Result<Vec<Namespace>>;
+    async fn get(&self, id: &str) -> Option<Namespace>;
+    async fn create(&self, ns: Namespace) -> StoreResult<()>;
+    async fn update(&self, ns: Namespace) -> StoreResult<bool>;
+    async fn delete(&self, id: &str) -> StoreResult<bool>;
+    async fn list(&self) -> StoreResult<Vec<Namespace>>;
+}
+
+#[async_trait]
+pub trait SchemaStore: Send + Sync {
+    async fn put(&self, schema: ConfigSchema) -> StoreResult<()>;
+    async fn get(&self, id: &str) -> Option<ConfigSchema>;
+    async fn delete(&self, id: &str) -> StoreResult<bool>;
+    async fn list(&self, namespace: Option<&str>) -> StoreResult<Vec<ConfigSchema>>;
+}
+
+/// 配置加密插件，按 data_id 前缀 `cipher-{name}-` 选用
+pub trait ConfigCipher: Send + Sync {
+    fn name(&self) -> &str;
+    /// 数据密钥引用，随密文一同存储与导出
+    fn key_ref(&self) -> String;
+    /// aad 为关联数据，参与认证但不加密；解密时须传入相同的 aad
+    fn encrypt(&self, plain: &str, aad: &[u8]) -> anyhow::Result<String>;
+    fn decrypt(&self, cipher: &str, aad: &[u8]) -> anyhow::Result<String>;
 }
 
 #[async_trait]
 pub trait Notifier: Send + Sync {
     async fn notify_config_change(&self, key: &ConfigKey);
+    /// 灰度变更，仅需命中规则的订阅者感知；默认按普通变更处理
+    async fn notify_config_beta_change(&self, key: &ConfigKey, _rule: &BetaRule) {
+        self.notify_config_change(key).await
+    }
+    /// 变更集内的多条配置合并为一次通知；默认逐条通知
+    async fn notify_config_batch(&self, _changeset: &str, _revision: i64, keys: &[ConfigKey]) {
+        for key in keys {
+            self.notify_config_change(key).await;
+        }
+    }
+    /// 带操作类型、版本、MD5 与操作人的配置事件；默认退化为仅通知 key
+    async fn notify_config_event(&self, event: &ConfigChangeEvent) {
+        self.notify_config_change(&event.key).await
+    }
     async fn notify_instance_change(&self, service: &ServiceName);
+    /// 带实例详情的实例事件；默认退化为仅通知服务名
+    async fn notify_instance_event(&self, event: &InstanceChangeEvent) {
+        self.notify_instance_change(&event.instance.service).await
+    }
 }
 
 #[async_trait]
 pub trait SchedulerPort: Send + Sync {
     async fn schedule_heartbeat_cleanup(&self);
+    /// 周期性执行到期的定时发布与窗口回退
+    async fn schedule_config_publishes(&self);
 }
 
 
@@ -0,0 +1,44 @@
+use core_model::audit::AUDIT_GENESIS_HASH;
+use core_ports::AuditStore;
+
+/// 哈希链校验结果
+#[derive(Debug)]
+pub struct ChainReport {
+    pub total: usize,
+    /// 第一条校验失败的记录序号，链完整时为 None
+    pub broken_at: Option<u64>,
+    pub reason: Option<String>,
+    /// 链尾 hash，可定期抄送到外部系统，便于发现整段截断
+    pub head_hash: Option<String>,
+}
+
+/// 逐条重算哈希并核对序号与前后衔接，发现篡改、删除或插入时指出第一处断点
+pub struct VerifyAuditChain<'a> {
+    pub store: &'a dyn AuditStore,
+}
+
+impl<'a> VerifyAuditChain<'a> {
+    pub async fn exec(&self) -> anyhow::Result<ChainReport> {
+        let entries = self.store.audit_chain().await?;
+        let mut prev_hash = AUDIT_GENESIS_HASH;
+        let mut broken = None;
+        for (i, e) in entries.iter().enumerate() {
+            let reason = if e.seq != i as u64 + 1 {
+                Some(format!("expected seq {}, found {}", i + 1, e.seq))
+            } else if e.prev_hash != prev_hash {
+                Some("prev_hash does not match the previous entry".to_string())
+            } else if !e.is_intact() {
+                Some("hash does not match the entry content".to_string())
+            } else {
+                None
+            };
+            if let Some(reason) = reason {
+                broken = Some((e.seq, reason));
+                break;
+            }
+            prev_hash = &e.hash;
+        }
+        let (broken_at, reason) = broken.unzip();
+        Ok(ChainReport { total: entries.len(), broken_at, reason, head_hash: entries.last().map(|e| e.hash.clone()) })
+    }
+}
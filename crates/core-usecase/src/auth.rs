@@ -0,0 +1,473 @@
+use std::fmt;
+
+use chrono::{Duration, Utc};
+use core_model::auth::{
+    canonical_request, AccessKey, Action, IssuedToken, Permission, Resource, RoleBinding, User, CERT_PRINCIPAL_PREFIX,
+    GLOBAL_ADMIN_ROLE,
+};
+use core_ports::{AccessKeyStore, NonceStore, PasswordHasher, PermissionStore, RoleStore, TokenService, UserStore};
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+
+/// 认证、授权与用户管理失败的原因，API 层据此映射 401 / 403 / 404 / 409 / 400
+#[derive(Debug)]
+pub enum AuthError {
+    /// 用户不存在或密码错误，不区分以免泄露用户名
+    InvalidCredentials,
+    /// 令牌缺失、过期或签名不符
+    Unauthorized(String),
+    /// 已登录但缺少所需权限
+    Forbidden(String),
+    UserNotFound,
+    UserExists,
+    AccessKeyNotFound,
+    Invalid(String),
+}
+
+impl fmt::Display for AuthError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AuthError::InvalidCredentials => write!(f, "unknown user or wrong password"),
+            AuthError::Unauthorized(msg) => write!(f, "{}", msg),
+            AuthError::Forbidden(msg) => write!(f, "{}", msg),
+            AuthError::UserNotFound => write!(f, "user not found"),
+            AuthError::UserExists => write!(f, "user already exists"),
+            AuthError::AccessKeyNotFound => write!(f, "access key not found"),
+            AuthError::Invalid(msg) => write!(f, "{}", msg),
+        }
+    }
+}
+
+impl std::error::Error for AuthError {}
+
+fn check_password(password: &str) -> Result<(), AuthError> {
+    if password.chars().count() < 6 {
+        return Err(AuthError::Invalid("password must be at least 6 characters".into()));
+    }
+    Ok(())
+}
+
+/// 用户名密码登录，成功返回访问令牌
+pub struct Login<'a> {
+    pub users: &'a dyn UserStore,
+    pub hasher: &'a dyn PasswordHasher,
+    pub tokens: &'a dyn TokenService,
+}
+
+impl<'a> Login<'a> {
+    pub async fn exec(&self, username: &str, password: &str) -> anyhow::Result<IssuedToken> {
+        // 用户不存在时同样校验一次占位摘要，使两种失败的耗时一致
+        let Some(user) = self.users.get_user(username).await else {
+            self.hasher.verify(password, self.hasher.dummy_hash()).await;
+            return Err(AuthError::InvalidCredentials.into());
+        };
+        if !self.hasher.verify(password, &user.password_hash).await {
+            return Err(AuthError::InvalidCredentials.into());
+        }
+        self.tokens.issue(&user.username)
+    }
+}
+
+/// 校验访问令牌，返回用户名；用户已被删除的令牌同样拒绝
+pub struct VerifyToken<'a> {
+    pub users: &'a dyn UserStore,
+    pub tokens: &'a dyn TokenService,
+}
+
+impl<'a> VerifyToken<'a> {
+    pub async fn exec(&self, token: &str) -> anyhow::Result<String> {
+        let username = self
+            .tokens
+            .verify(token)
+            .map_err(|e| AuthError::Unauthorized(format!("invalid access token: {}", e)))?;
+        if self.users.get_user(&username).await.is_none() {
+            return Err(AuthError::Unauthorized("user no longer exists".into()).into());
+        }
+        Ok(username)
+    }
+}
+
+pub struct CreateUser<'a> {
+    pub users: &'a dyn UserStore,
+    pub hasher: &'a dyn PasswordHasher,
+}
+
+impl<'a> CreateUser<'a> {
+    pub async fn exec(&self, username: &str, password: &str) -> anyhow::Result<User> {
+        let username = username.trim();
+        if username.is_empty() || username.contains(char::is_whitespace) {
+            return Err(AuthError::Invalid("username must be non-empty without whitespace".into()).into());
+        }
+        if username.starts_with(CERT_PRINCIPAL_PREFIX) {
+            return Err(AuthError::Invalid(format!("username must not start with `{}`", CERT_PRINCIPAL_PREFIX)).into());
+        }
+        check_password(password)?;
+        if self.users.get_user(username).await.is_some() {
+            return Err(AuthError::UserExists.into());
+        }
+        let now = Utc::now();
+        let user = User {
+            username: username.to_string(),
+            password_hash: self.hasher.hash(password).await?,
+            created_at: now,
+            updated_at: now,
+        };
+        self.users.put_user(user.clone()).await?;
+        Ok(user)
+    }
+}
+
+pub struct ChangePassword<'a> {
+    pub users: &'a dyn UserStore,
+    pub hasher: &'a dyn PasswordHasher,
+}
+
+impl<'a> ChangePassword<'a> {
+    pub async fn exec(&self, username: &str, password: &str) -> anyhow::Result<User> {
+        check_password(password)?;
+        let mut user = self.users.get_user(username).await.ok_or(AuthError::UserNotFound)?;
+        user.password_hash = self.hasher.hash(password).await?;
+        user.updated_at = Utc::now();
+        self.users.put_user(user.clone()).await?;
+        Ok(user)
+    }
+}
+
+/// 删除用户并解除其角色绑定；不允许删除最后一个用户或最后一个全局管理员
+pub struct DeleteUser<'a> {
+    pub users: &'a dyn UserStore,
+    pub roles: &'a dyn RoleStore,
+}
+
+impl<'a> DeleteUser<'a> {
+    pub async fn exec(&self, username: &str) -> anyhow::Result<()> {
+        if self.users.get_user(username).await.is_none() {
+            return Err(AuthError::UserNotFound.into());
+        }
+        if self.users.list_users().await?.len() <= 1 {
+            return Err(AuthError::Invalid("cannot delete the last user".into()).into());
+        }
+        let bindings = self.roles.list_roles(Some(username)).await?;
+        if bindings.iter().any(|b| b.role == GLOBAL_ADMIN_ROLE) && admin_count(self.roles).await? <= 1 {
+            return Err(AuthError::Invalid("cannot delete the last global admin".into()).into());
+        }
+        self.users.delete_user(username).await?;
+        for b in bindings {
+            self.roles.delete_role(&b.role, Some(username)).await?;
+        }
+        Ok(())
+    }
+}
+
+async fn admin_count(roles: &dyn RoleStore) -> anyhow::Result<usize> {
+    Ok(roles.list_roles(None).await?.iter().filter(|b| b.role == GLOBAL_ADMIN_ROLE).count())
+}
+
+/// 用户当前拥有的权限，按请求加载一次后用于逐项判断与列表过滤
+#[derive(Debug, Clone, Default)]
+pub struct Grants {
+    pub admin: bool,
+    pub permissions: Vec<Permission>,
+}
+
+impl Grants {
+    pub fn allows(&self, resource: &Resource, action: Action) -> bool {
+        self.admin || self.permissions.iter().any(|p| p.grants(resource, action))
+    }
+}
+
+/// 汇总用户各角色的权限
+pub struct LoadGrants<'a> {
+    pub roles: &'a dyn RoleStore,
+    pub permissions: &'a dyn PermissionStore,
+}
+
+impl<'a> LoadGrants<'a> {
+    pub async fn exec(&self, username: &str) -> anyhow::Result<Grants> {
+        let mut grants = Grants::default();
+        for binding in self.roles.list_roles(Some(username)).await? {
+            if binding.role == GLOBAL_ADMIN_ROLE {
+                grants.admin = true;
+            }
+            grants.permissions.extend(self.permissions.list_permissions(Some(&binding.role)).await?);
+        }
+        Ok(grants)
+    }
+}
+
+fn check_role(role: &str) -> Result<(), AuthError> {
+    if role.trim().is_empty() || role.contains(char::is_whitespace) {
+        return Err(AuthError::Invalid("role must be non-empty without whitespace".into()));
+    }
+    Ok(())
+}
+
+/// 为用户、AK 或客户端证书身份（`cert:` 前缀，无需预先登记）绑定角色；角色在首次绑定时创建
+pub struct BindRole<'a> {
+    pub users: &'a dyn UserStore,
+    pub keys: &'a dyn AccessKeyStore,
+    pub roles: &'a dyn RoleStore,
+}
+
+impl<'a> BindRole<'a> {
+    pub async fn exec(&self, role: &str, username: &str) -> anyhow::Result<RoleBinding> {
+        check_role(role)?;
+        if let Some(subject) = username.strip_prefix(CERT_PRINCIPAL_PREFIX) {
+            if subject.trim().is_empty() {
+                return Err(AuthError::Invalid("certificate principal must name a subject".into()).into());
+            }
+        } else if self.users.get_user(username).await.is_none() && self.keys.get_access_key(username).await.is_none() {
+            return Err(AuthError::UserNotFound.into());
+        }
+        let binding = RoleBinding { role: role.to_string(), username: username.to_string() };
+        if !self.roles.add_role(binding.clone()).await? {
+            return Err(AuthError::Invalid(format!("user {} already has role {}", username, role)).into());
+        }
+        Ok(binding)
+    }
+}
+
+/// 解除绑定；不指定用户时删除整个角色及其权限。最后一个全局管理员不可解除
+pub struct DeleteRole<'a> {
+    pub roles: &'a dyn RoleStore,
+    pub permissions: &'a dyn PermissionStore,
+}
+
+impl<'a> DeleteRole<'a> {
+    pub async fn exec(&self, role: &str, username: Option<&str>) -> anyhow::Result<usize> {
+        if role == GLOBAL_ADMIN_ROLE {
+            let remaining = match username {
+                Some(u) => {
+                    let bound = self.roles.list_roles(Some(u)).await?.iter().any(|b| b.role == role);
+                    admin_count(self.roles).await? - usize::from(bound)
+                }
+                None => 0,
+            };
+            if remaining == 0 {
+                return Err(AuthError::Invalid("cannot remove the last global admin".into()).into());
+            }
+        }
+        let removed = self.roles.delete_role(role, username).await?;
+        if username.is_none() {
+            for p in self.permissions.list_permissions(Some(role)).await? {
+                self.permissions.delete_permission(&p.role, &p.resource, p.action).await?;
+            }
+        }
+        Ok(removed)
+    }
+}
+
+/// 为已存在的角色授予资源权限
+pub struct GrantPermission<'a> {
+    pub roles: &'a dyn RoleStore,
+    pub permissions: &'a dyn PermissionStore,
+}
+
+impl<'a> GrantPermission<'a> {
+    pub async fn exec(&self, role: &str, resource: &str, action: Action) -> anyhow::Result<Permission> {
+        if role == GLOBAL_ADMIN_ROLE {
+            return Err(AuthError::Invalid(format!("{} already has every permission", GLOBAL_ADMIN_ROLE)).into());
+        }
+        let resource = resource.trim();
+        if resource.is_empty() || resource.contains(char::is_whitespace) {
+            return Err(AuthError::Invalid("resource must be non-empty without whitespace".into()).into());
+        }
+        if !self.roles.list_roles(None).await?.iter().any(|b| b.role == role) {
+            return Err(AuthError::Invalid(format!("role {} does not exist", role)).into());
+        }
+        let permission = Permission { role: role.to_string(), resource: resource.to_string(), action };
+        if !self.permissions.add_permission(permission.clone()).await? {
+            return Err(AuthError::Invalid("permission already exists".into()).into());
+        }
+        Ok(permission)
+    }
+}
+
+/// 用户表为空时创建初始管理员；没有任何全局管理员时为该用户补绑 ROLE_ADMIN。
+/// 返回是否新建了用户
+pub async fn seed_admin(
+    users: &dyn UserStore,
+    roles: &dyn RoleStore,
+    hasher: &dyn PasswordHasher,
+    username: &str,
+    password: &str,
+) -> anyhow::Result<bool> {
+    let created = users.list_users().await?.is_empty();
+    if created {
+        let now = Utc::now();
+        users
+            .put_user(User {
+                username: username.to_string(),
+                password_hash: hasher.hash(password).await?,
+                created_at: now,
+                updated_at: now,
+            })
+            .await?;
+    }
+    if admin_count(roles).await? == 0 && users.get_user(username).await.is_some() {
+        roles.add_role(RoleBinding { role: GLOBAL_ADMIN_ROLE.to_string(), username: username.to_string() }).await?;
+    }
+    Ok(created)
+}
+
+// -------------------- AK/SK --------------------
+/// 请求体的 SHA-256，小写 hex
+pub fn body_sha256(body: &[u8]) -> String {
+    hex::encode(Sha256::digest(body))
+}
+
+fn signing_mac(secret: &str, canonical: &str) -> Hmac<Sha256> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("hmac accepts any key length");
+    mac.update(canonical.as_bytes());
+    mac
+}
+
+/// `hex(HMAC-SHA256(secret_key, canonical_request))`
+pub fn request_signature(secret: &str, canonical: &str) -> String {
+    hex::encode(signing_mac(secret, canonical).finalize().into_bytes())
+}
+
+/// 签名请求中参与验签的部分
+pub struct SignedRequest<'a> {
+    pub access_key: &'a str,
+    pub signature: &'a str,
+    pub method: &'a str,
+    pub path: &'a str,
+    pub query: &'a str,
+    pub timestamp: i64,
+    pub nonce: &'a str,
+    pub body: &'a [u8],
+}
+
+/// 校验 AK/SK 签名，返回 access_key。时间戳须在窗口内，同一 nonce 在窗口内只能使用一次
+pub struct VerifySignature<'a> {
+    pub keys: &'a dyn AccessKeyStore,
+    pub nonces: &'a dyn NonceStore,
+    /// 允许的时钟偏差，秒
+    pub window_secs: i64,
+}
+
+impl<'a> VerifySignature<'a> {
+    pub async fn exec(&self, req: &SignedRequest<'_>) -> anyhow::Result<String> {
+        let unauthorized = |msg: &str| AuthError::Unauthorized(msg.to_string());
+        let key = self.keys.get_access_key(req.access_key).await.ok_or_else(|| unauthorized("unknown access key"))?;
+        if key.revoked_at.is_some() {
+            return Err(unauthorized("access key has been revoked").into());
+        }
+        let now = Utc::now();
+        if (now.timestamp() - req.timestamp).abs() > self.window_secs {
+            return Err(unauthorized("request timestamp outside the allowed window").into());
+        }
+        if req.nonce.is_empty() || req.nonce.len() > 64 {
+            return Err(unauthorized("nonce must be 1 to 64 characters").into());
+        }
+        let signature = hex::decode(req.signature).map_err(|_| unauthorized("malformed signature"))?;
+        let canonical = canonical_request(req.method, req.path, req.query, req.timestamp, req.nonce, &body_sha256(req.body));
+        // 轮换宽限期内旧密钥仍然有效
+        let previous = key.previous_secret_key.as_deref().filter(|_| key.previous_expires_at.is_some_and(|t| t > now));
+        let matched = std::iter::once(key.secret_key.as_str())
+            .chain(previous)
+            .any(|secret| signing_mac(secret, &canonical).verify_slice(&signature).is_ok());
+        if !matched {
+            return Err(unauthorized("signature mismatch").into());
+        }
+        // 验签通过后才登记 nonce，伪造请求无法占用合法客户端的 nonce
+        let expires_at = now + Duration::seconds(self.window_secs * 2);
+        if !self.nonces.remember_nonce(&key.access_key, req.nonce, expires_at).await {
+            return Err(unauthorized("nonce has already been used").into());
+        }
+        Ok(key.access_key)
+    }
+}
+
+/// 创建 AK/SK 并绑定角色
+pub struct CreateAccessKey<'a> {
+    pub keys: &'a dyn AccessKeyStore,
+    pub roles: &'a dyn RoleStore,
+}
+
+impl<'a> CreateAccessKey<'a> {
+    pub async fn exec(&self, access_key: String, secret_key: String, desc: String, roles: &[String]) -> anyhow::Result<AccessKey> {
+        for role in roles {
+            check_role(role)?;
+        }
+        let now = Utc::now();
+        let key = AccessKey {
+            access_key,
+            secret_key,
+            previous_secret_key: None,
+            previous_expires_at: None,
+            desc,
+            revoked_at: None,
+            created_at: now,
+            updated_at: now,
+        };
+        self.keys.put_access_key(key.clone()).await?;
+        for role in roles {
+            self.roles.add_role(RoleBinding { role: role.clone(), username: key.access_key.clone() }).await?;
+        }
+        Ok(key)
+    }
+}
+
+/// 轮换密钥；旧密钥在 grace_secs 内仍可验签，为 0 时立即失效
+pub struct RotateAccessKey<'a> {
+    pub keys: &'a dyn AccessKeyStore,
+}
+
+impl<'a> RotateAccessKey<'a> {
+    pub async fn exec(&self, access_key: &str, secret_key: String, grace_secs: i64) -> anyhow::Result<AccessKey> {
+        let mut key = self.keys.get_access_key(access_key).await.ok_or(AuthError::AccessKeyNotFound)?;
+        if key.revoked_at.is_some() {
+            return Err(AuthError::Invalid("access key has been revoked".into()).into());
+        }
+        let now = Utc::now();
+        let old = std::mem::replace(&mut key.secret_key, secret_key);
+        (key.previous_secret_key, key.previous_expires_at) = if grace_secs > 0 {
+            (Some(old), Some(now + Duration::seconds(grace_secs)))
+        } else {
+            (None, None)
+        };
+        key.updated_at = now;
+        self.keys.put_access_key(key.clone()).await?;
+        Ok(key)
+    }
+}
+
+/// 吊销后立即拒绝该 AK 的所有签名（含轮换宽限期内的旧密钥），记录保留备查
+pub struct RevokeAccessKey<'a> {
+    pub keys: &'a dyn AccessKeyStore,
+}
+
+impl<'a> RevokeAccessKey<'a> {
+    pub async fn exec(&self, access_key: &str) -> anyhow::Result<AccessKey> {
+        let mut key = self.keys.get_access_key(access_key).await.ok_or(AuthError::AccessKeyNotFound)?;
+        if key.revoked_at.is_none() {
+            let now = Utc::now();
+            key.revoked_at = Some(now);
+            key.previous_secret_key = None;
+            key.previous_expires_at = None;
+            key.updated_at = now;
+            self.keys.put_access_key(key.clone()).await?;
+        }
+        Ok(key)
+    }
+}
+
+/// 删除 AK 并解除其角色绑定
+pub struct DeleteAccessKey<'a> {
+    pub keys: &'a dyn AccessKeyStore,
+    pub roles: &'a dyn RoleStore,
+}
+
+impl<'a> DeleteAccessKey<'a> {
+    pub async fn exec(&self, access_key: &str) -> anyhow::Result<()> {
+        if !self.keys.delete_access_key(access_key).await? {
+            return Err(AuthError::AccessKeyNotFound.into());
+        }
+        for b in self.roles.list_roles(Some(access_key)).await? {
+            self.roles.delete_role(&b.role, Some(access_key)).await?;
+        }
+        Ok(())
+    }
+}
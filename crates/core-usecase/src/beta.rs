@@ -0,0 +1,124 @@
+use chrono::Utc;
+use core_model::client::ClientInfo;
+use core_model::config::{BetaConfigItem, BetaRule, ConfigItem, ConfigKey, ConfigMeta};
+use core_ports::{BetaConfigStore, ConfigCipher, ConfigStore, Notifier, SchemaStore};
+
+use crate::cipher::{open, seal};
+use crate::config::PublishConfig;
+use crate::schema::ValidateConfig;
+
+pub struct PublishBeta<'a> {
+    pub beta: &'a dyn BetaConfigStore,
+    pub notifier: Option<&'a dyn Notifier>,
+    pub schemas: Option<&'a dyn SchemaStore>,
+    pub cipher: Option<&'a dyn ConfigCipher>,
+}
+
+impl<'a> PublishBeta<'a> {
+    pub async fn exec(
+        &self,
+        key: ConfigKey,
+        content: String,
+        format: Option<String>,
+        rule: BetaRule,
+        actor: Option<String>,
+    ) -> anyhow::Result<()> {
+        if rule.ips.is_empty() && rule.labels.is_empty() {
+            return Err(anyhow::anyhow!("beta rule requires at least one ip or label"));
+        }
+        if let Some(schemas) = self.schemas {
+            ValidateConfig { schemas }
+                .exec(&key, &content, format.as_deref())
+                .await?;
+        }
+        let (content, data_key) = seal(self.cipher, &key, content)?;
+        let item = BetaConfigItem {
+            key: key.clone(),
+            content,
+            format,
+            rule: rule.clone(),
+            updated_at: Utc::now(),
+            updated_by: actor,
+            data_key,
+        };
+        self.beta.put_beta(item).await?;
+        if let Some(n) = self.notifier {
+            n.notify_config_beta_change(&key, &rule).await;
+        }
+        Ok(())
+    }
+}
+
+/// 灰度转正式：以灰度内容执行正式发布，并清除灰度版本
+pub struct PromoteBeta<'a> {
+    pub publish: PublishConfig<'a>,
+    pub beta: &'a dyn BetaConfigStore,
+}
+
+impl<'a> PromoteBeta<'a> {
+    /// 返回 false 表示不存在灰度版本
+    pub async fn exec(&self, key: ConfigKey, actor: Option<String>) -> anyhow::Result<bool> {
+        let Some(item) = self.beta.get_beta(&key).await else {
+            return Ok(false);
+        };
+        // 灰度内容可能是密文，先还原再走正式发布（会重新加密）
+        let content = open(self.publish.cipher, &key, &item.content, item.data_key.as_deref())?;
+        self.publish
+            .exec(key.clone(), content, item.format, ConfigMeta::default(), actor)
+            .await?;
+        self.beta.delete_beta(&key).await?;
+        Ok(true)
+    }
+}
+
+/// 停止灰度：删除灰度版本，命中规则的客户端回落到正式版本
+pub struct StopBeta<'a> {
+    pub beta: &'a dyn BetaConfigStore,
+    pub notifier: Option<&'a dyn Notifier>,
+}
+
+impl<'a> StopBeta<'a> {
+    pub async fn exec(&self, key: &ConfigKey) -> anyhow::Result<bool> {
+        let Some(item) = self.beta.get_beta(key).await else {
+            return Ok(false);
+        };
+        self.beta.delete_beta(key).await?;
+        if let Some(n) = self.notifier {
+            n.notify_config_beta_change(key, &item.rule).await;
+        }
+        Ok(true)
+    }
+}
+
+/// 按客户端解析应下发的版本，返回 (配置, 是否灰度)
+pub async fn resolve_config(
+    store: &dyn ConfigStore,
+    beta: &dyn BetaConfigStore,
+    key: &ConfigKey,
+    client: &ClientInfo,
+) -> Option<(ConfigItem, bool)> {
+    let release = store.get(key).await;
+    if let Some(b) = beta.get_beta(key).await {
+        if b.rule.matches(client) {
+            // 灰度版本沿用正式版本的元数据
+            let (tags, desc, app_name) = match &release {
+                Some(r) => (r.tags.clone(), r.desc.clone(), r.app_name.clone()),
+                None => (vec![], None, None),
+            };
+            let item = ConfigItem {
+                key: b.key,
+                content: b.content,
+                format: b.format,
+                updated_at: b.updated_at,
+                updated_by: b.updated_by,
+                version_ts: b.updated_at.timestamp(),
+                tags,
+                desc,
+                app_name,
+                data_key: b.data_key,
+            };
+            return Some((item, true));
+        }
+    }
+    release.map(|c| (c, false))
+}
@@ -0,0 +1,296 @@
+use std::collections::HashSet;
+use std::fmt;
+
+use chrono::Utc;
+use core_model::changeset::{Changeset, ChangesetEntry, ConfigBatch};
+use core_model::config::{ConfigHistoryItem, ConfigItem, ConfigKey, ConfigMeta};
+use core_model::template::{self, RefTarget};
+use core_ports::{
+    ChangesetStore, ConfigBatchWriter, ConfigCipher, ConfigDependencyStore, ConfigStore, Notifier,
+    SchemaStore, StoreError,
+};
+
+use crate::cipher::{open, seal};
+use crate::schema::ValidateConfig;
+use crate::template::collect_dependents;
+
+pub enum ChangeOp {
+    Put {
+        key: ConfigKey,
+        content: String,
+        format: Option<String>,
+        meta: ConfigMeta,
+    },
+    Delete {
+        key: ConfigKey,
+    },
+}
+
+impl ChangeOp {
+    fn key(&self) -> &ConfigKey {
+        match self {
+            ChangeOp::Put { key, .. } | ChangeOp::Delete { key } => key,
+        }
+    }
+}
+
+/// 变更集校验失败，任何一项不通过都不会写入；API 层据此返回 400
+#[derive(Debug)]
+pub struct ChangesetError {
+    pub errors: Vec<(ConfigKey, String)>,
+}
+
+impl fmt::Display for ChangesetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "changeset rejected")?;
+        for (i, (k, msg)) in self.errors.iter().enumerate() {
+            let sep = if i == 0 { ": " } else { "; " };
+            write!(f, "{}{}:{}:{} {}", sep, k.namespace, k.group, k.data_id, msg)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ChangesetError {}
+
+/// 目标配置已被并发或后续变更修改（回滚冲突、提交时快照失效）；API 层据此返回 409
+#[derive(Debug)]
+pub struct ChangesetConflict {
+    pub keys: Vec<ConfigKey>,
+}
+
+impl fmt::Display for ChangesetConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let keys: Vec<String> = self
+            .keys
+            .iter()
+            .map(|k| format!("{}:{}:{}", k.namespace, k.group, k.data_id))
+            .collect();
+        write!(f, "configs changed since the changeset was applied: {}", keys.join(", "))
+    }
+}
+
+impl std::error::Error for ChangesetConflict {}
+
+/// 原子应用 / 回滚多配置变更集：先整体校验，再一次性提交配置与历史，最后合并通知
+pub struct ApplyChangeset<'a> {
+    pub store: &'a dyn ConfigStore,
+    pub writer: &'a dyn ConfigBatchWriter,
+    pub changesets: &'a dyn ChangesetStore,
+    pub notifier: Option<&'a dyn Notifier>,
+    pub schemas: Option<&'a dyn SchemaStore>,
+    pub cipher: Option<&'a dyn ConfigCipher>,
+    pub deps: Option<&'a dyn ConfigDependencyStore>,
+}
+
+impl<'a> ApplyChangeset<'a> {
+    pub async fn exec(
+        &self,
+        id: String,
+        ops: Vec<ChangeOp>,
+        description: Option<String>,
+        actor: Option<String>,
+    ) -> anyhow::Result<Changeset> {
+        if ops.is_empty() {
+            return Err(anyhow::anyhow!("changeset has no operations"));
+        }
+        let revision = self.writer.next_revision().await;
+        let mut errors = Vec::new();
+        let mut seen = HashSet::new();
+        let mut entries = Vec::with_capacity(ops.len());
+        let mut refs = Vec::with_capacity(ops.len());
+        for op in ops {
+            let key = op.key().clone();
+            if !seen.insert(key.clone()) {
+                errors.push((key, "appears more than once".to_string()));
+                continue;
+            }
+            let before = self.store.get(&key).await;
+            match op {
+                ChangeOp::Delete { key } => {
+                    if before.is_none() {
+                        errors.push((key, "config not found".to_string()));
+                        continue;
+                    }
+                    refs.push((key.clone(), vec![]));
+                    entries.push(ChangesetEntry { key, before, after: None });
+                }
+                ChangeOp::Put { key, content, format, meta } => {
+                    if let Some(schemas) = self.schemas {
+                        let checked = ValidateConfig { schemas }.exec(&key, &content, format.as_deref()).await;
+                        if let Err(e) = checked {
+                            errors.push((key, e.to_string()));
+                            continue;
+                        }
+                    }
+                    let targets = template::targets(&content, &key.namespace);
+                    let (content, data_key) = match seal(self.cipher, &key, content) {
+                        Ok(v) => v,
+                        Err(e) => {
+                            errors.push((key, e.to_string()));
+                            continue;
+                        }
+                    };
+                    let (old_tags, old_desc, old_app) = match &before {
+                        Some(o) => (o.tags.clone(), o.desc.clone(), o.app_name.clone()),
+                        None => (vec![], None, None),
+                    };
+                    let after = ConfigItem {
+                        key: key.clone(),
+                        content,
+                        format,
+                        updated_at: Utc::now(),
+                        updated_by: actor.clone(),
+                        version_ts: revision,
+                        tags: meta.tags.unwrap_or(old_tags),
+                        desc: meta.desc.or(old_desc),
+                        app_name: meta.app_name.or(old_app),
+                        data_key,
+                    };
+                    refs.push((key.clone(), targets));
+                    entries.push(ChangesetEntry { key, before, after: Some(after) });
+                }
+            }
+        }
+        if !errors.is_empty() {
+            return Err(ChangesetError { errors }.into());
+        }
+        let cs = Changeset {
+            id,
+            revision,
+            description,
+            actor,
+            created_at: Utc::now(),
+            entries,
+            rollback_of: None,
+            rolled_back_by: None,
+        };
+        self.commit(cs, refs).await
+    }
+
+    /// 整体回滚：恢复每个配置在变更集之前的状态，本身记为一个新的变更集。
+    /// 非 force 模式下若有配置在变更集之后又被修改则拒绝
+    pub async fn rollback(
+        &self,
+        id: &str,
+        new_id: String,
+        force: bool,
+        actor: Option<String>,
+    ) -> anyhow::Result<Option<Changeset>> {
+        let Some(mut original) = self.changesets.get_changeset(id).await else {
+            return Ok(None);
+        };
+        if let Some(by) = &original.rolled_back_by {
+            return Err(anyhow::anyhow!("changeset {} was already rolled back by {}", id, by));
+        }
+        let revision = self.writer.next_revision().await;
+        let mut conflicts = Vec::new();
+        let mut entries = Vec::with_capacity(original.entries.len());
+        let mut refs = Vec::with_capacity(original.entries.len());
+        for e in &original.entries {
+            let current = self.store.get(&e.key).await;
+            let unchanged = match (&current, &e.after) {
+                (None, None) => true,
+                (Some(c), Some(a)) => c.version_ts == a.version_ts && c.content == a.content,
+                _ => false,
+            };
+            if !unchanged && !force {
+                conflicts.push(e.key.clone());
+                continue;
+            }
+            let after = e.before.clone().map(|b| ConfigItem {
+                updated_at: Utc::now(),
+                updated_by: actor.clone(),
+                version_ts: revision,
+                ..b
+            });
+            let targets = after
+                .as_ref()
+                .and_then(|a| {
+                    open(self.cipher, &a.key, &a.content, a.data_key.as_deref())
+                        .ok()
+                        .map(|plain| template::targets(&plain, &a.key.namespace))
+                })
+                .unwrap_or_default();
+            refs.push((e.key.clone(), targets));
+            entries.push(ChangesetEntry { key: e.key.clone(), before: current, after });
+        }
+        if !conflicts.is_empty() {
+            return Err(ChangesetConflict { keys: conflicts }.into());
+        }
+        let cs = Changeset {
+            id: new_id,
+            revision,
+            description: Some(format!("rollback of {}", id)),
+            actor,
+            created_at: Utc::now(),
+            entries,
+            rollback_of: Some(id.to_string()),
+            rolled_back_by: None,
+        };
+        let cs = self.commit(cs, refs).await?;
+        original.rolled_back_by = Some(cs.id.clone());
+        self.changesets.put_changeset(original).await?;
+        Ok(Some(cs))
+    }
+
+    async fn commit(
+        &self,
+        cs: Changeset,
+        refs: Vec<(ConfigKey, Vec<RefTarget>)>,
+    ) -> anyhow::Result<Changeset> {
+        let mut batch = ConfigBatch::default();
+        for e in &cs.entries {
+            // 校验在锁外完成，提交时要求各配置仍是读取快照时的状态，否则整体拒绝
+            batch.expected.push((e.key.clone(), e.before.clone()));
+            // 与单条发布一致：先记旧值，再记新值；删除记为 deleted
+            if let Some(b) = &e.before {
+                batch.history.push(history_of(b, cs.revision, e.after.is_none(), &cs.actor));
+            }
+            match &e.after {
+                Some(a) => {
+                    batch.history.push(history_of(a, cs.revision, false, &cs.actor));
+                    batch.puts.push(a.clone());
+                }
+                None => batch.deletes.push(e.key.clone()),
+            }
+        }
+        match self.writer.commit(batch).await {
+            Ok(()) => {}
+            Err(StoreError::Stale(keys)) => return Err(ChangesetConflict { keys }.into()),
+            Err(e) => return Err(e.into()),
+        }
+        if let Some(d) = self.deps {
+            for (key, targets) in refs {
+                d.set_refs(&key, targets).await;
+            }
+        }
+        self.changesets.put_changeset(cs.clone()).await?;
+        if let Some(n) = self.notifier {
+            let mut keys: Vec<ConfigKey> = cs.entries.iter().map(|e| e.key.clone()).collect();
+            if let Some(d) = self.deps {
+                let targets = keys.iter().cloned().map(RefTarget::Config).collect();
+                for k in collect_dependents(d, targets).await {
+                    if !keys.contains(&k) {
+                        keys.push(k);
+                    }
+                }
+            }
+            n.notify_config_batch(&cs.id, cs.revision, &keys).await;
+        }
+        Ok(cs)
+    }
+}
+
+fn history_of(item: &ConfigItem, revision: i64, deleted: bool, actor: &Option<String>) -> ConfigHistoryItem {
+    ConfigHistoryItem {
+        key: item.key.clone(),
+        content: item.content.clone(),
+        format: item.format.clone(),
+        version_ts: revision,
+        deleted,
+        updated_at: Utc::now(),
+        actor: actor.clone(),
+        data_key: item.data_key.clone(),
+    }
+}
@@ -0,0 +1,48 @@
+use core_model::config::ConfigKey;
+use core_ports::ConfigCipher;
+
+pub const CIPHER_PREFIX: &str = "cipher-";
+
+/// `cipher-aes-db.properties` → Some("aes")
+pub fn cipher_name(data_id: &str) -> Option<&str> {
+    data_id
+        .strip_prefix(CIPHER_PREFIX)
+        .and_then(|rest| rest.split_once('-'))
+        .map(|(name, _)| name)
+}
+
+/// 密文绑定的关联数据：namespace / group / data_id，密文搬到其他配置下无法解密
+fn aad(key: &ConfigKey) -> Vec<u8> {
+    format!("{}\n{}\n{}", key.namespace, key.group, key.data_id).into_bytes()
+}
+
+/// 按 data_id 前缀加密明文，返回 (存储内容, 数据密钥引用)
+pub fn seal(
+    cipher: Option<&dyn ConfigCipher>,
+    key: &ConfigKey,
+    plain: String,
+) -> anyhow::Result<(String, Option<String>)> {
+    let Some(name) = cipher_name(&key.data_id) else {
+        return Ok((plain, None));
+    };
+    match cipher {
+        Some(c) if c.name() == name => Ok((c.encrypt(&plain, &aad(key))?, Some(c.key_ref()))),
+        _ => Err(anyhow::anyhow!("no cipher plugin `{}` configured for {}", name, key.data_id)),
+    }
+}
+
+/// 还原 key 对应配置的存储内容；data_key 为空时原样返回
+pub fn open(
+    cipher: Option<&dyn ConfigCipher>,
+    key: &ConfigKey,
+    content: &str,
+    data_key: Option<&str>,
+) -> anyhow::Result<String> {
+    let Some(data_key) = data_key else {
+        return Ok(content.to_string());
+    };
+    match cipher {
+        Some(c) if c.key_ref() == data_key => c.decrypt(content, &aad(key)),
+        _ => Err(anyhow::anyhow!("data key `{}` is not available", data_key)),
+    }
+}
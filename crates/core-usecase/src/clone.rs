@@ -0,0 +1,124 @@
+use core_model::config::{ConfigKey, ConfigMeta};
+use similar::TextDiff;
+
+use crate::cipher::open;
+use crate::config::PublishConfig;
+use crate::transfer::ImportPolicy;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CloneAction {
+    Create,
+    Update,
+    /// 目标内容与源一致，无需写入
+    Unchanged,
+    Skip,
+    Fail,
+}
+
+#[derive(Debug)]
+pub struct ClonePlanItem {
+    pub source: ConfigKey,
+    pub target: ConfigKey,
+    pub action: CloneAction,
+    /// Update 时为目标 → 源的 unified diff
+    pub diff: Option<String>,
+    pub reason: Option<String>,
+}
+
+/// 将源命名空间中选定的配置复制到目标命名空间，可改写 group；
+/// dry_run 时只返回计划，不做任何写入
+pub struct CloneConfigs<'a> {
+    pub publish: PublishConfig<'a>,
+}
+
+impl<'a> CloneConfigs<'a> {
+    pub async fn exec(
+        &self,
+        sources: Vec<ConfigKey>,
+        target_namespace: &str,
+        target_group: Option<&str>,
+        policy: ImportPolicy,
+        dry_run: bool,
+        actor: Option<String>,
+    ) -> Vec<ClonePlanItem> {
+        let store = self.publish.store;
+        let cipher = self.publish.cipher;
+        let mut plan = Vec::with_capacity(sources.len());
+        let mut aborted = false;
+        for source in sources {
+            let target = ConfigKey {
+                namespace: target_namespace.to_string(),
+                group: target_group.unwrap_or(&source.group).to_string(),
+                data_id: source.data_id.clone(),
+            };
+            let mut item = ClonePlanItem { source, target, action: CloneAction::Fail, diff: None, reason: None };
+            if aborted {
+                item.reason = Some("aborted after earlier conflict".into());
+                plan.push(item);
+                continue;
+            }
+            let Some(src) = store.get(&item.source).await else {
+                item.reason = Some("source config not found".into());
+                plan.push(item);
+                continue;
+            };
+            let content = match open(cipher, &src.key, &src.content, src.data_key.as_deref()) {
+                Ok(c) => c,
+                Err(e) => {
+                    item.reason = Some(e.to_string());
+                    plan.push(item);
+                    continue;
+                }
+            };
+            match store.get(&item.target).await {
+                None => item.action = CloneAction::Create,
+                Some(_) if policy == ImportPolicy::Skip => {
+                    item.action = CloneAction::Skip;
+                    plan.push(item);
+                    continue;
+                }
+                Some(_) if policy == ImportPolicy::Abort => {
+                    item.reason = Some("target config already exists".into());
+                    aborted = true;
+                    plan.push(item);
+                    continue;
+                }
+                Some(dst) => match open(cipher, &dst.key, &dst.content, dst.data_key.as_deref()) {
+                    Ok(old) if old == content => item.action = CloneAction::Unchanged,
+                    Ok(old) => {
+                        item.action = CloneAction::Update;
+                        item.diff = Some(unified_diff(&item.target, &old, &content));
+                    }
+                    Err(e) => {
+                        item.reason = Some(e.to_string());
+                        plan.push(item);
+                        continue;
+                    }
+                },
+            }
+            if !dry_run && matches!(item.action, CloneAction::Create | CloneAction::Update) {
+                let meta = ConfigMeta { tags: Some(src.tags), desc: src.desc, app_name: src.app_name };
+                if let Err(e) = self
+                    .publish
+                    .exec(item.target.clone(), content, src.format, meta, actor.clone())
+                    .await
+                {
+                    item.action = CloneAction::Fail;
+                    item.diff = None;
+                    item.reason = Some(e.to_string());
+                }
+            }
+            plan.push(item);
+        }
+        plan
+    }
+}
+
+pub(crate) fn unified_diff(key: &ConfigKey, old: &str, new: &str) -> String {
+    let name = format!("{}/{}/{}", key.namespace, key.group, key.data_id);
+    TextDiff::from_lines(old, new)
+        .unified_diff()
+        .context_radius(3)
+        .header(&format!("a/{}", name), &format!("b/{}", name))
+        .to_string()
+}
@@ -1,23 +1,79 @@
 use chrono::Utc;
-use core_model::config::{ConfigHistoryItem, ConfigItem, ConfigKey};
-use core_ports::{ConfigHistoryStore, ConfigStore, Notifier};
+use core_model::config::{ConfigHistoryItem, ConfigItem, ConfigKey, ConfigMeta};
+use core_model::event::{ConfigChangeEvent, ConfigOp};
+use core_model::template::{self, RefTarget};
+use core_ports::{BetaConfigStore, ConfigCipher, ConfigDependencyStore, ConfigHistoryStore, ConfigStore, Notifier, SchemaStore};
+
+use crate::cipher::{seal, CIPHER_PREFIX};
+use crate::schema::ValidateConfig;
+use crate::template::notify_dependents;
 
 pub struct PublishConfig<'a> {
     pub store: &'a dyn ConfigStore,
     pub history: &'a dyn ConfigHistoryStore,
     pub notifier: Option<&'a dyn Notifier>,
+    pub schemas: Option<&'a dyn SchemaStore>,
+    pub cipher: Option<&'a dyn ConfigCipher>,
+    pub deps: Option<&'a dyn ConfigDependencyStore>,
+    /// 灰度进行中的配置不在事件中附带内容，避免灰度客户端拿到正式版本
+    pub betas: Option<&'a dyn BetaConfigStore>,
 }
 
+/// 事件中附带明文内容的上限，超出则只带 MD5
+pub const EVENT_CONTENT_LIMIT: usize = 64 * 1024;
+
 impl<'a> PublishConfig<'a> {
     pub async fn exec(
         &self,
         key: ConfigKey,
         content: String,
         format: Option<String>,
+        meta: ConfigMeta,
+        actor: Option<String>,
+    ) -> anyhow::Result<()> {
+        self.publish(ConfigOp::Published, key, content, format, meta, actor).await
+    }
+
+    /// 恢复为历史内容，写入流程与发布相同，事件标记为 rolled_back
+    pub async fn rollback(
+        &self,
+        key: ConfigKey,
+        content: String,
+        format: Option<String>,
+        meta: ConfigMeta,
         actor: Option<String>,
     ) -> anyhow::Result<()> {
+        self.publish(ConfigOp::RolledBack, key, content, format, meta, actor).await
+    }
+
+    async fn publish(
+        &self,
+        op: ConfigOp,
+        key: ConfigKey,
+        content: String,
+        format: Option<String>,
+        meta: ConfigMeta,
+        actor: Option<String>,
+    ) -> anyhow::Result<()> {
+        // Schema 校验，不通过时返回 SchemaValidationError
+        if let Some(schemas) = self.schemas {
+            ValidateConfig { schemas }
+                .exec(&key, &content, format.as_deref())
+                .await?;
+        }
+        // 依赖关系按明文解析
+        let refs = template::targets(&content, &key.namespace);
+        let beta_active = match self.betas {
+            Some(b) => b.get_beta(&key).await.is_some(),
+            None => false,
+        };
+        let plain = (!beta_active && !key.data_id.starts_with(CIPHER_PREFIX) && content.len() <= EVENT_CONTENT_LIMIT)
+            .then(|| content.clone());
+        // cipher-* 配置以密文落盘，历史同样只保存密文
+        let (content, data_key) = seal(self.cipher, &key, content)?;
         // 读取旧值，写历史
-        if let Some(old) = self.store.get(&key).await {
+        let old = self.store.get(&key).await;
+        if let Some(old) = &old {
             let hist = ConfigHistoryItem {
                 key: old.key.clone(),
                 content: old.content.clone(),
@@ -26,10 +82,15 @@ impl<'a> PublishConfig<'a> {
                 deleted: false,
                 updated_at: Utc::now(),
                 actor: actor.clone(),
+                data_key: old.data_key.clone(),
             };
             self.history.append(hist).await?;
         }
-        // 写新值
+        // 写新值，未指定的元数据沿用旧值
+        let (old_tags, old_desc, old_app) = match old {
+            Some(o) => (o.tags, o.desc, o.app_name),
+            None => (vec![], None, None),
+        };
         let item = ConfigItem {
             key: key.clone(),
             content: content.clone(),
@@ -37,8 +98,23 @@ impl<'a> PublishConfig<'a> {
             updated_at: Utc::now(),
             updated_by: actor.clone(),
             version_ts: Utc::now().timestamp(),
+            tags: meta.tags.unwrap_or(old_tags),
+            desc: meta.desc.or(old_desc),
+            app_name: meta.app_name.or(old_app),
+            data_key: data_key.clone(),
+        };
+        let event = ConfigChangeEvent {
+            key: key.clone(),
+            op,
+            revision: item.version_ts,
+            md5: Some(content_md5(&item.content)),
+            actor: actor.clone(),
+            content: plain,
         };
         self.store.put(item).await?;
+        if let Some(d) = self.deps {
+            d.set_refs(&key, refs).await;
+        }
         // 写历史
         let hist_new = ConfigHistoryItem {
             key,
@@ -48,14 +124,30 @@ impl<'a> PublishConfig<'a> {
             deleted: false,
             updated_at: Utc::now(),
             actor,
+            data_key,
         };
         let key_for_notify = hist_new.key.clone();
         self.history.append(hist_new).await?;
         if let Some(n) = self.notifier {
-            n.notify_config_change(&key_for_notify).await;
+            n.notify_config_event(&event).await;
+            // 引用了本配置的模板同样视为变更
+            if let Some(d) = self.deps {
+                notify_dependents(d, n, RefTarget::Config(key_for_notify)).await;
+            }
         }
         Ok(())
     }
 }
 
 
+/// 删除事件：无内容与 MD5，版本取删除时间
+pub fn deleted_event(key: ConfigKey, actor: Option<String>) -> ConfigChangeEvent {
+    ConfigChangeEvent { key, op: ConfigOp::Deleted, revision: Utc::now().timestamp(), md5: None, actor, content: None }
+}
+
+/// 配置内容的 MD5（十六进制小写），按存储内容计算，加密配置即为密文的 MD5；
+/// 客户端以此判断本地版本是否最新
+pub fn content_md5(content: &str) -> String {
+    use md5::{Digest, Md5};
+    Md5::digest(content.as_bytes()).iter().map(|b| format!("{:02x}", b)).collect()
+}
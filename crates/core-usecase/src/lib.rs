@@ -1,4 +1,15 @@
+pub mod audit;
+pub mod auth;
+pub mod beta;
+pub mod changeset;
+pub mod cipher;
+pub mod clone;
 pub mod config;
+pub mod review;
+pub mod schedule;
+pub mod schema;
+pub mod template;
+pub mod transfer;
 pub mod instance {}
 pub mod namespace {}
 
@@ -0,0 +1,163 @@
+use std::fmt;
+
+use chrono::Utc;
+use core_model::config::{ConfigKey, ConfigMeta};
+use core_model::review::{ChangeRequest, ChangeRequestState};
+use core_ports::{ChangeRequestStore, ConfigCipher, ConfigStore, NamespaceStore, SchemaStore};
+
+use crate::cipher::{open, seal};
+use crate::clone::unified_diff;
+use crate::config::PublishConfig;
+use crate::schema::ValidateConfig;
+
+/// 变更请求流转失败的原因，API 层据此映射 404 / 403 / 409
+#[derive(Debug)]
+pub enum ReviewError {
+    NotFound,
+    Forbidden(String),
+    InvalidState(ChangeRequestState),
+    /// 提交后正式版本已被修改，需重新提交
+    Stale,
+}
+
+impl fmt::Display for ReviewError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReviewError::NotFound => write!(f, "change request not found"),
+            ReviewError::Forbidden(msg) => write!(f, "{}", msg),
+            ReviewError::InvalidState(s) => write!(f, "change request is {:?}", s),
+            ReviewError::Stale => write!(f, "config changed after the request was submitted"),
+        }
+    }
+}
+
+impl std::error::Error for ReviewError {}
+
+/// 提交变更草稿：校验 Schema、记录 diff 与基线版本，不改动正式配置
+pub struct SubmitChangeRequest<'a> {
+    pub store: &'a dyn ConfigStore,
+    pub requests: &'a dyn ChangeRequestStore,
+    pub schemas: Option<&'a dyn SchemaStore>,
+    pub cipher: Option<&'a dyn ConfigCipher>,
+}
+
+impl<'a> SubmitChangeRequest<'a> {
+    #[allow(clippy::too_many_arguments)]
+    pub async fn exec(
+        &self,
+        id: String,
+        key: ConfigKey,
+        content: String,
+        format: Option<String>,
+        meta: ConfigMeta,
+        comment: Option<String>,
+        author: String,
+    ) -> anyhow::Result<ChangeRequest> {
+        if let Some(schemas) = self.schemas {
+            ValidateConfig { schemas }
+                .exec(&key, &content, format.as_deref())
+                .await?;
+        }
+        let current = self.store.get(&key).await;
+        let (sealed, data_key) = seal(self.cipher, &key, content.clone())?;
+        // 加密配置不在变更请求中留存明文 diff
+        let diff = if data_key.is_some() {
+            String::new()
+        } else {
+            let old = current.as_ref().map(|c| c.content.as_str()).unwrap_or("");
+            unified_diff(&key, old, &content)
+        };
+        let content = sealed;
+        let now = Utc::now();
+        let cr = ChangeRequest {
+            id,
+            key,
+            content,
+            data_key,
+            format,
+            tags: meta.tags,
+            desc: meta.desc,
+            app_name: meta.app_name,
+            base_version: current.map(|c| c.version_ts),
+            diff,
+            author,
+            comment,
+            state: ChangeRequestState::Draft,
+            reviewer: None,
+            review_comment: None,
+            published_by: None,
+            created_at: now,
+            updated_at: now,
+        };
+        self.requests.put_change_request(cr.clone()).await?;
+        Ok(cr)
+    }
+}
+
+/// 审批 / 驳回：审批人不能是作者，且须在命名空间的审批人名单内（名单为空时由 API 层要求写权限）
+pub struct ReviewChangeRequest<'a> {
+    pub requests: &'a dyn ChangeRequestStore,
+    pub namespaces: &'a dyn NamespaceStore,
+}
+
+impl<'a> ReviewChangeRequest<'a> {
+    pub async fn exec(
+        &self,
+        id: &str,
+        approve: bool,
+        reviewer: String,
+        comment: Option<String>,
+    ) -> anyhow::Result<ChangeRequest> {
+        let mut cr = self.requests.get_change_request(id).await.ok_or(ReviewError::NotFound)?;
+        if cr.state != ChangeRequestState::Draft {
+            return Err(ReviewError::InvalidState(cr.state).into());
+        }
+        if cr.author == reviewer {
+            return Err(ReviewError::Forbidden("author cannot review own change request".into()).into());
+        }
+        let reviewers = self
+            .namespaces
+            .get(&cr.key.namespace)
+            .await
+            .map(|ns| ns.reviewers)
+            .unwrap_or_default();
+        if !reviewers.is_empty() && !reviewers.contains(&reviewer) {
+            return Err(ReviewError::Forbidden(format!("{} is not a reviewer of {}", reviewer, cr.key.namespace)).into());
+        }
+        cr.state = if approve { ChangeRequestState::Approved } else { ChangeRequestState::Rejected };
+        cr.reviewer = Some(reviewer);
+        cr.review_comment = comment;
+        cr.updated_at = Utc::now();
+        self.requests.put_change_request(cr.clone()).await?;
+        Ok(cr)
+    }
+}
+
+/// 发布已审批的变更请求；正式版本在提交后被改动过则拒绝
+pub struct ApplyChangeRequest<'a> {
+    pub publish: PublishConfig<'a>,
+    pub requests: &'a dyn ChangeRequestStore,
+}
+
+impl<'a> ApplyChangeRequest<'a> {
+    pub async fn exec(&self, id: &str, actor: String) -> anyhow::Result<ChangeRequest> {
+        let mut cr = self.requests.get_change_request(id).await.ok_or(ReviewError::NotFound)?;
+        if cr.state != ChangeRequestState::Approved {
+            return Err(ReviewError::InvalidState(cr.state).into());
+        }
+        let current = self.publish.store.get(&cr.key).await.map(|c| c.version_ts);
+        if current != cr.base_version {
+            return Err(ReviewError::Stale.into());
+        }
+        let content = open(self.publish.cipher, &cr.key, &cr.content, cr.data_key.as_deref())?;
+        let meta = ConfigMeta { tags: cr.tags.clone(), desc: cr.desc.clone(), app_name: cr.app_name.clone() };
+        self.publish
+            .exec(cr.key.clone(), content, cr.format.clone(), meta, Some(cr.author.clone()))
+            .await?;
+        cr.state = ChangeRequestState::Published;
+        cr.published_by = Some(actor);
+        cr.updated_at = Utc::now();
+        self.requests.put_change_request(cr.clone()).await?;
+        Ok(cr)
+    }
+}
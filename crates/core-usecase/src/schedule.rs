@@ -0,0 +1,271 @@
+use std::fmt;
+
+use chrono::{DateTime, Duration, Utc};
+use core_model::audit::{AuditAction, AuditRecord, SYSTEM_OPERATOR};
+use core_model::auth::Resource;
+use core_model::config::{ConfigKey, ConfigMeta};
+use core_model::schedule::{ConfigSnapshot, ScheduleState, ScheduledChange};
+use core_model::template::RefTarget;
+use core_ports::{AuditStore, ConfigCipher, NamespaceStore, ScheduledChangeStore, SchemaStore};
+
+use crate::cipher::{open, seal};
+use crate::config::{content_md5, deleted_event, PublishConfig};
+use crate::schema::ValidateConfig;
+use crate::template::notify_dependents;
+
+/// API 层据此映射 404 / 409 / 400
+#[derive(Debug)]
+pub enum ScheduleError {
+    NotFound,
+    InvalidState(ScheduleState),
+    Invalid(String),
+}
+
+impl fmt::Display for ScheduleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScheduleError::NotFound => write!(f, "scheduled change not found"),
+            ScheduleError::InvalidState(s) => write!(f, "scheduled change is {:?}", s),
+            ScheduleError::Invalid(msg) => write!(f, "{}", msg),
+        }
+    }
+}
+
+impl std::error::Error for ScheduleError {}
+
+/// 登记定时发布：提交时即校验 Schema 并加密，到期由调度器发布
+pub struct ScheduleConfig<'a> {
+    pub schedules: &'a dyn ScheduledChangeStore,
+    pub schemas: Option<&'a dyn SchemaStore>,
+    pub cipher: Option<&'a dyn ConfigCipher>,
+}
+
+impl<'a> ScheduleConfig<'a> {
+    #[allow(clippy::too_many_arguments)]
+    pub async fn exec(
+        &self,
+        id: String,
+        key: ConfigKey,
+        content: String,
+        format: Option<String>,
+        meta: ConfigMeta,
+        publish_at: DateTime<Utc>,
+        revert_after_secs: Option<i64>,
+        actor: String,
+    ) -> anyhow::Result<ScheduledChange> {
+        if revert_after_secs.is_some_and(|s| s <= 0) {
+            return Err(ScheduleError::Invalid("revert_after_secs must be positive".into()).into());
+        }
+        if let Some(schemas) = self.schemas {
+            ValidateConfig { schemas }
+                .exec(&key, &content, format.as_deref())
+                .await?;
+        }
+        let (content, data_key) = seal(self.cipher, &key, content)?;
+        let now = Utc::now();
+        let change = ScheduledChange {
+            id,
+            key,
+            content,
+            data_key,
+            format,
+            tags: meta.tags,
+            desc: meta.desc,
+            app_name: meta.app_name,
+            publish_at,
+            revert_after_secs,
+            revert_at: None,
+            previous: None,
+            published_version: None,
+            state: ScheduleState::Pending,
+            error: None,
+            created_by: actor,
+            created_at: now,
+            updated_at: now,
+        };
+        self.schedules.put_scheduled(change.clone()).await?;
+        Ok(change)
+    }
+}
+
+/// 取消：未生效的任务直接取消；已发布且等待回退的任务取消回退，保留当前内容
+pub struct CancelScheduled<'a> {
+    pub schedules: &'a dyn ScheduledChangeStore,
+}
+
+impl<'a> CancelScheduled<'a> {
+    pub async fn exec(&self, id: &str) -> anyhow::Result<ScheduledChange> {
+        let mut change = self.schedules.get_scheduled(id).await.ok_or(ScheduleError::NotFound)?;
+        match change.state {
+            ScheduleState::Pending => change.state = ScheduleState::Cancelled,
+            ScheduleState::Published if change.revert_at.is_some() => change.revert_at = None,
+            s => return Err(ScheduleError::InvalidState(s).into()),
+        }
+        change.updated_at = Utc::now();
+        self.schedules.put_scheduled(change.clone()).await?;
+        Ok(change)
+    }
+}
+
+/// 调度器每个周期调用：发布到期任务，回退到期窗口；单个任务失败记为 Failed，不影响其他任务
+pub struct RunScheduledChanges<'a> {
+    pub publish: PublishConfig<'a>,
+    pub schedules: &'a dyn ScheduledChangeStore,
+    /// 每次发布与回退以系统身份记入审计日志
+    pub audit: Option<&'a dyn AuditStore>,
+    /// 到期时重新检查命名空间的审批要求，登记后才开启审批的任务不再直接发布或回退
+    pub namespaces: Option<&'a dyn NamespaceStore>,
+}
+
+impl<'a> RunScheduledChanges<'a> {
+    /// 返回本次处理的任务数
+    pub async fn exec(&self, now: DateTime<Utc>) -> anyhow::Result<usize> {
+        let mut handled = 0;
+        for change in self.schedules.list_scheduled(None, Some(ScheduleState::Pending)).await? {
+            if change.publish_at <= now {
+                self.publish_one(change, now).await?;
+                handled += 1;
+            }
+        }
+        for change in self.schedules.list_scheduled(None, Some(ScheduleState::Published)).await? {
+            if change.revert_at.is_some_and(|t| t <= now) {
+                self.revert_one(change).await?;
+                handled += 1;
+            }
+        }
+        Ok(handled)
+    }
+
+    async fn check_approval(&self, key: &ConfigKey) -> anyhow::Result<()> {
+        let Some(namespaces) = self.namespaces else {
+            return Ok(());
+        };
+        if namespaces.get(&key.namespace).await.is_some_and(|n| n.require_approval) {
+            return Err(anyhow::anyhow!("namespace {} requires approval, submit a change request instead", key.namespace));
+        }
+        Ok(())
+    }
+
+    async fn current_hash(&self, key: &ConfigKey) -> Option<String> {
+        self.publish.store.get(key).await.map(|c| content_md5(&c.content))
+    }
+
+    /// 写入失败只记日志，不影响任务状态
+    async fn record(&self, change: &ScheduledChange, action: AuditAction, before_hash: Option<String>, result: &anyhow::Result<()>) {
+        let Some(audit) = self.audit else {
+            return;
+        };
+        let detail = format!("scheduled change {} created by {}", change.id, change.created_by);
+        let record = AuditRecord {
+            at: Utc::now(),
+            operator: SYSTEM_OPERATOR.to_string(),
+            source_ip: String::new(),
+            user_agent: "scheduler".to_string(),
+            action,
+            resource: Resource::Config(change.key.clone()).path(),
+            before_hash,
+            after_hash: self.current_hash(&change.key).await,
+            success: result.is_ok(),
+            message: Some(match result {
+                Ok(()) => detail,
+                Err(e) => format!("{}: {}", detail, e),
+            }),
+        };
+        if let Err(e) = audit.append_audit(record).await {
+            tracing::error!("failed to append audit record: {}", e);
+        }
+    }
+
+    async fn publish_one(&self, mut change: ScheduledChange, now: DateTime<Utc>) -> anyhow::Result<()> {
+        let store = self.publish.store;
+        let before = self.current_hash(&change.key).await;
+        let previous = store.get(&change.key).await.map(|c| ConfigSnapshot {
+            content: c.content,
+            data_key: c.data_key,
+            format: c.format,
+            tags: c.tags,
+            desc: c.desc,
+            app_name: c.app_name,
+        });
+        let meta = ConfigMeta { tags: change.tags.clone(), desc: change.desc.clone(), app_name: change.app_name.clone() };
+        let opened = match self.check_approval(&change.key).await {
+            Ok(()) => open(self.publish.cipher, &change.key, &change.content, change.data_key.as_deref()),
+            Err(e) => Err(e),
+        };
+        let published = match opened {
+            Ok(plain) => {
+                self.publish
+                    .exec(change.key.clone(), plain, change.format.clone(), meta, Some(change.created_by.clone()))
+                    .await
+            }
+            Err(e) => Err(e),
+        };
+        self.record(&change, AuditAction::ConfigPublish, before, &published).await;
+        match published {
+            Ok(()) => {
+                change.state = ScheduleState::Published;
+                change.previous = previous;
+                change.published_version = store.get(&change.key).await.map(|c| c.version_ts);
+                change.revert_at = change.revert_after_secs.map(|s| now + Duration::seconds(s));
+            }
+            Err(e) => {
+                change.state = ScheduleState::Failed;
+                change.error = Some(e.to_string());
+            }
+        }
+        change.updated_at = Utc::now();
+        Ok(self.schedules.put_scheduled(change).await?)
+    }
+
+    async fn revert_one(&self, mut change: ScheduledChange) -> anyhow::Result<()> {
+        let before = self.current_hash(&change.key).await;
+        let current = self.publish.store.get(&change.key).await.map(|c| c.version_ts);
+        let reverted = if current != change.published_version {
+            // 窗口内已被他人修改，不覆盖
+            Err(anyhow::anyhow!("config changed after the scheduled publish, revert skipped"))
+        } else if let Err(e) = self.check_approval(&change.key).await {
+            Err(e)
+        } else {
+            match &change.previous {
+                Some(prev) => self.restore(&change, prev).await,
+                None => self.remove(&change).await,
+            }
+        };
+        let action = if change.previous.is_some() { AuditAction::ConfigRollback } else { AuditAction::ConfigDelete };
+        self.record(&change, action, before, &reverted).await;
+        match reverted {
+            Ok(()) => change.state = ScheduleState::Reverted,
+            Err(e) => {
+                change.state = ScheduleState::Failed;
+                change.error = Some(e.to_string());
+            }
+        }
+        change.revert_at = None;
+        change.updated_at = Utc::now();
+        Ok(self.schedules.put_scheduled(change).await?)
+    }
+
+    async fn restore(&self, change: &ScheduledChange, prev: &ConfigSnapshot) -> anyhow::Result<()> {
+        let plain = open(self.publish.cipher, &change.key, &prev.content, prev.data_key.as_deref())?;
+        let meta = ConfigMeta { tags: Some(prev.tags.clone()), desc: prev.desc.clone(), app_name: prev.app_name.clone() };
+        self.publish
+            .rollback(change.key.clone(), plain, prev.format.clone(), meta, Some(change.created_by.clone()))
+            .await
+    }
+
+    /// 发布前不存在的配置，回退即删除
+    async fn remove(&self, change: &ScheduledChange) -> anyhow::Result<()> {
+        let key = &change.key;
+        self.publish.store.delete(key).await?;
+        if let Some(d) = self.publish.deps {
+            d.set_refs(key, vec![]).await;
+        }
+        if let Some(n) = self.publish.notifier {
+            n.notify_config_event(&deleted_event(key.clone(), Some(change.created_by.clone()))).await;
+            if let Some(d) = self.publish.deps {
+                notify_dependents(d, n, RefTarget::Config(key.clone())).await;
+            }
+        }
+        Ok(())
+    }
+}
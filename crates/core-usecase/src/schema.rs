@@ -0,0 +1,181 @@
+use std::fmt;
+
+use core_model::config::ConfigKey;
+use core_model::pattern::glob_match;
+use core_model::schema::{ConfigSchema, SchemaViolation};
+use core_ports::SchemaStore;
+use serde_json::{Map, Value};
+
+/// 发布内容未通过 Schema 校验
+#[derive(Debug, Clone)]
+pub struct SchemaValidationError {
+    pub violations: Vec<SchemaViolation>,
+}
+
+impl fmt::Display for SchemaValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "schema validation failed")?;
+        for (i, v) in self.violations.iter().enumerate() {
+            let sep = if i == 0 { ": " } else { "; " };
+            let path = if v.path.is_empty() { "/" } else { v.path.as_str() };
+            write!(f, "{}{} {}", sep, path, v.message)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for SchemaValidationError {}
+
+/// 检查 schema 本身是否是合法的 JSON Schema
+pub fn check_schema(schema: &Value) -> anyhow::Result<()> {
+    jsonschema::validator_for(schema)
+        .map(|_| ())
+        .map_err(|e| anyhow::anyhow!("invalid json schema: {}", e))
+}
+
+/// 推断配置格式：优先 format 字段，其次 data_id 扩展名
+pub fn detect_format(data_id: &str, format: Option<&str>) -> String {
+    if let Some(f) = format.filter(|f| !f.trim().is_empty()) {
+        return f.trim().to_ascii_lowercase();
+    }
+    match data_id.rsplit_once('.') {
+        Some((_, ext)) => ext.to_ascii_lowercase(),
+        None => "text".into(),
+    }
+}
+
+/// 将 JSON/YAML/TOML/Properties 内容解析为 JSON 树
+pub fn parse_content(content: &str, format: &str) -> anyhow::Result<Value> {
+    match format {
+        "json" => Ok(serde_json::from_str(content)?),
+        "yaml" | "yml" => Ok(serde_yaml::from_str(content)?),
+        "toml" => {
+            let v: toml::Value = toml::from_str(content)?;
+            Ok(serde_json::to_value(v)?)
+        }
+        "properties" => Ok(properties_to_tree(content)),
+        other => Err(anyhow::anyhow!("format `{}` cannot be validated against a schema", other)),
+    }
+}
+
+/// `a.b.c=1` 形式映射为嵌套对象；数值与布尔按字面量转换
+fn properties_to_tree(content: &str) -> Value {
+    let mut root = Map::new();
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with('!') {
+            continue;
+        }
+        let (k, v) = match line.find(['=', ':']) {
+            Some(i) => (line[..i].trim(), line[i + 1..].trim()),
+            None => (line, ""),
+        };
+        let mut node = &mut root;
+        let parts: Vec<&str> = k.split('.').collect();
+        for (i, part) in parts.iter().enumerate() {
+            if i + 1 == parts.len() {
+                node.insert(part.to_string(), scalar_of(v));
+            } else {
+                let next = node
+                    .entry(part.to_string())
+                    .or_insert_with(|| Value::Object(Map::new()));
+                if !next.is_object() {
+                    *next = Value::Object(Map::new());
+                }
+                node = next.as_object_mut().expect("object");
+            }
+        }
+    }
+    Value::Object(root)
+}
+
+fn scalar_of(v: &str) -> Value {
+    if v == "true" || v == "false" {
+        return Value::Bool(v == "true");
+    }
+    if let Ok(i) = v.parse::<i64>() {
+        return Value::from(i);
+    }
+    if let Ok(f) = v.parse::<f64>() {
+        if let Some(n) = serde_json::Number::from_f64(f) {
+            return Value::Number(n);
+        }
+    }
+    Value::String(v.to_string())
+}
+
+pub fn schema_matches(schema: &ConfigSchema, key: &ConfigKey) -> bool {
+    schema.namespace == key.namespace
+        && glob_match(&schema.group, &key.group)
+        && glob_match(&schema.data_id, &key.data_id)
+}
+
+pub struct ValidateConfig<'a> {
+    pub schemas: &'a dyn SchemaStore,
+}
+
+impl<'a> ValidateConfig<'a> {
+    /// 返回所有匹配 schema 的校验错误；无匹配 schema 时返回空
+    pub async fn violations(
+        &self,
+        key: &ConfigKey,
+        content: &str,
+        format: Option<&str>,
+    ) -> anyhow::Result<Vec<SchemaViolation>> {
+        let matched: Vec<ConfigSchema> = self
+            .schemas
+            .list(Some(&key.namespace))
+            .await?
+            .into_iter()
+            .filter(|s| schema_matches(s, key))
+            .collect();
+        if matched.is_empty() {
+            return Ok(vec![]);
+        }
+        let fmt = detect_format(&key.data_id, format);
+        let tree = match parse_content(content, &fmt) {
+            Ok(v) => v,
+            Err(e) => {
+                return Ok(matched
+                    .iter()
+                    .map(|s| SchemaViolation {
+                        schema_id: s.id.clone(),
+                        path: String::new(),
+                        message: e.to_string(),
+                    })
+                    .collect())
+            }
+        };
+        let mut out = Vec::new();
+        for s in &matched {
+            let validator = match jsonschema::validator_for(&s.schema) {
+                Ok(v) => v,
+                Err(e) => {
+                    out.push(SchemaViolation {
+                        schema_id: s.id.clone(),
+                        path: String::new(),
+                        message: format!("invalid json schema: {}", e),
+                    });
+                    continue;
+                }
+            };
+            for err in validator.iter_errors(&tree) {
+                out.push(SchemaViolation {
+                    schema_id: s.id.clone(),
+                    path: err.instance_path.to_string(),
+                    message: err.to_string(),
+                });
+            }
+        }
+        Ok(out)
+    }
+
+    pub async fn exec(&self, key: &ConfigKey, content: &str, format: Option<&str>) -> anyhow::Result<()> {
+        let violations = self.violations(key, content, format).await?;
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(SchemaValidationError { violations }.into())
+        }
+    }
+}
@@ -0,0 +1,185 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+
+use core_model::auth::{Action, Resource};
+use core_model::config::{ConfigItem, ConfigKey};
+use core_model::template::{parse, RefTarget, Segment, TemplateRef};
+use core_ports::{ConfigCipher, ConfigDependencyStore, ConfigStore, NamespaceStore, Notifier};
+use serde_json::Value;
+
+use crate::auth::Grants;
+use crate::cipher::open;
+use crate::schema::{detect_format, parse_content};
+
+/// 引用链深度上限
+const MAX_DEPTH: usize = 16;
+
+/// 模板无法解析；API 层据此返回 400，无权读取被引用配置时返回 403
+#[derive(Debug)]
+pub enum RenderError {
+    Forbidden(String),
+    Cycle(Vec<ConfigKey>),
+    TooDeep,
+    MissingConfig(String),
+    MissingVar(String),
+    BadPath { reference: String, reason: String },
+}
+
+impl fmt::Display for RenderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RenderError::Forbidden(r) => write!(f, "no read permission on referenced config: ${{{}}}", r),
+            RenderError::Cycle(chain) => {
+                let chain: Vec<String> = chain
+                    .iter()
+                    .map(|k| format!("{}:{}:{}", k.namespace, k.group, k.data_id))
+                    .collect();
+                write!(f, "template reference cycle: {}", chain.join(" -> "))
+            }
+            RenderError::TooDeep => write!(f, "template references nested deeper than {}", MAX_DEPTH),
+            RenderError::MissingConfig(r) => write!(f, "referenced config not found: ${{{}}}", r),
+            RenderError::MissingVar(r) => write!(f, "namespace variable not found: ${{{}}}", r),
+            RenderError::BadPath { reference, reason } => {
+                write!(f, "cannot resolve ${{{}}}: {}", reference, reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for RenderError {}
+
+/// 渲染配置模板：替换 `${ns:group:data_id#path}` 与 `${var}`，被引用配置自身也会先行渲染
+pub struct RenderConfig<'a> {
+    pub store: &'a dyn ConfigStore,
+    pub namespaces: &'a dyn NamespaceStore,
+    pub cipher: Option<&'a dyn ConfigCipher>,
+    /// 调用方的权限；非空时每个被引用配置（含间接引用）都须有读权限
+    pub grants: Option<&'a Grants>,
+}
+
+/// 单次渲染的状态：当前引用链用于发现循环，已渲染的配置只解析一次
+#[derive(Default)]
+struct RenderState {
+    stack: Vec<ConfigKey>,
+    /// 被引用配置渲染后的内容与格式
+    rendered: HashMap<ConfigKey, (String, String)>,
+}
+
+impl<'a> RenderConfig<'a> {
+    /// item.content 须为明文
+    pub async fn exec(&self, item: &ConfigItem) -> anyhow::Result<String> {
+        let mut state = RenderState { stack: vec![item.key.clone()], ..Default::default() };
+        self.render(&item.key.namespace, &item.content, &mut state).await
+    }
+
+    fn render<'b>(
+        &'b self,
+        namespace: &'b str,
+        content: &'b str,
+        state: &'b mut RenderState,
+    ) -> Pin<Box<dyn Future<Output = anyhow::Result<String>> + Send + 'b>> {
+        Box::pin(async move {
+            let mut out = String::with_capacity(content.len());
+            for seg in parse(content, namespace) {
+                match seg {
+                    Segment::Text(t) => out.push_str(&t),
+                    Segment::Ref(r) => out.push_str(&self.resolve(&r, state).await?),
+                }
+            }
+            Ok(out)
+        })
+    }
+
+    async fn resolve(&self, r: &TemplateRef, state: &mut RenderState) -> anyhow::Result<String> {
+        let key = match &r.target {
+            RefTarget::Var { namespace, name } => {
+                let value = self
+                    .namespaces
+                    .get(namespace)
+                    .await
+                    .and_then(|ns| ns.variables.get(name).cloned());
+                return value.ok_or_else(|| RenderError::MissingVar(r.raw.clone()).into());
+            }
+            RefTarget::Config(key) => key,
+        };
+        if self.grants.is_some_and(|g| !g.allows(&Resource::Config(key.clone()), Action::Read)) {
+            return Err(RenderError::Forbidden(r.raw.clone()).into());
+        }
+        let (rendered, format) = match state.rendered.get(key) {
+            Some(done) => done.clone(),
+            None => {
+                let done = self.render_ref(key, r, state).await?;
+                state.rendered.insert(key.clone(), done.clone());
+                done
+            }
+        };
+        let Some(path) = &r.path else {
+            return Ok(rendered);
+        };
+        let bad = |reason: String| RenderError::BadPath { reference: r.raw.clone(), reason };
+        let tree = parse_content(&rendered, &format).map_err(|e| bad(e.to_string()))?;
+        let value = lookup(&tree, path).ok_or_else(|| bad(format!("path `{}` not found", path)))?;
+        Ok(match value {
+            Value::String(s) => s.clone(),
+            Value::Null => String::new(),
+            other => other.to_string(),
+        })
+    }
+
+    /// 读取、解密并渲染被引用的配置，返回渲染结果与格式
+    async fn render_ref(&self, key: &ConfigKey, r: &TemplateRef, state: &mut RenderState) -> anyhow::Result<(String, String)> {
+        if let Some(i) = state.stack.iter().position(|k| k == key) {
+            let mut chain = state.stack[i..].to_vec();
+            chain.push(key.clone());
+            return Err(RenderError::Cycle(chain).into());
+        }
+        if state.stack.len() >= MAX_DEPTH {
+            return Err(RenderError::TooDeep.into());
+        }
+        let item = self
+            .store
+            .get(key)
+            .await
+            .ok_or_else(|| RenderError::MissingConfig(r.raw.clone()))?;
+        let plain = open(self.cipher, &item.key, &item.content, item.data_key.as_deref())?;
+        state.stack.push(key.clone());
+        let rendered = self.render(&key.namespace, &plain, state).await;
+        state.stack.pop();
+        Ok((rendered?, detect_format(&key.data_id, item.format.as_deref())))
+    }
+}
+
+/// 点分路径取值，数组以下标访问：`db.hosts.0`
+fn lookup<'v>(tree: &'v Value, path: &str) -> Option<&'v Value> {
+    path.split('.').try_fold(tree, |node, part| match node {
+        Value::Object(m) => m.get(part),
+        Value::Array(a) => part.parse::<usize>().ok().and_then(|i| a.get(i)),
+        _ => None,
+    })
+}
+
+/// 直接或间接引用了 targets 的配置（不含 targets 本身）
+pub async fn collect_dependents(deps: &dyn ConfigDependencyStore, targets: Vec<RefTarget>) -> Vec<ConfigKey> {
+    let mut seen: HashSet<String> = targets.iter().map(RefTarget::id).collect();
+    let mut queue = targets;
+    let mut out = Vec::new();
+    while let Some(t) = queue.pop() {
+        for key in deps.dependents(&t).await {
+            let next = RefTarget::Config(key.clone());
+            if seen.insert(next.id()) {
+                out.push(key);
+                queue.push(next);
+            }
+        }
+    }
+    out
+}
+
+/// 通知直接或间接引用了 target 的配置的订阅者
+pub async fn notify_dependents(deps: &dyn ConfigDependencyStore, notifier: &dyn Notifier, target: RefTarget) {
+    for key in collect_dependents(deps, vec![target]).await {
+        notifier.notify_config_change(&key).await;
+    }
+}
@@ -0,0 +1,124 @@
+use std::str::FromStr;
+
+use core_model::config::{ConfigKey, ConfigMeta};
+
+use crate::cipher::open;
+use crate::config::PublishConfig;
+
+/// 导入时遇到同名配置的处理策略（与 Nacos 一致）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ImportPolicy {
+    /// 遇到冲突即终止，后续条目均记为失败
+    #[default]
+    Abort,
+    /// 跳过已存在的配置
+    Skip,
+    /// 覆盖已存在的配置
+    Overwrite,
+}
+
+impl FromStr for ImportPolicy {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_uppercase().as_str() {
+            "ABORT" => Ok(ImportPolicy::Abort),
+            "SKIP" => Ok(ImportPolicy::Skip),
+            "OVERWRITE" => Ok(ImportPolicy::Overwrite),
+            _ => Err(anyhow::anyhow!("unknown import policy `{}`", s)),
+        }
+    }
+}
+
+pub struct ImportItem {
+    pub key: ConfigKey,
+    pub content: String,
+    pub format: Option<String>,
+    pub meta: ConfigMeta,
+    /// 非空表示 content 为该密钥加密的密文
+    pub data_key: Option<String>,
+    /// 密文加密时所在的命名空间，为空表示与 key 相同；跨命名空间导入时按它解密
+    pub source_namespace: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct ImportFailure {
+    pub key: ConfigKey,
+    pub reason: String,
+}
+
+#[derive(Debug, Default)]
+pub struct ImportResult {
+    pub succ_count: usize,
+    pub skip_count: usize,
+    pub fail_count: usize,
+    pub skip_data: Vec<ConfigKey>,
+    pub fail_data: Vec<ImportFailure>,
+}
+
+impl ImportResult {
+    fn fail(&mut self, key: ConfigKey, reason: impl Into<String>) {
+        self.fail_count += 1;
+        self.fail_data.push(ImportFailure { key, reason: reason.into() });
+    }
+}
+
+/// 批量导入：逐条发布（走 Schema 校验、加密与历史），按策略处理冲突
+pub struct ImportConfigs<'a> {
+    pub publish: PublishConfig<'a>,
+}
+
+impl<'a> ImportConfigs<'a> {
+    pub async fn exec(
+        &self,
+        items: Vec<ImportItem>,
+        policy: ImportPolicy,
+        actor: Option<String>,
+    ) -> ImportResult {
+        let mut result = ImportResult::default();
+        let mut aborted: Option<String> = None;
+        for it in items {
+            if let Some(reason) = &aborted {
+                result.fail(it.key, reason.clone());
+                continue;
+            }
+            if policy != ImportPolicy::Overwrite && self.publish.store.get(&it.key).await.is_some() {
+                if policy == ImportPolicy::Skip {
+                    result.skip_count += 1;
+                    result.skip_data.push(it.key);
+                } else {
+                    let reason = format!(
+                        "aborted on conflict with {}:{}:{}",
+                        it.key.namespace, it.key.group, it.key.data_id
+                    );
+                    result.fail(it.key, "config already exists");
+                    aborted = Some(reason);
+                }
+                continue;
+            }
+            // 密文绑定导出时的命名空间，先按来源解密，再由发布流程按目标重新加密
+            let source = ConfigKey {
+                namespace: it.source_namespace.clone().unwrap_or_else(|| it.key.namespace.clone()),
+                ..it.key.clone()
+            };
+            let published = match open(self.publish.cipher, &source, &it.content, it.data_key.as_deref()) {
+                Ok(content) => {
+                    self.publish
+                        .exec(it.key.clone(), content, it.format, it.meta, actor.clone())
+                        .await
+                }
+                Err(e) => Err(e),
+            };
+            match published {
+                Ok(_) => result.succ_count += 1,
+                Err(e) => {
+                    if policy == ImportPolicy::Abort {
+                        aborted = Some(format!("aborted after failure: {}", e));
+                    }
+                    result.fail(it.key, e.to_string());
+                }
+            }
+        }
+        result
+    }
+}
@@ -5,6 +5,7 @@ use tower_http::cors::{Any, CorsLayer};
 #[tokio::main]
 async fn main() {
     let app = app_bootstrap::build_app()
+        .await
         .layer(CorsLayer::new()
             .allow_origin(Any)
             .allow_methods([Method::GET, Method::POST, Method::DELETE, Method::PUT, Method::OPTIONS])
@@ -14,7 +15,7 @@ async fn main() {
     tracing_subscriber::fmt::init();
     tracing::info!("nextapp listening on {}", addr);
     let listener = TcpListener::bind(addr).await.unwrap();
-    axum::serve(listener, app).await.unwrap();
+    app_bootstrap::serve(listener, app).await.unwrap();
 }
 
 
@@ -16,6 +16,7 @@ async fn main() {
     tracing_subscriber::fmt::init();
     let args = Args::parse();
     let app = app_bootstrap::build_app()
+        .await
         .layer(
             CorsLayer::new()
                 .allow_origin(Any)
@@ -25,7 +26,7 @@ async fn main() {
     let addr = std::net::SocketAddr::from(([0, 0, 0, 0], args.port));
     tracing::info!("rustacos listening on {}", addr);
     let listener = TcpListener::bind(addr).await.unwrap();
-    axum::serve(listener, app).await.unwrap();
+    app_bootstrap::serve(listener, app).await.unwrap();
 }
 
 
@@ -6,7 +6,7 @@ use leptos_meta::*;
 #[cfg(target_arch = "wasm32")]
 use crate::frontend::{
     components::Navbar,
-    pages::{Dashboard, Services, Configs, Namespaces},
+    pages::{Dashboard, Services, Configs, Namespaces, Login, Audit},
 };
 
 #[cfg(target_arch = "wasm32")]
@@ -25,6 +25,8 @@ pub fn App() -> impl IntoView {
                         <Route path="/services" view=Services />
                         <Route path="/configs" view=Configs />
                         <Route path="/namespaces" view=Namespaces />
+                        <Route path="/audit" view=Audit />
+                        <Route path="/login" view=Login />
                         <Route path="/*any" view=NotFound />
                     </Routes>
                 </main>
@@ -1,10 +1,25 @@
 #[cfg(target_arch = "wasm32")]
 use leptos::*;
 use leptos_router::*;
+#[cfg(target_arch = "wasm32")]
+use crate::frontend::services::ApiClient;
 
 #[cfg(target_arch = "wasm32")]
 #[component]
 pub fn Navbar() -> impl IntoView {
+    // 未登录或令牌过期时跳转登录页
+    let pathname = use_location().pathname;
+    let navigate = use_navigate();
+    create_effect(move |_| {
+        if pathname.get() != "/login" && !ApiClient::logged_in() {
+            navigate("/login", Default::default());
+        }
+    });
+    let on_logout = move |_| {
+        ApiClient::logout();
+        use_navigate()("/login", Default::default());
+    };
+
     view! {
         <nav class="navbar navbar-expand-lg navbar-dark bg-dark">
             <div class="container">
@@ -36,6 +51,21 @@ pub fn Navbar() -> impl IntoView {
                                 <i class="bi bi-folder"></i> {" 命名空间"}
                             </A>
                         </li>
+                        <li class="nav-item">
+                            <A href="/audit" class="nav-link">
+                                <i class="bi bi-shield-check"></i> {" 审计"}
+                            </A>
+                        </li>
+                        <Show when=move || pathname.get() != "/login" && ApiClient::logged_in()>
+                            <li class="nav-item d-flex align-items-center ms-lg-3">
+                                <span class="navbar-text me-2">
+                                    <i class="bi bi-person-circle"></i> {ApiClient::current_user().unwrap_or_default()}
+                                </span>
+                                <button class="btn btn-outline-light btn-sm" on:click=on_logout>
+                                    {"退出"}
+                                </button>
+                            </li>
+                        </Show>
                     </ul>
                 </div>
             </div>
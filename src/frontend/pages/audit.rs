@@ -0,0 +1,237 @@
+#[cfg(target_arch = "wasm32")]
+use leptos::*;
+#[cfg(target_arch = "wasm32")]
+use crate::frontend::components::Loading;
+#[cfg(target_arch = "wasm32")]
+use crate::frontend::services::{ApiClient, AuditChainReport, AuditPage, AuditQuery};
+
+#[cfg(target_arch = "wasm32")]
+const ACTIONS: [(&str, &str); 24] = [
+    ("config_publish", "发布配置"),
+    ("config_delete", "删除配置"),
+    ("config_rollback", "回滚配置"),
+    ("config_import", "导入 / 克隆"),
+    ("namespace_create", "创建命名空间"),
+    ("namespace_update", "修改命名空间"),
+    ("namespace_delete", "删除命名空间"),
+    ("instance_register", "注册实例"),
+    ("instance_deregister", "注销实例"),
+    ("instance_update", "实例恢复"),
+    ("user_create", "创建用户"),
+    ("user_update", "修改密码"),
+    ("user_delete", "删除用户"),
+    ("role_bind", "绑定角色"),
+    ("role_delete", "删除角色"),
+    ("permission_grant", "授予权限"),
+    ("permission_revoke", "撤销权限"),
+    ("access_key_create", "创建 AK"),
+    ("access_key_rotate", "轮换 AK"),
+    ("access_key_revoke", "吊销 AK"),
+    ("access_key_delete", "删除 AK"),
+    ("webhook_create", "登记 Webhook"),
+    ("webhook_update", "修改 Webhook"),
+    ("webhook_delete", "删除 Webhook"),
+];
+
+#[cfg(target_arch = "wasm32")]
+fn action_label(action: &str) -> String {
+    ACTIONS.iter().find(|(k, _)| *k == action).map(|(_, v)| v.to_string()).unwrap_or_else(|| action.to_string())
+}
+
+#[cfg(target_arch = "wasm32")]
+fn short_hash(h: &Option<String>) -> String {
+    h.as_deref().map(|h| h.chars().take(8).collect()).unwrap_or_else(|| "-".to_string())
+}
+
+#[cfg(target_arch = "wasm32")]
+#[component]
+pub fn Audit() -> impl IntoView {
+    let (loading, set_loading) = create_signal(true);
+    let (page, set_page) = create_signal::<Option<AuditPage>>(None);
+    let (error, set_error) = create_signal::<Option<String>>(None);
+    let (report, set_report) = create_signal::<Option<AuditChainReport>>(None);
+
+    // 筛选条件
+    let (operator, set_operator) = create_signal(String::new());
+    let (action, set_action) = create_signal(String::new());
+    let (resource, set_resource) = create_signal(String::new());
+    let (success, set_success) = create_signal(String::new());
+    let (page_no, set_page_no) = create_signal(1usize);
+
+    let reload = move || {
+        set_loading.set(true);
+        let q = AuditQuery {
+            operator: operator.get_untracked(),
+            action: action.get_untracked(),
+            resource: resource.get_untracked(),
+            success: success.get_untracked(),
+            page: page_no.get_untracked(),
+        };
+        spawn_local(async move {
+            match ApiClient::new().list_audit(&q).await {
+                Ok(p) => {
+                    set_page.set(Some(p));
+                    set_error.set(None);
+                }
+                Err(e) => set_error.set(Some(e)),
+            }
+            set_loading.set(false);
+        });
+    };
+    reload();
+
+    let on_search = move |_| {
+        set_page_no.set(1);
+        reload();
+    };
+    let go_page = move |n: usize| {
+        set_page_no.set(n.max(1));
+        reload();
+    };
+    let on_verify = move |_| {
+        spawn_local(async move {
+            match ApiClient::new().verify_audit().await {
+                Ok(r) => set_report.set(Some(r)),
+                Err(e) => set_error.set(Some(format!("校验失败: {}", e))),
+            }
+        });
+    };
+
+    view! {
+        <div class="container mt-4">
+            <h2 class="page-title">
+                <i class="bi bi-shield-check"></i> {" 审计日志"}
+            </h2>
+            {move || report.get().map(|r| if r.intact {
+                view! {
+                    <div class="alert alert-success py-2">
+                        {format!("哈希链完整，共 {} 条", r.total)}
+                        {r.head_hash.map(|h| view! { <small class="ms-2 text-muted">{"链尾 "}<code>{h}</code></small> })}
+                    </div>
+                }.into_view()
+            } else {
+                view! {
+                    <div class="alert alert-danger py-2">
+                        {format!("哈希链在第 {} 条断开：{}", r.broken_at.unwrap_or_default(), r.reason.unwrap_or_default())}
+                    </div>
+                }.into_view()
+            })}
+
+            <div class="card mb-3">
+                <div class="card-body">
+                    <div class="row g-2 align-items-end">
+                        <div class="col-md-2">
+                            <label class="form-label">{"操作人"}</label>
+                            <input class="form-control form-control-sm"
+                                   prop:value=operator
+                                   on:input=move |e| set_operator.set(event_target_value(&e)) />
+                        </div>
+                        <div class="col-md-2">
+                            <label class="form-label">{"操作"}</label>
+                            <select class="form-select form-select-sm"
+                                    on:change=move |e| set_action.set(event_target_value(&e))>
+                                <option value="">{"全部"}</option>
+                                {ACTIONS.iter().map(|(k, v)| view! { <option value=*k>{*v}</option> }).collect_view()}
+                            </select>
+                        </div>
+                        <div class="col-md-4">
+                            <label class="form-label">{"资源"}</label>
+                            <input class="form-control form-control-sm"
+                                   placeholder="支持通配，如 dev:*:config/app-*"
+                                   prop:value=resource
+                                   on:input=move |e| set_resource.set(event_target_value(&e)) />
+                        </div>
+                        <div class="col-md-2">
+                            <label class="form-label">{"结果"}</label>
+                            <select class="form-select form-select-sm"
+                                    on:change=move |e| set_success.set(event_target_value(&e))>
+                                <option value="">{"全部"}</option>
+                                <option value="true">{"成功"}</option>
+                                <option value="false">{"失败"}</option>
+                            </select>
+                        </div>
+                        <div class="col-md-2 text-end">
+                            <button class="btn btn-sm btn-primary me-2" on:click=on_search>
+                                <i class="bi bi-search"></i>{" 查询"}
+                            </button>
+                            <button class="btn btn-sm btn-outline-secondary" on:click=on_verify>
+                                <i class="bi bi-link-45deg"></i>{" 校验"}
+                            </button>
+                        </div>
+                    </div>
+                </div>
+            </div>
+
+            <div class="card">
+                <div class="card-body">
+                    {move || if loading.get() {
+                        view! { <Loading /> }.into_view()
+                    } else if let Some(msg) = error.get() {
+                        view! { <div class="alert alert-danger">{msg}</div> }.into_view()
+                    } else {
+                        let p = page.get();
+                        let total = p.as_ref().map(|p| p.total_count).unwrap_or_default();
+                        let size = p.as_ref().map(|p| p.page_size).unwrap_or(50).max(1);
+                        let current = page_no.get();
+                        let pages = total.div_ceil(size).max(1);
+                        let items = p.map(|p| p.items).unwrap_or_default();
+                        view! {
+                            <div class="table-responsive">
+                                <table class="table table-sm table-hover align-middle">
+                                    <thead>
+                                        <tr>
+                                            <th>{"#"}</th>
+                                            <th>{"时间"}</th>
+                                            <th>{"操作人"}</th>
+                                            <th>{"来源"}</th>
+                                            <th>{"操作"}</th>
+                                            <th>{"资源"}</th>
+                                            <th>{"变更前 / 后"}</th>
+                                            <th>{"结果"}</th>
+                                        </tr>
+                                    </thead>
+                                    <tbody>
+                                        {items.into_iter().map(|e| view! {
+                                            <tr>
+                                                <td class="text-muted small" title=e.hash.clone()>{e.seq}</td>
+                                                <td class="small">{e.at.chars().take(19).collect::<String>().replace('T', " ")}</td>
+                                                <td>{e.operator}</td>
+                                                <td class="small" title=e.user_agent>{e.source_ip}</td>
+                                                <td>{action_label(&e.action)}</td>
+                                                <td><code class="small">{e.resource}</code></td>
+                                                <td class="small font-monospace">
+                                                    {format!("{} → {}", short_hash(&e.before_hash), short_hash(&e.after_hash))}
+                                                </td>
+                                                <td>
+                                                    {if e.success {
+                                                        view! { <span class="badge bg-success">{"成功"}</span> }.into_view()
+                                                    } else {
+                                                        view! { <span class="badge bg-danger" title=e.message.unwrap_or_default()>{"失败"}</span> }.into_view()
+                                                    }}
+                                                </td>
+                                            </tr>
+                                        }).collect_view()}
+                                    </tbody>
+                                </table>
+                            </div>
+                            <div class="d-flex justify-content-between align-items-center">
+                                <small class="text-muted">{format!("共 {} 条，第 {} / {} 页", total, current, pages)}</small>
+                                <div>
+                                    <button class="btn btn-sm btn-outline-secondary me-2"
+                                            disabled=current <= 1
+                                            on:click=move |_| go_page(current - 1)>{"上一页"}</button>
+                                    <button class="btn btn-sm btn-outline-secondary"
+                                            disabled=current >= pages
+                                            on:click=move |_| go_page(current + 1)>{"下一页"}</button>
+                                </div>
+                            </div>
+                        }.into_view()
+                    }}
+                </div>
+            </div>
+        </div>
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn Audit() {}
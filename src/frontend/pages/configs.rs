@@ -2,7 +2,7 @@
 use leptos::*;
 use crate::frontend::components::Loading;
 #[cfg(target_arch = "wasm32")]
-use crate::frontend::services::{ApiResponse, ConfigItem, Namespace, ApiClient, PublishConfigRequest, SseHandle};
+use crate::frontend::services::{ApiResponse, ChangeRequest, ConfigItem, ConfigListeners, Namespace, ApiClient, PublishConfigRequest, PublishBetaRequest, EventHandle, SubmitChangeRequest};
 #[cfg(target_arch = "wasm32")]
 use serde_json::Value;
 #[cfg(target_arch = "wasm32")]
@@ -33,6 +33,10 @@ pub fn Configs() -> impl IntoView {
     let (error, set_error) = create_signal::<Option<String>>(None);
     let (info, set_info) = create_signal::<Option<String>>(None);
     let (search, set_search) = create_signal::<String>(String::new());
+    let (filter_group, set_filter_group) = create_signal::<String>(String::new());
+    let (filter_app, set_filter_app) = create_signal::<String>(String::new());
+    let (filter_tags, set_filter_tags) = create_signal::<String>(String::new());
+    let (filter_format, set_filter_format) = create_signal::<String>(String::new());
     let (page, set_page) = create_signal::<u32>(1);
     let (size, set_size) = create_signal::<u32>(10);
     let (total_pages, set_total_pages) = create_signal::<u32>(0);
@@ -40,6 +44,7 @@ pub fn Configs() -> impl IntoView {
     let (view_open, set_view_open) = create_signal(false);
     let (view_content, set_view_content) = create_signal(String::new());
     let (view_type, set_view_type) = create_signal(String::from("text"));
+    let (view_listeners, set_view_listeners) = create_signal::<Option<ConfigListeners>>(None);
     let (history_open, set_history_open) = create_signal(false);
     let (history_items, set_history_items) = create_signal::<Vec<(i64, bool, String)>>(vec![]);
     let (left_ver, set_left_ver) = create_signal::<Option<i64>>(None);
@@ -49,7 +54,7 @@ pub fn Configs() -> impl IntoView {
     let (diff_open, set_diff_open) = create_signal(false);
     let (diff_html, set_diff_html) = create_signal(String::new());
     // SSE 句柄
-    let sse_handle = create_rw_signal::<Option<SseHandle>>(None);
+    let sse_handle = create_rw_signal::<Option<EventHandle>>(None);
 
     // 加载命名空间 + 当前命名空间配置
     spawn_local(async move {
@@ -66,14 +71,20 @@ pub fn Configs() -> impl IntoView {
         let set_configs_cloned = set_configs.clone();
         let set_error_cloned = set_error.clone();
         let keyword = search.get();
+        let group_kw = filter_group.get();
+        let app_kw = filter_app.get();
+        let tags_kw = filter_tags.get();
+        let format_kw = filter_format.get();
         let page_now = page.get();
         let size_now = size.get();
         let set_total_pages_cloned = set_total_pages.clone();
         let set_total_count_cloned = set_total_count.clone();
         spawn_local(async move {
             let url = format!(
-                "/nacos/v1/cs/configs/list?namespace={}&page={}&size={}&search={}",
-                ns, page_now, size_now, urlencoding::encode(&keyword)
+                "/nacos/v1/cs/configs/list?namespace={}&page={}&size={}&search={}&group={}&app_name={}&tags={}&format={}",
+                ns, page_now, size_now, urlencoding::encode(&keyword),
+                urlencoding::encode(&group_kw), urlencoding::encode(&app_kw),
+                urlencoding::encode(&tags_kw), urlencoding::encode(&format_kw)
             );
             let req = {
                 if let Some(token) = web_sys::window()
@@ -112,22 +123,45 @@ pub fn Configs() -> impl IntoView {
     // 初次加载 public
     load_configs(current_ns.get());
 
-    // 订阅配置变更：同命名空间则刷新列表
-    {
-        let current_ns_sig = current_ns.clone();
+    // 订阅当前命名空间的配置变更（服务端过滤），切换命名空间时重新订阅
+    let subscribe_ns = {
         let load_configs_cb = load_configs.clone();
-        match ApiClient::subscribe_events("config", move |msg| {
-            if let Ok(v) = serde_json::from_str::<Value>(&msg) {
-                let ns = v.get("namespace").and_then(|x| x.as_str()).unwrap_or_default().to_string();
-                if ns == current_ns_sig.get() {
-                    load_configs_cb(ns);
+        move |ns: String| {
+            let mut old = None;
+            sse_handle.update(|h| old = h.take());
+            if let Some(h) = old {
+                h.close();
+            }
+            let load_configs_cb = load_configs_cb.clone();
+            let ns_cb = ns.clone();
+            match ApiClient::subscribe_events_filtered("config", &[("namespace", ns.as_str())], move |msg| {
+                // 带操作类型的单条事件就地更新列表，其余（变更集、灰度、resync）整页重新加载
+                let v = serde_json::from_str::<Value>(&msg).unwrap_or_default();
+                let field = |name: &str| v.get(name).and_then(|x| x.as_str()).unwrap_or_default().to_string();
+                let (op, group, data_id) = (field("op"), field("group"), field("data_id"));
+                let same = |c: &ConfigItem| c.group == group && c.data_id == data_id;
+                let known = configs.get_untracked().iter().any(same);
+                match op.as_str() {
+                    "deleted" => set_configs.update(|list| list.retain(|c| !same(c))),
+                    "published" | "rolled_back" if known && v.get("content").is_some() => {
+                        let content = field("content");
+                        let revision = v.get("revision").and_then(|x| x.as_i64()).unwrap_or_default();
+                        set_configs.update(|list| {
+                            for c in list.iter_mut().filter(|c| same(c)) {
+                                c.content = content.clone();
+                                c.update_time = revision;
+                            }
+                        });
+                    }
+                    _ => load_configs_cb(ns_cb.clone()),
                 }
+            }) {
+                Ok(h) => sse_handle.set(Some(h)),
+                Err(e) => web_sys::console::error_1(&format!("SSE 订阅失败: {}", e).into()),
             }
-        }) {
-            Ok(h) => sse_handle.set(Some(h)),
-            Err(e) => web_sys::console::error_1(&format!("SSE 订阅失败: {}", e).into()),
         }
-    }
+    };
+    subscribe_ns(current_ns.get());
 
     // 辅助：类型到 CodeMirror/hljs 模式
     fn cm_mode(t: &str) -> &str {
@@ -151,10 +185,55 @@ pub fn Configs() -> impl IntoView {
         }
     }
 
+    // 变更审批
+    let (review_open, set_review_open) = create_signal(false);
+    let (review_items, set_review_items) = create_signal::<Vec<ChangeRequest>>(vec![]);
+    let (current_user, set_current_user) = create_signal(ApiClient::current_user().unwrap_or_default());
+    let (review_comment, set_review_comment) = create_signal(String::new());
+    // 命名空间开启审批时，新增 / 编辑改为提交变更请求
+    let needs_approval = move |ns: &str| namespaces.get().iter().any(|n| n.namespace == ns && n.require_approval);
+    let load_reviews = move |ns: String| {
+        spawn_local(async move {
+            match ApiClient::new().list_change_requests(&ns).await {
+                Ok(list) => set_review_items.set(list),
+                Err(e) => set_error.set(Some(format!("加载变更请求失败: {}", e))),
+            }
+        });
+    };
+    let on_review = {
+        let load_configs = load_configs.clone();
+        move |id: String, action: &'static str| {
+            let comment = Some(review_comment.get()).filter(|c| !c.trim().is_empty());
+            spawn_local(async move {
+                match ApiClient::new().review_change_request(&id, action, comment).await {
+                    Ok(cr) => {
+                        let msg = match action {
+                            "approve" => "已审批通过",
+                            "reject" => "已驳回",
+                            _ => "变更已发布",
+                        };
+                        set_info.set(Some(msg.to_string()));
+                        set_error.set(None);
+                        set_review_comment.set(String::new());
+                        load_reviews(cr.namespace.clone());
+                        if action == "apply" {
+                            load_configs(cr.namespace);
+                        }
+                    }
+                    Err(e) => set_error.set(Some(format!("操作失败: {}", e))),
+                }
+            });
+        }
+    };
+
     // 命名空间选择变更
     let on_change_ns = move |ns: String| {
         set_current_ns.set(ns.clone());
         set_page.set(1);
+        if review_open.get_untracked() {
+            load_reviews(ns.clone());
+        }
+        subscribe_ns(ns.clone());
         load_configs(ns);
     };
 
@@ -230,8 +309,32 @@ pub fn Configs() -> impl IntoView {
                 return;
             }
             let final_id = apply_ext(data_id, &cfg_type);
+            let approval = needs_approval(&ns_val);
             set_creating.set(true);
             spawn_local(async move {
+                if approval {
+                    let req = SubmitChangeRequest {
+                        data_id: final_id,
+                        group,
+                        content,
+                        namespace: Some(ns_val.clone()),
+                        comment: None,
+                    };
+                    match ApiClient::new().submit_change_request(req).await {
+                        Ok(_) => {
+                            set_info.set(Some("该命名空间需审批，已提交变更请求".to_string()));
+                            set_error.set(None);
+                            set_create_open.set(false);
+                            set_create_data_id.set(String::new());
+                            set_create_content.set(String::new());
+                            set_review_open.set(true);
+                            load_reviews(ns_val);
+                        }
+                        Err(e) => set_error.set(Some(format!("提交失败: {}", e))),
+                    }
+                    set_creating.set(false);
+                    return;
+                }
                 let req = PublishConfigRequest {
                     data_id: final_id.clone(),
                     group: group.clone(),
@@ -319,6 +422,16 @@ pub fn Configs() -> impl IntoView {
         set_edit_type.set(ty.to_string());
         set_edit_content.set(c.content.clone());
         set_edit_open.set(true);
+        // 加密配置在列表中是密文，编辑前单独读取明文
+        if c.data_key.is_some() {
+            spawn_local(async move {
+                match ApiClient::new().get_config(&c.data_id, &c.group, &c.namespace).await {
+                    Ok(Some(plain)) => set_edit_content.set(plain.content),
+                    Ok(None) => {}
+                    Err(e) => set_error.set(Some(format!("解密失败: {}", e))),
+                }
+            });
+        }
     };
     let open_view = move |c: ConfigItem| {
         // 推断语言
@@ -331,6 +444,17 @@ pub fn Configs() -> impl IntoView {
         set_view_type.set(ty.to_string());
         set_view_content.set(c.content.clone());
         set_view_open.set(true);
+        // 在线监听者
+        set_view_listeners.set(None);
+        {
+            let (data_id, group, ns) = (c.data_id.clone(), c.group.clone(), c.namespace.clone());
+            spawn_local(async move {
+                match ApiClient::new().list_listeners(&data_id, &group, &ns).await {
+                    Ok(l) => set_view_listeners.set(Some(l)),
+                    Err(e) => web_sys::console::error_1(&format!("加载监听者失败: {}", e).into()),
+                }
+            });
+        }
         // 延迟高亮
         gloo_timers::callback::Timeout::new(50, move || {
             if let Some(w) = web_sys::window() {
@@ -532,16 +656,85 @@ pub fn Configs() -> impl IntoView {
             });
         }
     };
+    // Nacos ZIP 导出（.meta.yml）
+    let on_export_zip = {
+        let current_ns = current_ns.clone();
+        move |_| {
+            let url = format!("/nacos/v1/cs/configs/export/zip?namespace={}&meta=v2", current_ns.get());
+            spawn_local(async move {
+                let req = match ApiClient::auth_header_value() {
+                    Some(v) => Request::get(&url).header("Authorization", &v),
+                    None => Request::get(&url),
+                };
+                if let Ok(resp) = req.send().await {
+                    if resp.ok() {
+                        if let Ok(bytes) = resp.binary().await {
+                            if let Some(w) = web_sys::window() {
+                                let arr = js_sys::Uint8Array::from(bytes.as_slice());
+                                let blob = web_sys::Blob::new_with_u8_array_sequence(&js_sys::Array::of1(&arr)).unwrap();
+                                let url = web_sys::Url::create_object_url_with_blob(&blob).unwrap();
+                                let document = w.document().unwrap();
+                                let a = document.create_element("a").unwrap();
+                                a.set_attribute("href", &url).unwrap();
+                                a.set_attribute("download", "nacos_config_export.zip").unwrap();
+                                let a: web_sys::HtmlElement = a.dyn_into().unwrap();
+                                a.click();
+                                let _ = web_sys::Url::revoke_object_url(&url);
+                            }
+                        }
+                    }
+                }
+            });
+        }
+    };
+    let (import_policy, set_import_policy) = create_signal::<String>("ABORT".to_string());
+    // 导入结果：成功/跳过/失败条数
+    let report_import = move |resp: gloo_net::http::Response| async move {
+        match resp.json::<ApiResponse<Value>>().await {
+            Ok(r) if r.code == 200 => {
+                let d = r.data.unwrap_or(Value::Null);
+                let n = |k: &str| d.get(k).and_then(|v| v.as_u64()).unwrap_or(0);
+                let msg = format!("导入完成：成功 {}，跳过 {}，失败 {}", n("succ_count"), n("skip_count"), n("fail_count"));
+                if n("fail_count") > 0 { set_error.set(Some(msg)); } else { set_info.set(Some(msg)); set_error.set(None); }
+                load_configs(current_ns.get_untracked());
+            }
+            Ok(r) => set_error.set(Some(format!("导入失败: {}", r.message))),
+            Err(e) => set_error.set(Some(format!("导入失败: {}", e))),
+        }
+    };
     let on_import = move |e: leptos::ev::Event| {
         let target: web_sys::EventTarget = event_target(&e);
         let input: web_sys::HtmlInputElement = target.unchecked_into();
         if let Some(file) = input.files().and_then(|fl| fl.get(0)) {
+            if file.name().ends_with(".zip") {
+                let url = format!(
+                    "/nacos/v1/cs/configs/import/zip?namespace={}&policy={}",
+                    current_ns.get_untracked(), import_policy.get_untracked()
+                );
+                let form = web_sys::FormData::new().unwrap();
+                form.append_with_blob("file", &file).unwrap();
+                spawn_local(async move {
+                    let req = match ApiClient::auth_header_value() {
+                        Some(v) => Request::post(&url).header("Authorization", &v),
+                        None => Request::post(&url),
+                    };
+                    match req.body(form) {
+                        Ok(req2) => match req2.send().await {
+                            Ok(resp) => report_import(resp).await,
+                            Err(e) => set_error.set(Some(format!("导入失败: {}", e))),
+                        },
+                        Err(e) => set_error.set(Some(format!("导入失败: {}", e))),
+                    }
+                });
+                return;
+            }
             let fr = web_sys::FileReader::new().unwrap();
             let onload = Closure::wrap(Box::new(move |ev: web_sys::Event| {
                 let fr: web_sys::FileReader = ev.target().unwrap().dyn_into().unwrap();
                 if let Ok(Some(text)) = fr.result().map(|r| r.as_string()) {
                     spawn_local(async move {
-                        let url = "/nacos/v1/cs/configs/import";
+                        let url = format!("/nacos/v1/cs/configs/import?policy={}", import_policy.get_untracked());
+                        let url = url.as_str();
                         let req = {
                             if let Some(token) = web_sys::window()
                                 .and_then(|w| w.local_storage().ok().flatten())
@@ -554,14 +747,7 @@ pub fn Configs() -> impl IntoView {
                         match req.body(text) {
                             Ok(req2) => {
                                 match req2.send().await {
-                                    Ok(resp) => {
-                                        if resp.ok() {
-                                            set_info.set(Some("导入完成".to_string()));
-                                            set_error.set(None);
-                                        } else {
-                                            set_error.set(Some("导入失败".to_string()));
-                                        }
-                                    }
+                                    Ok(resp) => report_import(resp).await,
                                     Err(e) => set_error.set(Some(format!("导入失败: {}", e))),
                                 }
                             }
@@ -591,10 +777,39 @@ pub fn Configs() -> impl IntoView {
                 return;
             }
             let final_id = apply_ext(data_id, &cfg_type);
+            let key_changed = !(orig_id == final_id && orig_group == group && orig_ns == ns_val);
+            if needs_approval(&ns_val) || needs_approval(&orig_ns) {
+                // 需审批的命名空间不能直接删除旧 key，改名须另行提交
+                if key_changed {
+                    set_error.set(Some("需审批的命名空间不支持修改数据ID、分组或命名空间".to_string()));
+                    return;
+                }
+                set_updating.set(true);
+                spawn_local(async move {
+                    let req = SubmitChangeRequest {
+                        data_id: final_id,
+                        group,
+                        content,
+                        namespace: Some(ns_val.clone()),
+                        comment: None,
+                    };
+                    match ApiClient::new().submit_change_request(req).await {
+                        Ok(_) => {
+                            set_info.set(Some("该命名空间需审批，已提交变更请求".to_string()));
+                            set_error.set(None);
+                            set_edit_open.set(false);
+                            set_review_open.set(true);
+                            load_reviews(ns_val);
+                        }
+                        Err(e) => set_error.set(Some(format!("提交失败: {}", e))),
+                    }
+                    set_updating.set(false);
+                });
+                return;
+            }
             set_updating.set(true);
             spawn_local(async move {
                 // 若 key 变化，先删旧，再发新；否则直接发布覆盖
-                let key_changed = !(orig_id == final_id && orig_group == group && orig_ns == ns_val);
                 if key_changed {
                     let _ = ApiClient::new().remove_config(&orig_id, &orig_group, &orig_ns).await;
                 }
@@ -619,6 +834,109 @@ pub fn Configs() -> impl IntoView {
         }
     };
 
+    // 灰度发布
+    let (beta_open, set_beta_open) = create_signal(false);
+    let (beta_tab, set_beta_tab) = create_signal::<&'static str>("beta");
+    let (beta_target, set_beta_target) = create_signal::<Option<ConfigItem>>(None);
+    let (beta_exists, set_beta_exists) = create_signal(false);
+    let (beta_ips, set_beta_ips) = create_signal(String::new());
+    let (beta_labels, set_beta_labels) = create_signal(String::new());
+    let (beta_content, set_beta_content) = create_signal(String::new());
+    let (beta_busy, set_beta_busy) = create_signal(false);
+    let open_beta = move |c: ConfigItem| {
+        set_beta_target.set(Some(c.clone()));
+        set_beta_tab.set("beta");
+        set_beta_exists.set(false);
+        set_beta_ips.set(String::new());
+        set_beta_labels.set(String::new());
+        set_beta_content.set(c.content.clone());
+        set_beta_open.set(true);
+        spawn_local(async move {
+            match ApiClient::new().get_beta(&c.data_id, &c.group, &c.namespace).await {
+                Ok(Some(b)) => {
+                    set_beta_exists.set(true);
+                    set_beta_ips.set(b.beta_ips.join(","));
+                    set_beta_labels.set(b.beta_labels.iter().map(|(k, v)| format!("{}={}", k, v)).collect::<Vec<_>>().join(","));
+                    set_beta_content.set(b.content);
+                }
+                Ok(None) => {}
+                Err(e) => set_error.set(Some(format!("加载灰度失败: {}", e))),
+            }
+        });
+    };
+    let on_publish_beta = move |_| {
+        let Some(c) = beta_target.get() else { return; };
+        let ips: Vec<String> = beta_ips.get().split(',').map(|s| s.trim().to_string()).filter(|s| !s.is_empty()).collect();
+        let labels: std::collections::HashMap<String, String> = beta_labels.get()
+            .split(',')
+            .filter_map(|kv| kv.split_once('='))
+            .map(|(k, v)| (k.trim().to_string(), v.trim().to_string()))
+            .filter(|(k, _)| !k.is_empty())
+            .collect();
+        if ips.is_empty() && labels.is_empty() {
+            set_error.set(Some("请至少填写一个灰度 IP 或标签".to_string()));
+            return;
+        }
+        let content = beta_content.get();
+        set_beta_busy.set(true);
+        spawn_local(async move {
+            let req = PublishBetaRequest {
+                data_id: c.data_id.clone(),
+                group: c.group.clone(),
+                content,
+                namespace: Some(c.namespace.clone()),
+                beta_ips: ips,
+                beta_labels: labels,
+            };
+            match ApiClient::new().publish_beta(req).await {
+                Ok(true) => {
+                    set_info.set(Some("灰度发布成功".to_string()));
+                    set_error.set(None);
+                    set_beta_exists.set(true);
+                }
+                Ok(false) => set_error.set(Some("灰度发布失败".to_string())),
+                Err(e) => set_error.set(Some(format!("灰度发布失败: {}", e))),
+            }
+            set_beta_busy.set(false);
+        });
+    };
+    let on_promote_beta = move |_| {
+        let Some(c) = beta_target.get() else { return; };
+        if !web_sys::window().and_then(|w| w.confirm_with_message("确定将灰度版本全量发布吗？").ok()).unwrap_or(false) {
+            return;
+        }
+        set_beta_busy.set(true);
+        spawn_local(async move {
+            match ApiClient::new().promote_beta(&c.data_id, &c.group, &c.namespace).await {
+                Ok(true) => {
+                    set_info.set(Some("灰度已全量发布".to_string()));
+                    set_error.set(None);
+                    set_beta_open.set(false);
+                    load_configs(c.namespace);
+                }
+                Ok(false) => set_error.set(Some("全量发布失败".to_string())),
+                Err(e) => set_error.set(Some(format!("全量发布失败: {}", e))),
+            }
+            set_beta_busy.set(false);
+        });
+    };
+    let on_stop_beta = move |_| {
+        let Some(c) = beta_target.get() else { return; };
+        set_beta_busy.set(true);
+        spawn_local(async move {
+            match ApiClient::new().stop_beta(&c.data_id, &c.group, &c.namespace).await {
+                Ok(_) => {
+                    set_info.set(Some("已停止灰度".to_string()));
+                    set_error.set(None);
+                    set_beta_exists.set(false);
+                    set_beta_content.set(c.content.clone());
+                }
+                Err(e) => set_error.set(Some(format!("停止灰度失败: {}", e))),
+            }
+            set_beta_busy.set(false);
+        });
+    };
+
     // 删除
     let on_delete = {
         let load_configs = load_configs.clone();
@@ -692,10 +1010,28 @@ pub fn Configs() -> impl IntoView {
                             <button class="btn btn-outline-secondary me-2" on:click=on_export>
                                 <i class="bi bi-download"></i> {" 导出"}
                             </button>
+                            <button class="btn btn-outline-secondary me-2" on:click=on_export_zip>
+                                <i class="bi bi-file-zip"></i> {" 导出 ZIP"}
+                            </button>
+                            <select class="form-select form-select-sm d-inline-block w-auto me-2" title="导入冲突策略"
+                                    on:change=move |ev| set_import_policy.set(event_target_value(&ev))>
+                                <option value="ABORT" selected=move || import_policy.get() == "ABORT">{"冲突终止"}</option>
+                                <option value="SKIP" selected=move || import_policy.get() == "SKIP">{"冲突跳过"}</option>
+                                <option value="OVERWRITE" selected=move || import_policy.get() == "OVERWRITE">{"冲突覆盖"}</option>
+                            </select>
                             <label class="btn btn-outline-secondary me-2 mb-0">
                                 <i class="bi bi-upload"></i> {" 导入"}
-                                <input type="file" accept=".json" style="display:none" on:change=on_import />
+                                <input type="file" accept=".json,.zip" style="display:none" on:change=on_import />
                             </label>
+                            <button class="btn btn-outline-warning me-2" on:click=move |_| {
+                                let open = !review_open.get();
+                                set_review_open.set(open);
+                                if open {
+                                    load_reviews(current_ns.get());
+                                }
+                            }>
+                                <i class="bi bi-check2-square"></i> {" 变更审批"}
+                            </button>
                             <button class="btn btn-primary" on:click=move |_| {
                                 set_create_ns.set(current_ns.get());
                                 set_create_open.set(true);
@@ -704,9 +1040,121 @@ pub fn Configs() -> impl IntoView {
                             </button>
                         </div>
                     </div>
+                    <div class="row g-2 align-items-end mt-1">
+                        <div class="col-md-3">
+                            <label class="form-label">{"分组（支持 * 通配）"}</label>
+                            <input class="form-control" placeholder="如 DEFAULT_*"
+                                   prop:value=filter_group
+                                   on:input=move |e| set_filter_group.set(event_target_value(&e)) />
+                        </div>
+                        <div class="col-md-3">
+                            <label class="form-label">{"应用名"}</label>
+                            <input class="form-control"
+                                   prop:value=filter_app
+                                   on:input=move |e| set_filter_app.set(event_target_value(&e)) />
+                        </div>
+                        <div class="col-md-3">
+                            <label class="form-label">{"标签（逗号分隔）"}</label>
+                            <input class="form-control" placeholder="db,prod"
+                                   prop:value=filter_tags
+                                   on:input=move |e| set_filter_tags.set(event_target_value(&e)) />
+                        </div>
+                        <div class="col-md-2">
+                            <label class="form-label">{"配置格式"}</label>
+                            <select class="form-select"
+                                    prop:value=filter_format
+                                    on:change=move |e| set_filter_format.set(event_target_value(&e))>
+                                <option value="">{"全部"}</option>
+                                <option value="text">{"TEXT"}</option>
+                                <option value="json">{"JSON"}</option>
+                                <option value="xml">{"XML"}</option>
+                                <option value="yaml">{"YAML"}</option>
+                                <option value="properties">{"Properties"}</option>
+                                <option value="html">{"HTML"}</option>
+                            </select>
+                        </div>
+                        <div class="col-md-1 text-end">
+                            <button class="btn btn-outline-primary w-100" on:click=move |_| { set_page.set(1); load_configs(current_ns.get()); }>
+                                <i class="bi bi-search"></i>
+                            </button>
+                        </div>
+                    </div>
                 </div>
             </div>
 
+            {move || if review_open.get() {
+                view!{
+                    <div class="card mb-3">
+                        <div class="card-header d-flex justify-content-between align-items-center">
+                            <span>{move || format!("变更审批 - {}", current_ns.get())}</span>
+                            <div class="d-flex align-items-center">
+                                <input class="form-control form-control-sm me-2" style="width:160px;" placeholder="当前用户"
+                                       prop:value=current_user
+                                       on:change=move |e| {
+                                           let name = event_target_value(&e);
+                                           ApiClient::set_current_user(&name);
+                                           set_current_user.set(name);
+                                       } />
+                                <button class="btn btn-sm btn-outline-secondary me-2" on:click=move |_| load_reviews(current_ns.get())>
+                                    <i class="bi bi-arrow-clockwise"></i>
+                                </button>
+                                <button class="btn btn-sm btn-secondary" on:click=move |_| set_review_open.set(false)>{"关闭"}</button>
+                            </div>
+                        </div>
+                        <div class="card-body">
+                            <input class="form-control form-control-sm mb-2" placeholder="审批意见（可选）"
+                                   prop:value=review_comment
+                                   on:input=move |e| set_review_comment.set(event_target_value(&e)) />
+                            {move || {
+                                let items = review_items.get();
+                                if items.is_empty() {
+                                    return view!{ <div class="text-muted">{"暂无变更请求"}</div> }.into_view();
+                                }
+                                let me = current_user.get();
+                                items.into_iter().map(|cr| {
+                                    let badge = match cr.state.as_str() {
+                                        "draft" => "bg-secondary",
+                                        "approved" => "bg-success",
+                                        "rejected" => "bg-danger",
+                                        _ => "bg-primary",
+                                    };
+                                    let can_review = cr.state == "draft" && cr.author != me;
+                                    let can_apply = cr.state == "approved";
+                                    let (id_a, id_r, id_p) = (cr.id.clone(), cr.id.clone(), cr.id.clone());
+                                    let diff = if cr.diff.is_empty() { "（加密配置不展示差异）".to_string() } else { cr.diff.clone() };
+                                    let review_line = cr.reviewer.clone().map(|r| {
+                                        format!("审批人: {}{}", r, cr.review_comment.clone().map(|c| format!("（{}）", c)).unwrap_or_default())
+                                    });
+                                    view!{
+                                        <div class="border rounded p-2 mb-2">
+                                            <div class="d-flex justify-content-between align-items-center">
+                                                <div>
+                                                    <span class={format!("badge {} me-2", badge)}>{cr.state.clone()}</span>
+                                                    <code>{format!("{} / {}", cr.group, cr.data_id)}</code>
+                                                    {if cr.create { view!{ <span class="badge bg-info text-dark ms-2">{"新建"}</span> }.into_view() } else { view!{<></>}.into_view() }}
+                                                    <span class="text-muted small ms-2">{format!("作者: {}", cr.author)}</span>
+                                                    {cr.comment.clone().map(|c| view!{ <span class="text-muted small ms-2">{c}</span> })}
+                                                </div>
+                                                <div>
+                                                    <button class="btn btn-sm btn-outline-success me-1" disabled=!can_review
+                                                            on:click=move |_| on_review(id_a.clone(), "approve")>{"通过"}</button>
+                                                    <button class="btn btn-sm btn-outline-danger me-1" disabled=!can_review
+                                                            on:click=move |_| on_review(id_r.clone(), "reject")>{"驳回"}</button>
+                                                    <button class="btn btn-sm btn-primary" disabled=!can_apply
+                                                            on:click=move |_| on_review(id_p.clone(), "apply")>{"发布"}</button>
+                                                </div>
+                                            </div>
+                                            {review_line.map(|l| view!{ <div class="small text-muted mt-1">{l}</div> })}
+                                            <pre class="bg-light small p-2 mt-2 mb-0" style="max-height:240px;overflow:auto;">{diff}</pre>
+                                        </div>
+                                    }
+                                }).collect_view()
+                            }}
+                        </div>
+                    </div>
+                }.into_view()
+            } else { view!{<></>}.into_view() }}
+
             {move || if create_open.get() {
                 view!{
                     <div class="card mb-3">
@@ -832,6 +1280,46 @@ pub fn Configs() -> impl IntoView {
                         </div>
                         <div class="card-body">
                             <pre><code id="viewCode" class={format!("language-{}", lang)}>{view_content.get()}</code></pre>
+                            <h6 class="mt-3">{"监听者"}</h6>
+                            {move || match view_listeners.get() {
+                                None => view!{ <div class="text-muted small">{"加载中..."}</div> }.into_view(),
+                                Some(l) if l.watchers.is_empty() => view!{ <div class="text-muted small">{"暂无客户端监听该配置"}</div> }.into_view(),
+                                Some(l) => view!{
+                                    <table class="table table-sm mb-0">
+                                        <thead>
+                                            <tr>
+                                                <th>{"客户端 IP"}</th>
+                                                <th>{"应用"}</th>
+                                                <th>{"方式"}</th>
+                                                <th>{"MD5"}</th>
+                                                <th>{"最近活跃"}</th>
+                                            </tr>
+                                        </thead>
+                                        <tbody>
+                                            {l.watchers.into_iter().map(|w| {
+                                                let status = match w.up_to_date {
+                                                    Some(true) => view!{ <span class="badge bg-success ms-1">{"最新"}</span> }.into_view(),
+                                                    Some(false) => view!{ <span class="badge bg-warning text-dark ms-1">{"过期"}</span> }.into_view(),
+                                                    None => view!{<></>}.into_view(),
+                                                };
+                                                let seen = js_sys::Date::new(&JsValue::from_f64(w.last_seen as f64 * 1000.0))
+                                                    .to_locale_string("zh-CN", &JsValue::UNDEFINED)
+                                                    .as_string()
+                                                    .unwrap_or_default();
+                                                view!{
+                                                    <tr>
+                                                        <td><code>{w.ip}</code></td>
+                                                        <td>{w.app_name.unwrap_or_default()}</td>
+                                                        <td>{w.transport}</td>
+                                                        <td><code class="small">{w.md5.unwrap_or_else(|| "-".into())}</code>{status}</td>
+                                                        <td>{seen}</td>
+                                                    </tr>
+                                                }
+                                            }).collect_view()}
+                                        </tbody>
+                                    </table>
+                                }.into_view(),
+                            }}
                         </div>
                     </div>
                 }.into_view()
@@ -934,6 +1422,71 @@ pub fn Configs() -> impl IntoView {
                 }.into_view()
             } else { view!{<></>}.into_view() }}
 
+            {move || if beta_open.get() {
+                let title = beta_target.get().map(|c| c.data_id).unwrap_or_default();
+                let release_content = beta_target.get().map(|c| c.content).unwrap_or_default();
+                view!{
+                    <div class="card mb-3">
+                        <div class="card-header d-flex justify-content-between align-items-center">
+                            <span>{format!("灰度发布 - {}", title)}</span>
+                            <button class="btn btn-sm btn-secondary" on:click=move |_| set_beta_open.set(false)>{"关闭"}</button>
+                        </div>
+                        <div class="card-body">
+                            <ul class="nav nav-tabs mb-3">
+                                <li class="nav-item">
+                                    <a class={move || format!("nav-link {}", if beta_tab.get() == "release" { "active" } else { "" })} href="#"
+                                       on:click=move |e| { e.prevent_default(); set_beta_tab.set("release"); }>{"正式版本"}</a>
+                                </li>
+                                <li class="nav-item">
+                                    <a class={move || format!("nav-link {}", if beta_tab.get() == "beta" { "active" } else { "" })} href="#"
+                                       on:click=move |e| { e.prevent_default(); set_beta_tab.set("beta"); }>
+                                        {"灰度版本 "}
+                                        {move || if beta_exists.get() { view!{<span class="badge bg-warning text-dark">{"进行中"}</span>}.into_view() } else { view!{<></>}.into_view() }}
+                                    </a>
+                                </li>
+                            </ul>
+                            {move || if beta_tab.get() == "release" {
+                                view!{ <pre><code>{release_content.clone()}</code></pre> }.into_view()
+                            } else {
+                                view!{
+                                    <div class="row g-2">
+                                        <div class="col-md-6">
+                                            <label class="form-label">{"灰度 IP（逗号分隔，支持 * 通配）"}</label>
+                                            <input class="form-control" placeholder="10.0.0.1,10.0.1.*"
+                                                   prop:value=beta_ips
+                                                   on:input=move |e| set_beta_ips.set(event_target_value(&e)) />
+                                        </div>
+                                        <div class="col-md-6">
+                                            <label class="form-label">{"灰度标签（k=v，逗号分隔）"}</label>
+                                            <input class="form-control" placeholder="env=gray,zone=a"
+                                                   prop:value=beta_labels
+                                                   on:input=move |e| set_beta_labels.set(event_target_value(&e)) />
+                                        </div>
+                                    </div>
+                                    <div class="mt-2">
+                                        <label class="form-label">{"灰度内容"}</label>
+                                        <textarea class="form-control" rows=10
+                                                  prop:value=beta_content
+                                                  on:input=move |e| set_beta_content.set(event_target_value(&e)) />
+                                    </div>
+                                    <div class="mt-3">
+                                        <button class="btn btn-warning me-2" on:click=on_publish_beta disabled=move || beta_busy.get()>
+                                            <i class="bi bi-broadcast"></i> {" 发布灰度"}
+                                        </button>
+                                        <button class="btn btn-primary me-2" on:click=on_promote_beta disabled=move || beta_busy.get() || !beta_exists.get()>
+                                            <i class="bi bi-check2-all"></i> {" 全量发布"}
+                                        </button>
+                                        <button class="btn btn-outline-danger" on:click=on_stop_beta disabled=move || beta_busy.get() || !beta_exists.get()>
+                                            <i class="bi bi-stop-circle"></i> {" 停止灰度"}
+                                        </button>
+                                    </div>
+                                }.into_view()
+                            }}
+                        </div>
+                    </div>
+                }.into_view()
+            } else { view!{<></>}.into_view() }}
+
             {move || if sbs_open.get() {
                 view!{
                     <div class="card mb-3">
@@ -965,13 +1518,14 @@ pub fn Configs() -> impl IntoView {
                                             <th>{"数据ID"}</th>
                                             <th>{"分组"}</th>
                                             <th>{"命名空间"}</th>
+                                            <th>{"标签"}</th>
                                             <th>{"更新时间"}</th>
                                             <th class="text-end">{"操作"}</th>
                                         </tr>
                                     </thead>
                                     <tbody>
                                         {if cfgs.is_empty() {
-                                            view! { <tr><td colspan="4" class="text-center text-muted">{"暂无配置"}</td></tr> }.into_view()
+                                            view! { <tr><td colspan="6" class="text-center text-muted">{"暂无配置"}</td></tr> }.into_view()
                                         } else {
                                             cfgs.into_iter().map(|c| {
                                                 let ts = js_sys::Date::new(&JsValue::from_f64(c.update_time as f64))
@@ -982,11 +1536,17 @@ pub fn Configs() -> impl IntoView {
                                                 let c_for_view = c.clone();
                                                 let c_for_history = c.clone();
                                                 let c_for_delete = c.clone();
+                                                let c_for_beta = c.clone();
                                                 view! {
                                                     <tr>
                                                         <td><code>{c.data_id}</code></td>
                                                         <td>{c.group}</td>
                                                         <td>{c.namespace.clone()}</td>
+                                                        <td>
+                                                            {c.config_tags.split(',').filter(|t| !t.is_empty()).map(|t| {
+                                                                view!{ <span class="badge bg-light text-dark border me-1">{t.to_string()}</span> }
+                                                            }).collect_view()}
+                                                        </td>
                                                         <td>{ts}</td>
                                                         <td class="text-end">
                                                             <button class="btn btn-sm btn-outline-secondary me-2"
@@ -997,6 +1557,10 @@ pub fn Configs() -> impl IntoView {
                                                                     on:click=move |_| open_history(c_for_history.clone())>
                                                                 <i class="bi bi-clock-history"></i> {" 历史"}
                                                             </button>
+                                                            <button class="btn btn-sm btn-outline-warning me-2"
+                                                                    on:click=move |_| open_beta(c_for_beta.clone())>
+                                                                <i class="bi bi-broadcast"></i> {" 灰度"}
+                                                            </button>
                                                             <button class="btn btn-sm btn-outline-primary me-2"
                                                                     on:click=move |_| open_edit(c_for_edit.clone())>
                                                                 <i class="bi bi-pencil"></i> {" 编辑"}
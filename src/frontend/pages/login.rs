@@ -0,0 +1,72 @@
+#[cfg(target_arch = "wasm32")]
+use leptos::*;
+#[cfg(target_arch = "wasm32")]
+use leptos_router::use_navigate;
+#[cfg(target_arch = "wasm32")]
+use crate::frontend::services::ApiClient;
+
+#[cfg(target_arch = "wasm32")]
+#[component]
+pub fn Login() -> impl IntoView {
+    let (username, set_username) = create_signal(ApiClient::current_user().unwrap_or_default());
+    let (password, set_password) = create_signal(String::new());
+    let (error, set_error) = create_signal::<Option<String>>(None);
+    let (submitting, set_submitting) = create_signal(false);
+
+    let on_submit = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+        let user = username.get();
+        let pass = password.get();
+        if user.trim().is_empty() || pass.is_empty() {
+            set_error.set(Some("请输入用户名和密码".to_string()));
+            return;
+        }
+        set_submitting.set(true);
+        set_error.set(None);
+        let navigate = use_navigate();
+        spawn_local(async move {
+            match ApiClient::new().login(user.trim(), &pass).await {
+                Ok(_) => navigate("/", Default::default()),
+                Err(e) => set_error.set(Some(e)),
+            }
+            set_submitting.set(false);
+        });
+    };
+
+    view! {
+        <div class="container mt-5">
+            <div class="row justify-content-center">
+                <div class="col-md-4">
+                    <div class="card shadow-sm">
+                        <div class="card-body">
+                            <h4 class="card-title mb-4">
+                                <i class="bi bi-box-arrow-in-right"></i> {" 登录"}
+                            </h4>
+                            {move || error.get().map(|e| view! { <div class="alert alert-danger">{e}</div> })}
+                            <form on:submit=on_submit>
+                                <div class="mb-3">
+                                    <label class="form-label">{"用户名"}</label>
+                                    <input type="text" class="form-control"
+                                        prop:value=username
+                                        on:input=move |ev| set_username.set(event_target_value(&ev)) />
+                                </div>
+                                <div class="mb-3">
+                                    <label class="form-label">{"密码"}</label>
+                                    <input type="password" class="form-control"
+                                        prop:value=password
+                                        on:input=move |ev| set_password.set(event_target_value(&ev)) />
+                                </div>
+                                <button type="submit" class="btn btn-primary w-100" disabled=submitting>
+                                    {move || if submitting.get() { "登录中..." } else { "登录" }}
+                                </button>
+                            </form>
+                        </div>
+                    </div>
+                </div>
+            </div>
+        </div>
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn Login() {}
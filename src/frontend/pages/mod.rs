@@ -2,8 +2,12 @@ pub mod dashboard;
 pub mod services;
 pub mod configs;
 pub mod namespaces;
+pub mod login;
+pub mod audit;
 
 pub use dashboard::Dashboard;
 pub use services::Services;
 pub use configs::Configs;
-pub use namespaces::Namespaces;
\ No newline at end of file
+pub use namespaces::Namespaces;
+pub use login::Login;
+pub use audit::Audit;
\ No newline at end of file
@@ -24,6 +24,8 @@ pub fn Namespaces() -> impl IntoView {
     let (edit_show_name, set_edit_show_name) = create_signal(String::new());
     let (edit_desc, set_edit_desc) = create_signal(String::new());
     let (edit_quota, set_edit_quota) = create_signal(String::new());
+    let (edit_require_approval, set_edit_require_approval) = create_signal(false);
+    let (edit_reviewers, set_edit_reviewers) = create_signal(String::new());
     let (updating, set_updating) = create_signal(false);
 
     // 加载命名空间
@@ -86,6 +88,8 @@ pub fn Namespaces() -> impl IntoView {
         set_edit_show_name.set(ns.namespace_show_name);
         set_edit_desc.set(ns.namespace_desc);
         set_edit_quota.set(ns.quota.to_string());
+        set_edit_require_approval.set(ns.require_approval);
+        set_edit_reviewers.set(ns.reviewers.join(","));
     };
 
     // 保存编辑
@@ -101,12 +105,21 @@ pub fn Namespaces() -> impl IntoView {
                 let desc = edit_desc.get();
                 let quota_text = edit_quota.get();
                 let quota_val = quota_text.parse::<u32>().ok();
+                let require_approval = edit_require_approval.get();
+                let reviewers: Vec<String> = edit_reviewers
+                    .get()
+                    .split(',')
+                    .map(|r| r.trim().to_string())
+                    .filter(|r| !r.is_empty())
+                    .collect();
                 set_updating.set(true);
                 spawn_local(async move {
                     let req = UpdateNamespaceRequest {
                         namespace_show_name: show.clone(),
                         namespace_desc: if desc.trim().is_empty() { None } else { Some(desc.clone()) },
                         quota: quota_val,
+                        require_approval: Some(require_approval),
+                        reviewers: Some(reviewers),
                     };
                     match ApiClient::new().update_namespace(&target, req).await {
                         Ok(true) => {
@@ -223,6 +236,7 @@ pub fn Namespaces() -> impl IntoView {
                                             <th>{"显示名称"}</th>
                                             <th>{"描述"}</th>
                                             <th>{"配额"}</th>
+                                            <th>{"变更审批"}</th>
                                             <th class="text-end">{"操作"}</th>
                                         </tr>
                                     </thead>
@@ -249,6 +263,18 @@ pub fn Namespaces() -> impl IntoView {
                                                                    prop:value=edit_quota
                                                                    on:input=move |e| set_edit_quota.set(event_target_value(&e)) />
                                                         </td>
+                                                        <td>
+                                                            <div class="form-check mb-1">
+                                                                <input class="form-check-input" type="checkbox"
+                                                                       prop:checked=edit_require_approval
+                                                                       on:change=move |e| set_edit_require_approval.set(event_target_checked(&e)) />
+                                                                <label class="form-check-label small">{"需审批"}</label>
+                                                            </div>
+                                                            <input class="form-control form-control-sm"
+                                                                   placeholder="审批人，逗号分隔；留空不限"
+                                                                   prop:value=edit_reviewers
+                                                                   on:input=move |e| set_edit_reviewers.set(event_target_value(&e)) />
+                                                        </td>
                                                         <td class="text-end">
                                                             <button class="btn btn-sm btn-primary me-2"
                                                                     disabled=move || updating.get()
@@ -268,6 +294,14 @@ pub fn Namespaces() -> impl IntoView {
                                                         <td>{ns.namespace_show_name}</td>
                                                         <td>{ns.namespace_desc}</td>
                                                         <td>{ns.quota}</td>
+                                                        <td>
+                                                            {if ns.require_approval {
+                                                                let who = if ns.reviewers.is_empty() { "任意他人".to_string() } else { ns.reviewers.join(", ") };
+                                                                view! { <span class="badge bg-warning text-dark" title=who>{"需审批"}</span> }.into_view()
+                                                            } else {
+                                                                view! { <span class="text-muted small">{"直接发布"}</span> }.into_view()
+                                                            }}
+                                                        </td>
                                                         <td class="text-end">
                                                             <button class="btn btn-sm btn-outline-primary me-2"
                                                                     on:click=move |_| open_edit(ns_clone_for_edit.clone())>
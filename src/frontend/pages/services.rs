@@ -2,7 +2,7 @@
 use leptos::*;
 use crate::frontend::components::Loading;
 #[cfg(target_arch = "wasm32")]
-use crate::frontend::services::{ApiClient, Instance, RegisterInstanceRequest, SseHandle};
+use crate::frontend::services::{ApiClient, Instance, RegisterInstanceRequest, EventHandle};
 #[cfg(target_arch = "wasm32")]
 use serde_json::Value;
  
@@ -20,7 +20,7 @@ pub fn Services() -> impl IntoView {
     let (detail_open, set_detail_open) = create_signal(false);
     let (detail_instance, set_detail_instance) = create_signal::<Option<Instance>>(None);
     // 保存 SSE 句柄，组件销毁时自动关闭
-    let sse_handle = create_rw_signal::<Option<SseHandle>>(None);
+    let sse_handle = create_rw_signal::<Option<EventHandle>>(None);
 
     // 初次加载服务列表
     spawn_local(async move {
@@ -109,10 +109,29 @@ pub fn Services() -> impl IntoView {
         match ApiClient::subscribe_events("instance", move |msg| {
             if let Ok(v) = serde_json::from_str::<Value>(&msg) {
                 let svc = v.get("service_name").and_then(|x| x.as_str()).unwrap_or_default().to_string();
-                reload_services_cb();
+                // resync：断线期间的事件已丢失，当前服务实例也需重新加载
+                let resync = v.get("topic").and_then(|x| x.as_str()) == Some("resync");
+                let op = v.get("op").and_then(|x| x.as_str()).unwrap_or_default().to_string();
+                let instance = v.get("instance").cloned().and_then(|i| serde_json::from_value::<Instance>(i).ok());
+                // 健康状态变化不影响服务列表
+                if op != "health_changed" {
+                    reload_services_cb();
+                }
                 if let Some(cur) = current_service_sig.get() {
-                    if cur == svc {
-                        reload_instances_cb();
+                    match instance {
+                        // 带实例详情的事件直接增量更新当前服务的实例列表
+                        Some(ins) if !resync && cur == svc => set_instances.update(|list| {
+                            match (list.iter().position(|i| i.id == ins.id), op.as_str()) {
+                                (Some(pos), "removed") => {
+                                    list.remove(pos);
+                                }
+                                (Some(pos), _) => list[pos] = ins,
+                                (None, "removed") => {}
+                                (None, _) => list.push(ins),
+                            }
+                        }),
+                        _ if resync || cur == svc => reload_instances_cb(),
+                        _ => {}
                     }
                 }
             } else {
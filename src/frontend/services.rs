@@ -4,7 +4,7 @@ use web_sys::window;
 #[cfg(target_arch = "wasm32")]
 use wasm_bindgen::{closure::Closure, JsCast, JsValue};
 #[cfg(target_arch = "wasm32")]
-use web_sys::EventSource;
+use web_sys::{EventSource, WebSocket};
 #[cfg(target_arch = "wasm32")]
 use js_sys::{Function, Reflect};
 
@@ -29,6 +29,31 @@ pub struct ConfigItem {
     pub content: String,
     pub namespace: String,
     pub update_time: i64,
+    #[serde(default)]
+    pub beta: bool,
+    #[serde(default)]
+    pub format: Option<String>,
+    #[serde(default)]
+    pub config_tags: String,
+    #[serde(default)]
+    pub desc: Option<String>,
+    #[serde(default)]
+    pub app_name: Option<String>,
+    /// 非空表示 content 为密文
+    #[serde(default)]
+    pub data_key: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BetaConfig {
+    pub data_id: String,
+    pub group: String,
+    pub namespace: String,
+    pub content: String,
+    pub format: Option<String>,
+    pub beta_ips: Vec<String>,
+    pub beta_labels: std::collections::HashMap<String, String>,
+    pub update_time: i64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -39,6 +64,112 @@ pub struct Namespace {
     pub quota: u32,
     pub create_time: i64,
     pub update_time: i64,
+    /// 开启后配置变更须提交变更请求并经审批
+    #[serde(default)]
+    pub require_approval: bool,
+    #[serde(default)]
+    pub reviewers: Vec<String>,
+}
+
+/// 审计记录，见 /nacos/v1/audit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub seq: u64,
+    pub at: String,
+    pub operator: String,
+    pub source_ip: String,
+    pub user_agent: String,
+    pub action: String,
+    pub resource: String,
+    pub before_hash: Option<String>,
+    pub after_hash: Option<String>,
+    pub success: bool,
+    pub message: Option<String>,
+    pub hash: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditPage {
+    pub total_count: usize,
+    pub page_number: usize,
+    pub page_size: usize,
+    pub items: Vec<AuditEntry>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct AuditQuery {
+    pub operator: String,
+    pub action: String,
+    pub resource: String,
+    /// "" / "true" / "false"
+    pub success: String,
+    pub page: usize,
+}
+
+/// 哈希链校验结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditChainReport {
+    pub total: usize,
+    pub intact: bool,
+    pub broken_at: Option<u64>,
+    pub reason: Option<String>,
+    pub head_hash: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangeRequest {
+    pub id: String,
+    pub data_id: String,
+    pub group: String,
+    pub namespace: String,
+    #[serde(default)]
+    pub format: Option<String>,
+    pub diff: String,
+    #[serde(default)]
+    pub create: bool,
+    /// draft / approved / rejected / published
+    pub state: String,
+    pub author: String,
+    #[serde(default)]
+    pub comment: Option<String>,
+    #[serde(default)]
+    pub reviewer: Option<String>,
+    #[serde(default)]
+    pub review_comment: Option<String>,
+    #[serde(default)]
+    pub published_by: Option<String>,
+    pub create_time: i64,
+    pub update_time: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigWatcher {
+    pub ip: String,
+    #[serde(default)]
+    pub app_name: Option<String>,
+    #[serde(default)]
+    pub md5: Option<String>,
+    /// sse / long_poll
+    pub transport: String,
+    pub last_seen: i64,
+    #[serde(default)]
+    pub up_to_date: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigListeners {
+    #[serde(default)]
+    pub md5: Option<String>,
+    pub watchers: Vec<ConfigWatcher>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SubmitChangeRequest {
+    pub data_id: String,
+    pub group: String,
+    pub content: String,
+    pub namespace: Option<String>,
+    pub comment: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -60,6 +191,16 @@ pub struct PublishConfigRequest {
     pub namespace: Option<String>,
 }
 
+#[derive(Debug, Serialize)]
+pub struct PublishBetaRequest {
+    pub data_id: String,
+    pub group: String,
+    pub content: String,
+    pub namespace: Option<String>,
+    pub beta_ips: Vec<String>,
+    pub beta_labels: std::collections::HashMap<String, String>,
+}
+
 #[derive(Debug, Serialize)]
 pub struct CreateNamespaceRequest {
     pub namespace: String,
@@ -72,11 +213,16 @@ pub struct UpdateNamespaceRequest {
     pub namespace_show_name: String,
     pub namespace_desc: Option<String>,
     pub quota: Option<u32>,
+    pub require_approval: Option<bool>,
+    pub reviewers: Option<Vec<String>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ApiResponse<T> {
     pub code: i32,
+    /// 失败时的稳定错误码（not_found / conflict / forbidden 等），code 与 HTTP 状态码一致
+    #[serde(default)]
+    pub error: Option<String>,
     pub message: String,
     pub data: Option<T>,
     pub timestamp: i64,
@@ -93,17 +239,38 @@ impl ApiClient {
         }
     }
 
-    // SSE 订阅（topic: "config"|"instance"）
+    // 事件订阅（topic: "config"|"instance"）
     #[cfg(target_arch = "wasm32")]
-    pub fn subscribe_events<F>(topic: &str, mut on_message: F) -> Result<SseHandle, String>
+    pub fn subscribe_events<F>(topic: &str, on_message: F) -> Result<EventHandle, String>
     where
         F: 'static + FnMut(String),
     {
-        let mut url = format!("/nacos/v1/events/stream?topic={}", topic);
-        if let Some(token) = window()
+        Self::subscribe_events_filtered(topic, &[], on_message)
+    }
+
+    /// 事件通道：localStorage `eventTransport` 为 `ws` 时使用 WebSocket（适用于会缓冲 SSE 的代理），默认 SSE
+    pub fn event_transport() -> String {
+        window()
             .and_then(|w| w.local_storage().ok().flatten())
-            .and_then(|s| s.get_item("accessToken").ok().flatten())
-        {
+            .and_then(|s| s.get_item("eventTransport").ok().flatten())
+            .filter(|t| t == "ws")
+            .unwrap_or_else(|| "sse".to_string())
+    }
+
+    /// 带服务端过滤的订阅：filters 为 namespace / group / data_id / service 及其通配模式
+    #[cfg(target_arch = "wasm32")]
+    pub fn subscribe_events_filtered<F>(topic: &str, filters: &[(&str, &str)], mut on_message: F) -> Result<EventHandle, String>
+    where
+        F: 'static + FnMut(String),
+    {
+        if Self::event_transport() == "ws" {
+            return Self::subscribe_events_ws(topic, filters, on_message);
+        }
+        let mut url = format!("/nacos/v1/events/stream?topic={}", topic);
+        for (name, pattern) in filters {
+            url.push_str(&format!("&{}={}", name, urlencoding::encode(pattern)));
+        }
+        if let Some(token) = access_token() {
             url.push_str(&format!("&access_token={}", urlencoding::encode(&token)));
         }
         let es = EventSource::new(&url).map_err(|e| format!("SSE 打开失败: {:?}", e))?;
@@ -115,37 +282,159 @@ impl ApiClient {
         es.set_onmessage(Some(onmsg.as_ref().unchecked_ref()));
         // onopen 提示
         let onopen = Closure::<dyn FnMut(web_sys::Event)>::wrap(Box::new(move |_e: web_sys::Event| {
-            if let Some(w) = window() {
-                if let Ok(f) = Reflect::get(&w, &JsValue::from_str("showToast")) {
-                    if let Ok(func) = f.dyn_into::<Function>() {
-                        let _ = func.call2(&JsValue::NULL, &JsValue::from_str("实时连接已建立"), &JsValue::from_str("success"));
-                    }
-                }
-            }
+            show_toast("实时连接已建立", "success");
         }));
         es.set_onopen(Some(onopen.as_ref().unchecked_ref()));
         // onerror 提示（EventSource 会自动重连）
         let onerror = Closure::<dyn FnMut(web_sys::Event)>::wrap(Box::new(move |_e: web_sys::Event| {
-            if let Some(w) = window() {
-                if let Ok(f) = Reflect::get(&w, &JsValue::from_str("showToast")) {
-                    if let Ok(func) = f.dyn_into::<Function>() {
-                        let _ = func.call2(&JsValue::NULL, &JsValue::from_str("实时连接断开，重试中…"), &JsValue::from_str("primary"));
+            show_toast("实时连接断开，重试中…", "primary");
+        }));
+        es.set_onerror(Some(onerror.as_ref().unchecked_ref()));
+        Ok(EventHandle { source: EventChannel::Sse(es), _onmessage: onmsg, _onopen: onopen, _onerror: onerror })
+    }
+
+    /// WebSocket 订阅：连接建立后按过滤条件发送 subscribe，推送内容与 SSE 相同
+    #[cfg(target_arch = "wasm32")]
+    fn subscribe_events_ws<F>(topic: &str, filters: &[(&str, &str)], mut on_message: F) -> Result<EventHandle, String>
+    where
+        F: 'static + FnMut(String),
+    {
+        let loc = window().ok_or("无 window")?.location();
+        let scheme = if loc.protocol().unwrap_or_default() == "https:" { "wss" } else { "ws" };
+        let mut url = format!("{}://{}/nacos/v1/events/ws", scheme, loc.host().unwrap_or_default());
+        if let Some(token) = access_token() {
+            url.push_str(&format!("?access_token={}", urlencoding::encode(&token)));
+        }
+        let ws = WebSocket::new(&url).map_err(|e| format!("WebSocket 打开失败: {:?}", e))?;
+        // 每个维度逗号分隔，多个模式按组合展开
+        let patterns = |name: &str| -> Vec<String> {
+            filters
+                .iter()
+                .find(|(n, _)| *n == name)
+                .map(|(_, v)| v.split(',').map(|p| p.trim().to_string()).filter(|p| !p.is_empty()).collect())
+                .filter(|v: &Vec<String>| !v.is_empty())
+                .unwrap_or_else(|| vec!["*".to_string()])
+        };
+        let subscribe = if topic == "instance" {
+            serde_json::json!({ "op": "subscribe", "services": patterns("service") })
+        } else {
+            let mut configs = Vec::new();
+            for ns in patterns("namespace") {
+                for group in patterns("group") {
+                    for data_id in patterns("data_id") {
+                        configs.push(serde_json::json!({ "namespace": ns, "group": group, "data_id": data_id }));
                     }
                 }
             }
+            serde_json::json!({ "op": "subscribe", "configs": configs })
+        }
+        .to_string();
+        let onmsg = Closure::<dyn FnMut(web_sys::MessageEvent)>::wrap(Box::new(move |e: web_sys::MessageEvent| {
+            if let Some(text) = e.data().as_string() {
+                // ack / pong / error 为控制消息，不转交页面
+                let control = serde_json::from_str::<serde_json::Value>(&text)
+                    .ok()
+                    .and_then(|v| v.get("topic").and_then(|t| t.as_str()).map(|t| matches!(t, "ack" | "pong" | "error")))
+                    .unwrap_or(false);
+                if !control {
+                    on_message(text);
+                }
+            }
         }));
-        es.set_onerror(Some(onerror.as_ref().unchecked_ref()));
-        Ok(SseHandle { es, _onmessage: onmsg, _onopen: onopen, _onerror: onerror })
+        ws.set_onmessage(Some(onmsg.as_ref().unchecked_ref()));
+        let ws_open = ws.clone();
+        let onopen = Closure::<dyn FnMut(web_sys::Event)>::wrap(Box::new(move |_e: web_sys::Event| {
+            let _ = ws_open.send_with_str(&subscribe);
+            show_toast("实时连接已建立（WebSocket）", "success");
+        }));
+        ws.set_onopen(Some(onopen.as_ref().unchecked_ref()));
+        // WebSocket 不会自动重连
+        let onerror = Closure::<dyn FnMut(web_sys::Event)>::wrap(Box::new(move |_e: web_sys::Event| {
+            show_toast("实时连接断开，请刷新页面", "primary");
+        }));
+        ws.set_onclose(Some(onerror.as_ref().unchecked_ref()));
+        Ok(EventHandle { source: EventChannel::Ws(ws), _onmessage: onmsg, _onopen: onopen, _onerror: onerror })
     }
 
  
-    fn auth_header_value() -> Option<String> {
+    pub fn auth_header_value() -> Option<String> {
         let storage = window()?.local_storage().ok()??;
         let token = storage.get_item("accessToken").ok()??;
         Some(format!("Bearer {}", token))
     }
 
+    /// 登录成功后保存令牌、过期时间与用户名
+    #[cfg(target_arch = "wasm32")]
+    pub async fn login(&self, username: &str, password: &str) -> Result<String, String> {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct LoginResponse {
+            access_token: String,
+            token_ttl: i64,
+        }
+        let url = format!("{}/auth/login", self.base_url);
+        let body = format!("username={}&password={}", urlencoding::encode(username), urlencoding::encode(password));
+        let response = Request::post(&url)
+            .header("Content-Type", "application/x-www-form-urlencoded")
+            .body(body)
+            .map_err(|e| format!("请求构造失败: {}", e))?
+            .send()
+            .await
+            .map_err(|e| format!("网络错误: {}", e))?;
+        if !response.ok() {
+            let result: ApiResponse<()> = response.json().await.map_err(|e| format!("解析响应失败: {}", e))?;
+            return Err(result.message);
+        }
+        let result: LoginResponse = response.json().await.map_err(|e| format!("解析响应失败: {}", e))?;
+        if let Some(storage) = window().and_then(|w| w.local_storage().ok().flatten()) {
+            let expires_at = js_sys::Date::now() as i64 + result.token_ttl * 1000;
+            let _ = storage.set_item("accessToken", &result.access_token);
+            let _ = storage.set_item("tokenExpiresAt", &expires_at.to_string());
+        }
+        Self::set_current_user(username);
+        Ok(result.access_token)
+    }
+
+    pub fn logout() {
+        if let Some(storage) = window().and_then(|w| w.local_storage().ok().flatten()) {
+            let _ = storage.remove_item("accessToken");
+            let _ = storage.remove_item("tokenExpiresAt");
+        }
+    }
+
+    /// 本地持有未过期的令牌
+    #[cfg(target_arch = "wasm32")]
+    pub fn logged_in() -> bool {
+        let Some(storage) = window().and_then(|w| w.local_storage().ok().flatten()) else {
+            return false;
+        };
+        let has_token = storage.get_item("accessToken").ok().flatten().is_some_and(|t| !t.is_empty());
+        let expires_at = storage
+            .get_item("tokenExpiresAt")
+            .ok()
+            .flatten()
+            .and_then(|v| v.parse::<i64>().ok())
+            .unwrap_or(i64::MAX);
+        has_token && (js_sys::Date::now() as i64) < expires_at
+    }
+
+    /// 当前操作人，作为变更请求的作者 / 审批人
+    pub fn current_user() -> Option<String> {
+        let storage = window()?.local_storage().ok()??;
+        storage.get_item("username").ok()?.filter(|u| !u.trim().is_empty())
+    }
+
+    pub fn set_current_user(name: &str) {
+        if let Some(storage) = window().and_then(|w| w.local_storage().ok().flatten()) {
+            let _ = storage.set_item("username", name.trim());
+        }
+    }
+
     fn with_auth(req: RequestBuilder) -> RequestBuilder {
+        let req = match Self::current_user() {
+            Some(user) => req.header("X-Nacos-User", &user),
+            None => req,
+        };
         if let Some(value) = Self::auth_header_value() {
             req.header("Authorization", &value)
         } else {
@@ -168,14 +457,11 @@ impl ApiClient {
             .await
         {
             Ok(response) => {
-                if response.ok() {
-                    let result: ApiResponse<bool> = response
-                        .json()
-                        .await
-                        .map_err(|e| format!("解析响应失败: {}", e))?;
-                    Ok(result.data.unwrap_or(false))
-                } else {
-                    Err(format!("请求失败: {}", response.status_text()))
+                let status_text = response.status_text();
+                match response.json::<ApiResponse<bool>>().await {
+                    Ok(result) if result.code == 200 => Ok(result.data.unwrap_or(false)),
+                    Ok(result) => Err(result.message),
+                    Err(_) => Err(format!("请求失败: {}", status_text)),
                 }
             }
             Err(e) => Err(format!("网络错误: {}", e)),
@@ -266,8 +552,29 @@ impl ApiClient {
         
         match Self::with_auth(Request::get(&url)).send().await {
             Ok(response) => {
-                let result: ApiResponse<Option<ConfigItem>> = response.json().await.map_err(|e| format!("解析响应失败: {}", e))?;
-                if result.code == 200 { Ok(result.data.unwrap_or(None)) } else { Err(result.message) }
+                let result: ApiResponse<ConfigItem> = response.json().await.map_err(|e| format!("解析响应失败: {}", e))?;
+                // 配置不存在时服务端返回 404 not_found
+                match (result.code, result.error.as_deref()) {
+                    (200, _) => Ok(result.data),
+                    (_, Some("not_found")) => Ok(None),
+                    _ => Err(result.message),
+                }
+            }
+            Err(e) => Err(format!("网络错误: {}", e)),
+        }
+    }
+
+    // 查询配置的在线监听者
+    pub async fn list_listeners(&self, data_id: &str, group: &str, namespace: &str) -> Result<ConfigListeners, String> {
+        let url = format!(
+            "{}/cs/configs/listener?data_id={}&group={}&namespace={}",
+            self.base_url, data_id, group, namespace
+        );
+
+        match Self::with_auth(Request::get(&url)).send().await {
+            Ok(response) => {
+                let result: ApiResponse<ConfigListeners> = response.json().await.map_err(|e| format!("解析响应失败: {}", e))?;
+                if result.code == 200 { result.data.ok_or_else(|| "响应为空".to_string()) } else { Err(result.message) }
             }
             Err(e) => Err(format!("网络错误: {}", e)),
         }
@@ -289,6 +596,65 @@ impl ApiClient {
         }
     }
 
+    // 获取灰度配置
+    pub async fn get_beta(&self, data_id: &str, group: &str, namespace: &str) -> Result<Option<BetaConfig>, String> {
+        let url = format!(
+            "{}/cs/configs/beta?data_id={}&group={}&namespace={}",
+            self.base_url, data_id, group, namespace
+        );
+
+        match Self::with_auth(Request::get(&url)).send().await {
+            Ok(response) => {
+                let result: ApiResponse<Option<BetaConfig>> = response.json().await.map_err(|e| format!("解析响应失败: {}", e))?;
+                if result.code == 200 { Ok(result.data.unwrap_or(None)) } else { Err(result.message) }
+            }
+            Err(e) => Err(format!("网络错误: {}", e)),
+        }
+    }
+
+    // 发布灰度配置
+    pub async fn publish_beta(&self, data: PublishBetaRequest) -> Result<bool, String> {
+        let url = format!("{}/cs/configs/beta", self.base_url);
+
+        match Self::with_auth(Request::post(&url)).json(&data).map_err(|e| format!("序列化失败: {}", e))?.send().await {
+            Ok(response) => {
+                let result: ApiResponse<bool> = response.json().await.map_err(|e| format!("解析响应失败: {}", e))?;
+                if result.code == 200 { Ok(result.data.unwrap_or(false)) } else { Err(result.message) }
+            }
+            Err(e) => Err(format!("网络错误: {}", e)),
+        }
+    }
+
+    // 灰度转正式
+    pub async fn promote_beta(&self, data_id: &str, group: &str, namespace: &str) -> Result<bool, String> {
+        let url = format!("{}/cs/configs/beta/promote", self.base_url);
+        let body = serde_json::json!({ "data_id": data_id, "group": group, "namespace": namespace });
+
+        match Self::with_auth(Request::post(&url)).json(&body).map_err(|e| format!("序列化失败: {}", e))?.send().await {
+            Ok(response) => {
+                let result: ApiResponse<bool> = response.json().await.map_err(|e| format!("解析响应失败: {}", e))?;
+                if result.code == 200 { Ok(result.data.unwrap_or(false)) } else { Err(result.message) }
+            }
+            Err(e) => Err(format!("网络错误: {}", e)),
+        }
+    }
+
+    // 停止灰度
+    pub async fn stop_beta(&self, data_id: &str, group: &str, namespace: &str) -> Result<bool, String> {
+        let url = format!(
+            "{}/cs/configs/beta?data_id={}&group={}&namespace={}",
+            self.base_url, data_id, group, namespace
+        );
+
+        match Self::with_auth(Request::delete(&url)).send().await {
+            Ok(response) => {
+                let result: ApiResponse<bool> = response.json().await.map_err(|e| format!("解析响应失败: {}", e))?;
+                if result.code == 200 { Ok(result.data.unwrap_or(false)) } else { Err(result.message) }
+            }
+            Err(e) => Err(format!("网络错误: {}", e)),
+        }
+    }
+
     // 列出配置
     pub async fn list_configs(&self, namespace: &str) -> Result<Vec<ConfigItem>, String> {
         let url = format!("{}/cs/configs/list?namespace={}", self.base_url, namespace);
@@ -302,6 +668,46 @@ impl ApiClient {
         }
     }
 
+    // 提交变更请求
+    pub async fn submit_change_request(&self, data: SubmitChangeRequest) -> Result<ChangeRequest, String> {
+        let url = format!("{}/cs/change-requests", self.base_url);
+
+        match Self::with_auth(Request::post(&url)).json(&data).map_err(|e| format!("序列化失败: {}", e))?.send().await {
+            Ok(response) => {
+                let result: ApiResponse<ChangeRequest> = response.json().await.map_err(|e| format!("解析响应失败: {}", e))?;
+                if result.code == 200 { result.data.ok_or_else(|| "响应为空".to_string()) } else { Err(result.message) }
+            }
+            Err(e) => Err(format!("网络错误: {}", e)),
+        }
+    }
+
+    // 列出变更请求
+    pub async fn list_change_requests(&self, namespace: &str) -> Result<Vec<ChangeRequest>, String> {
+        let url = format!("{}/cs/change-requests?namespace={}", self.base_url, namespace);
+
+        match Self::with_auth(Request::get(&url)).send().await {
+            Ok(response) => {
+                let result: ApiResponse<Vec<ChangeRequest>> = response.json().await.map_err(|e| format!("解析响应失败: {}", e))?;
+                if result.code == 200 { Ok(result.data.unwrap_or_default()) } else { Err(result.message) }
+            }
+            Err(e) => Err(format!("网络错误: {}", e)),
+        }
+    }
+
+    // 审批变更请求：action 为 approve / reject / apply
+    pub async fn review_change_request(&self, id: &str, action: &str, comment: Option<String>) -> Result<ChangeRequest, String> {
+        let url = format!("{}/cs/change-requests/{}/{}", self.base_url, id, action);
+        let body = serde_json::json!({ "comment": comment });
+
+        match Self::with_auth(Request::post(&url)).json(&body).map_err(|e| format!("序列化失败: {}", e))?.send().await {
+            Ok(response) => {
+                let result: ApiResponse<ChangeRequest> = response.json().await.map_err(|e| format!("解析响应失败: {}", e))?;
+                if result.code == 200 { result.data.ok_or_else(|| "响应为空".to_string()) } else { Err(result.message) }
+            }
+            Err(e) => Err(format!("网络错误: {}", e)),
+        }
+    }
+
     // 创建命名空间
     pub async fn create_namespace(&self, data: CreateNamespaceRequest) -> Result<bool, String> {
         let url = format!("{}/console/namespaces", self.base_url);
@@ -341,6 +747,37 @@ impl ApiClient {
         }
     }
 
+    // 查询审计日志，空条件不参与过滤
+    pub async fn list_audit(&self, q: &AuditQuery) -> Result<AuditPage, String> {
+        let mut url = format!("{}/audit?page={}&size=50", self.base_url, q.page.max(1));
+        for (name, value) in [("operator", &q.operator), ("action", &q.action), ("resource", &q.resource), ("success", &q.success)] {
+            if !value.trim().is_empty() {
+                url.push_str(&format!("&{}={}", name, urlencoding::encode(value.trim())));
+            }
+        }
+
+        match Self::with_auth(Request::get(&url)).send().await {
+            Ok(response) => {
+                let result: ApiResponse<AuditPage> = response.json().await.map_err(|e| format!("解析响应失败: {}", e))?;
+                if result.code == 200 { result.data.ok_or_else(|| "响应为空".to_string()) } else { Err(result.message) }
+            }
+            Err(e) => Err(format!("网络错误: {}", e)),
+        }
+    }
+
+    // 校验审计哈希链
+    pub async fn verify_audit(&self) -> Result<AuditChainReport, String> {
+        let url = format!("{}/audit/verify", self.base_url);
+
+        match Self::with_auth(Request::get(&url)).send().await {
+            Ok(response) => {
+                let result: ApiResponse<AuditChainReport> = response.json().await.map_err(|e| format!("解析响应失败: {}", e))?;
+                if result.code == 200 { result.data.ok_or_else(|| "响应为空".to_string()) } else { Err(result.message) }
+            }
+            Err(e) => Err(format!("网络错误: {}", e)),
+        }
+    }
+
     // 删除命名空间
     pub async fn delete_namespace(&self, namespace: &str) -> Result<bool, String> {
         let url = format!("{}/console/namespaces/{}", self.base_url, namespace);
@@ -356,16 +793,46 @@ impl ApiClient {
 }
 
 #[cfg(target_arch = "wasm32")]
-pub struct SseHandle {
-    es: EventSource,
+fn access_token() -> Option<String> {
+    window()
+        .and_then(|w| w.local_storage().ok().flatten())
+        .and_then(|s| s.get_item("accessToken").ok().flatten())
+}
+
+#[cfg(target_arch = "wasm32")]
+fn show_toast(msg: &str, kind: &str) {
+    if let Some(w) = window() {
+        if let Ok(f) = Reflect::get(&w, &JsValue::from_str("showToast")) {
+            if let Ok(func) = f.dyn_into::<Function>() {
+                let _ = func.call2(&JsValue::NULL, &JsValue::from_str(msg), &JsValue::from_str(kind));
+            }
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+enum EventChannel {
+    Sse(EventSource),
+    Ws(WebSocket),
+}
+
+#[cfg(target_arch = "wasm32")]
+pub struct EventHandle {
+    source: EventChannel,
     // 保持闭包不被回收
     _onmessage: Closure<dyn FnMut(web_sys::MessageEvent)>,
     _onopen: Closure<dyn FnMut(web_sys::Event)>,
     _onerror: Closure<dyn FnMut(web_sys::Event)>,
 }
 #[cfg(target_arch = "wasm32")]
-impl SseHandle {
+impl EventHandle {
     pub fn close(self) {
-        self.es.close();
+        match self.source {
+            EventChannel::Sse(es) => es.close(),
+            EventChannel::Ws(ws) => {
+                ws.set_onclose(None);
+                let _ = ws.close();
+            }
+        }
     }
 }
\ No newline at end of file